@@ -0,0 +1,23 @@
+// Exposes the target triple and compiler version to `AtBuildInfo` and
+// `env_report()`; both are only known to build scripts.
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let target = std::env::var("TARGET").unwrap_or_default();
+    println!("cargo:rustc-env=WHEREAT_TARGET={}", target);
+
+    // Only `env_report()` (std) reads the version; skip spawning rustc otherwise
+    if std::env::var_os("CARGO_FEATURE_STD").is_none() {
+        return;
+    }
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|v| v.trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=WHEREAT_RUSTC_VERSION={}", version);
+}
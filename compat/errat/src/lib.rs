@@ -0,0 +1,30 @@
+//! Compatibility facade: `errat` is the same crate as [`whereat`].
+//!
+//! Early docs and examples used the name `errat`. This crate re-exports the
+//! whole `whereat` API, including macros, so code written against either name
+//! compiles unchanged. Types are identical, not copies: an `errat::At<E>` *is*
+//! a `whereat::At<E>`.
+//!
+//! ```rust
+//! errat::define_at_crate_info!();
+//!
+//! use errat::prelude::*;
+//!
+//! #[derive(Debug)]
+//! struct MyError;
+//!
+//! fn load() -> AtResult<(), MyError> {
+//!     Err(at!(MyError)).at_str("loading")
+//! }
+//!
+//! fn main() {
+//!     let err: whereat::At<MyError> = load().unwrap_err();
+//!     assert_eq!(err.frame_count(), 1);
+//! }
+//! ```
+//!
+//! New code should depend on `whereat` directly.
+
+#![no_std]
+
+pub use whereat::*;
@@ -5,14 +5,19 @@
 
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
-use alloc::string::{String, ToString};
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::panic::Location;
 
-use crate::AtCrateInfo;
-use crate::context::{AtContext, AtContextRef};
-use crate::trace::{AtFrame, AtFrameOwned, AtTrace, AtTraceBoxed};
+use crate::context::{AtBytes, AtContext, AtContextRef, AtExpectedActual, AtLevel};
+use crate::localize::variant_name;
+use crate::trace::{
+    AtFrame, AtFrameOwned, AtOrder, AtTrace, AtTraceBoxed, AtTraceStats, CAPTURE_OFF, RepeatSuffix,
+    SkipMarker, SuppressedNote, try_box, write_backtrace, write_traceable_body,
+};
+use crate::{AtCrateInfo, AtTraceFormat};
 
 // ============================================================================
 // At<E> - Core wrapper type
@@ -87,6 +92,20 @@ pub struct At<E> {
     trace: AtTraceBoxed,
 }
 
+/// Shorthand for `Result<T, At<E>>`.
+///
+/// ```rust
+/// use whereat::{at, AtResult};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// fn load() -> AtResult<u32, MyError> {
+///     Err(at(MyError))
+/// }
+/// ```
+pub type AtResult<T, E> = Result<T, At<E>>;
+
 // ============================================================================
 // At<E> Implementation
 // ============================================================================
@@ -120,9 +139,31 @@ impl<E> At<E> {
         }
     }
 
-    /// Ensure trace exists, creating it if necessary.
-    fn ensure_trace(&mut self) -> &mut AtTrace {
-        self.trace.get_or_insert_mut()
+    /// The trace to record a new location or context into, created if necessary.
+    ///
+    /// `None` when capture is compiled out with the `off` feature or switched
+    /// off with [`set_enabled()`](crate::set_enabled), so callers return
+    /// before capturing a location or allocating.
+    ///
+    /// A sampled-out error still gets a trace on its first capture: it holds
+    /// the decision, so later frames don't start a partial trace.
+    #[inline]
+    fn capture_trace(&mut self) -> Option<&mut AtTrace> {
+        if !crate::config::is_enabled() {
+            return None;
+        }
+        self.edit_trace()
+    }
+
+    /// The trace to move existing frames or crate info into, created if necessary.
+    ///
+    /// `None` only when capture is compiled out with the `off` feature.
+    #[inline]
+    fn edit_trace(&mut self) -> Option<&mut AtTrace> {
+        if CAPTURE_OFF {
+            return None;
+        }
+        Some(self.trace.get_or_insert_mut())
     }
 
     /// Add the caller's location to the trace.
@@ -150,7 +191,9 @@ impl<E> At<E> {
     #[inline]
     pub fn at(mut self) -> Self {
         let loc = Location::caller();
-        let trace = self.trace.get_or_insert_mut();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
         let _ = trace.try_push(loc);
         self
     }
@@ -184,7 +227,9 @@ impl<E> At<E> {
         // Strip "::{{closure}}" suffix if present
         let name = full_name.strip_suffix("::{{closure}}").unwrap_or(full_name);
         let loc = Location::caller();
-        let trace = self.trace.get_or_insert_mut();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
         // First push a new location frame
         let _ = trace.try_push(loc);
         // Then add function name context to that frame
@@ -220,7 +265,9 @@ impl<E> At<E> {
     #[inline]
     pub fn at_named(mut self, name: &'static str) -> Self {
         let loc = Location::caller();
-        let trace = self.trace.get_or_insert_mut();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
         // Push a new location frame
         let _ = trace.try_push(loc);
         // Add the name as function-name-style context
@@ -276,9 +323,47 @@ impl<E> At<E> {
     #[inline]
     pub fn at_str(mut self, msg: &'static str) -> Self {
         let loc = Location::caller();
-        let context = AtContext::Text(Cow::Borrowed(msg));
-        let trace = self.trace.get_or_insert_mut();
-        trace.try_add_context(loc, context);
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Text(Cow::Borrowed(msg))));
+        self
+    }
+
+    /// Add a static string context to the oldest (origin) frame.
+    ///
+    /// Explanatory text about where an error started is often only known higher
+    /// up the stack. This attaches it to the first frame no matter where it is
+    /// called. If the trace is empty, creates a frame at the caller's location.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// struct ParseError;
+    ///
+    /// fn parse_line() -> Result<(), At<ParseError>> {
+    ///     Err(at(ParseError))
+    /// }
+    ///
+    /// fn load_config() -> Result<(), At<ParseError>> {
+    ///     parse_line().at().at_origin_str("while parsing config")
+    /// }
+    ///
+    /// let err = load_config().unwrap_err();
+    /// let origin = err.frames().next().unwrap();
+    /// assert_eq!(origin.contexts().next().unwrap().as_text(), Some("while parsing config"));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_origin_str(mut self, msg: &'static str) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_origin_context(loc, || AtContext::Text(Cow::Borrowed(msg)));
         self
     }
 
@@ -313,12 +398,24 @@ impl<E> At<E> {
     #[inline]
     pub fn at_string(mut self, f: impl FnOnce() -> String) -> Self {
         let loc = Location::caller();
-        let context = AtContext::Text(Cow::Owned(f()));
-        let trace = self.trace.get_or_insert_mut();
-        trace.try_add_context(loc, context);
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Text(Cow::Owned(f()))));
         self
     }
 
+    /// Used by [`err_at!`](crate::err_at): borrows a literal message, formats otherwise.
+    #[doc(hidden)]
+    #[track_caller]
+    #[inline]
+    pub fn __at_fmt(self, args: fmt::Arguments<'_>) -> Self {
+        match args.as_str() {
+            Some(msg) => self.at_str(msg),
+            None => self.at_string(|| alloc::fmt::format(args)),
+        }
+    }
+
     /// Add lazily-computed typed context (Display) to the last location frame.
     ///
     /// **Does not add a new location frame** - attaches context to the most recent
@@ -364,10 +461,10 @@ impl<E> At<E> {
         f: impl FnOnce() -> T,
     ) -> Self {
         let loc = Location::caller();
-        let ctx = f();
-        let context = AtContext::Display(Box::new(ctx));
-        let trace = self.trace.get_or_insert_mut();
-        trace.try_add_context(loc, context);
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Display(Box::new(f()))));
         self
     }
 
@@ -409,10 +506,158 @@ impl<E> At<E> {
         f: impl FnOnce() -> T,
     ) -> Self {
         let loc = Location::caller();
-        let ctx = f();
-        let context = AtContext::Debug(Box::new(ctx));
-        let trace = self.trace.get_or_insert_mut();
-        trace.try_add_context(loc, context);
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Debug(Box::new(f()))));
+        self
+    }
+
+    /// Add lazily-computed sensitive context, printed as `[redacted]`.
+    ///
+    /// Like [`at_debug()`](Self::at_debug), but every formatter (`Debug`,
+    /// `full_trace()`, JSON, serde, `tracing` events) writes `[redacted]` in
+    /// place of the value, so SQL, user identifiers and similar data stay out
+    /// of logs. The value is still available to code through
+    /// [`find_context()`](Self::find_context), and is printed with `Debug` by
+    /// [`display_unredacted()`](Self::display_unredacted).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct QueryFailed;
+    ///
+    /// let err = at(QueryFailed).at_secret(|| "SELECT * FROM users WHERE email = 'a@b.c'");
+    ///
+    /// assert!(format!("{:?}", err).contains("╰─ [redacted]\n"));
+    /// assert!(err.display_unredacted().to_string().contains("a@b.c"));
+    /// assert!(err.find_context::<&str>().unwrap().contains("a@b.c"));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_secret<T: fmt::Debug + Send + Sync + 'static>(
+        mut self,
+        f: impl FnOnce() -> T,
+    ) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Secret(Box::new(f()))));
+        self
+    }
+
+    /// Attach an expected and an actual value to the last location frame.
+    ///
+    /// Both values are stored typed (see [`AtExpectedActual`]) and rendered as
+    /// an aligned `expected:` / `actual:` block. For two strings, a caret marks
+    /// the first difference. If the trace is empty, creates a frame at the
+    /// caller's location first.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct SchemaMismatch;
+    ///
+    /// let err = at(SchemaMismatch).at_expected_actual("user_id", "userid".to_string());
+    /// let output = format!("{:?}", err);
+    /// assert!(output.contains(
+    ///     "╰─ expected: \"user_id\"\n          actual:   \"userid\"\n                         ^\n"
+    /// ));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_expected_actual<T, U>(mut self, expected: T, actual: U) -> Self
+    where
+        T: fmt::Debug + Send + Sync + 'static,
+        U: fmt::Debug + Send + Sync + 'static,
+    {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || {
+            let pair = AtExpectedActual::new(expected, actual);
+            try_box(pair).map(AtContext::ExpectedActual)
+        });
+        self
+    }
+
+    /// Attach a trace received from another process to the last location frame.
+    ///
+    /// For errors that cross an FFI, RPC or worker-process boundary: the other
+    /// side sends its trace as an [`AtTraceDetached`](crate::AtTraceDetached)
+    /// (via [`AtTrace::to_detached()`](crate::AtTrace::to_detached), `serde`
+    /// or `persist`), and this side wraps it into its own error. Formatters
+    /// render it as an indented `remote trace:` block under the frame. If the
+    /// trace is empty, creates a frame at the caller's location first.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtFrameDetached, AtTraceDetached};
+    ///
+    /// #[derive(Debug)]
+    /// struct WorkerFailed;
+    ///
+    /// let remote = AtTraceDetached::from_frames([
+    ///     AtFrameDetached::new("src/worker.rs", 42, 9).with_context("decoding frame 7"),
+    /// ]);
+    /// let err = at(WorkerFailed).at_remote_trace(remote);
+    /// let output = format!("{:?}", err);
+    /// assert!(output.contains(
+    ///     "╰─ remote trace:\n          at src/worker.rs:42:9\n             ╰─ decoding frame 7\n"
+    /// ));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_remote_trace(mut self, trace: crate::AtTraceDetached) -> Self {
+        let loc = Location::caller();
+        let Some(at_trace) = self.capture_trace() else {
+            return self;
+        };
+        at_trace.try_add_context_with(loc, || try_box(trace).map(AtContext::Remote));
+        self
+    }
+
+    /// Attach a copy of raw bytes to the last location frame.
+    ///
+    /// Copies at most [`AT_MAX_CONTEXT_BYTES`](crate::AT_MAX_CONTEXT_BYTES) and
+    /// records the full length (see [`AtBytes`]). Trace formatters render a
+    /// hex/ASCII dump; the `serde` output carries base64. Meant for parsers,
+    /// where the offending input is the most useful context. If the trace is
+    /// empty, creates a frame at the caller's location first.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct BadMagic;
+    ///
+    /// let err = at(BadMagic).at_bytes("magic", b"\x89PNX");
+    /// let output = format!("{:?}", err);
+    /// assert!(output.contains("╰─ magic (4 bytes):\n          0000  89 50 4e 58 "));
+    /// assert!(output.contains("  |.PNX|\n"));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_bytes(mut self, label: &'static str, bytes: &[u8]) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || {
+            let bytes = AtBytes::try_copy_from(label, bytes)?;
+            try_box(bytes).map(AtContext::Bytes)
+        });
         self
     }
 
@@ -442,9 +687,10 @@ impl<E> At<E> {
     #[inline]
     pub fn at_error<Err: core::error::Error + Send + Sync + 'static>(mut self, err: Err) -> Self {
         let loc = Location::caller();
-        let context = AtContext::Error(Box::new(err));
-        let trace = self.trace.get_or_insert_mut();
-        trace.try_add_context(loc, context);
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Error(Box::new(err))));
         self
     }
 
@@ -480,159 +726,803 @@ impl<E> At<E> {
     #[inline]
     pub fn at_crate(mut self, info: &'static AtCrateInfo) -> Self {
         let loc = Location::caller();
-        let trace = self.trace.get_or_insert_mut();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
         trace.try_add_crate_boundary(loc, info);
         self
     }
 
-    /// Add a skip marker (`[...]`) to the trace.
+    /// Attach an externally supplied correlation/request id to the last location frame.
     ///
-    /// Use this to indicate that some frames were skipped, either because
-    /// tracing started late in the call stack or because intermediate frames
-    /// are not meaningful.
-    #[doc(hidden)]
-    #[inline]
-    pub fn at_skipped_frames(mut self) -> Self {
-        let trace = self.trace.get_or_insert_mut();
-        let _ = trace.try_push_skipped();
-        self
-    }
-
-    /// Set the crate info for this trace.
+    /// Use this to join a trace with distributed tracing or request logs. The id is
+    /// stored as an [`AtCorrelationId`](crate::AtCorrelationId) context, shown by all
+    /// trace formatters, and returned by [`correlation_id()`](Self::correlation_id).
     ///
-    /// This is used by `at!()` to provide repository metadata for GitHub links.
-    /// Calling this creates the trace if it doesn't exist yet.
+    /// Like crate boundaries, it is kept under the `trace-locations-only` feature,
+    /// and `to_json()` and `serde` output carry it as a top-level `"correlation_id"`.
     ///
     /// ## Example
     ///
-    /// ```rust,ignore
-    /// // Requires define_at_crate_info!() setup
-    /// whereat::define_at_crate_info!();
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
     ///
     /// #[derive(Debug)]
-    /// enum MyError { Oops }
+    /// struct MyError;
     ///
-    /// let err = At::wrap(MyError::Oops)
-    ///     .set_crate_info(crate::at_crate_info())
-    ///     .at();
+    /// fn handle(request_id: &str) -> Result<(), At<MyError>> {
+    ///     Err(at(MyError)).at_correlation(request_id.to_string())
+    /// }
+    ///
+    /// let err = handle("req-42").unwrap_err();
+    /// assert_eq!(err.correlation_id(), Some("req-42"));
     /// ```
+    #[track_caller]
     #[inline]
-    pub fn set_crate_info(mut self, info: &'static AtCrateInfo) -> Self {
-        let trace = self.trace.get_or_insert_mut();
-        trace.set_crate_info(info);
+    pub fn at_correlation(mut self, id: impl Into<Cow<'static, str>>) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        let context = AtContext::Display(Box::new(crate::AtCorrelationId::new(id)));
+        trace.try_add_context(loc, context);
         self
     }
 
-    /// Get the crate info for this trace, if set.
-    #[inline]
-    pub fn crate_info(&self) -> Option<&'static AtCrateInfo> {
-        self.trace.as_ref().and_then(|t| t.crate_info())
-    }
-
-    /// Get a reference to the inner error.
-    #[inline]
-    pub fn error(&self) -> &E {
-        &self.error
-    }
-
-    /// Get a mutable reference to the inner error.
-    #[inline]
-    pub fn error_mut(&mut self) -> &mut E {
-        &mut self.error
-    }
-
-    /// Consume self and return the inner error, discarding the trace.
-    #[inline]
-    pub fn into_inner(self) -> E {
-        self.error
-    }
-
-    /// Check if the trace is empty.
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.trace.is_empty()
-    }
-
-    /// Iterate over all traced locations, oldest first.
-    ///
-    /// Skipped frame markers (`[...]`) are not included in this iteration.
-    /// Use [`frames()`](Self::frames) for full iteration including contexts.
-    #[inline]
-    #[allow(dead_code)] // Used in tests
-    pub(crate) fn locations(&self) -> impl Iterator<Item = &'static Location<'static>> + '_ {
-        self.trace
-            .as_ref()
-            .into_iter()
-            .flat_map(|t| t.iter())
-            .flatten() // Filter out None (skipped frame markers)
-    }
-
-    /// Get the first (oldest) location in the trace, if any.
-    #[inline]
-    #[allow(dead_code)] // Used in tests
-    pub(crate) fn first_location(&self) -> Option<&'static Location<'static>> {
-        self.locations().next()
-    }
-
-    /// Get the last (most recent) location in the trace, if any.
-    #[inline]
-    #[allow(dead_code)] // Used in tests
-    pub(crate) fn last_location(&self) -> Option<&'static Location<'static>> {
-        self.locations().last()
-    }
-
-    /// Get a reference to the underlying trace, if any.
-    #[inline]
-    #[allow(dead_code)] // Used in format module
-    pub(crate) fn trace_ref(&self) -> Option<&AtTrace> {
-        self.trace.as_ref()
-    }
-
-    /// Iterate over all context entries, newest first.
-    ///
-    /// Each call to `at_str()`, `at_string()`, `at_data()`, or `at_debug()` creates
-    /// a context entry. Use [`AtContextRef`] methods to inspect context data.
+    /// Attach a `key=value` pair to the last location frame.
     ///
-    /// **Note:** Prefer [`frames()`](Self::frames) for unified iteration over
-    /// locations with their contexts.
+    /// The value is formatted with `Display` right away. Pairs render as
+    /// `key=value` and are returned by [`AtContextRef::as_kv()`]. If the trace
+    /// is empty, creates a frame at the caller's location first.
     ///
     /// ## Example
     ///
     /// ```rust
-    /// use whereat::at;
+    /// use whereat::{at, At, ResultAtExt};
     ///
     /// #[derive(Debug)]
     /// struct MyError;
     ///
-    /// let err = at(MyError)
-    ///     .at_str("loading config")
-    ///     .at_str("initializing");
+    /// fn load(user_id: u64) -> Result<(), At<MyError>> {
+    ///     Err(at(MyError)).at_kv("user_id", user_id)
+    /// }
     ///
-    /// let texts: Vec<_> = err.contexts()
-    ///     .filter_map(|ctx| ctx.as_text())
-    ///     .collect();
-    /// assert_eq!(texts, vec!["initializing", "loading config"]); // newest first
+    /// let err = load(42).unwrap_err();
+    /// assert!(format!("{:?}", err).contains("╰─ user_id=42"));
     /// ```
+    #[track_caller]
     #[inline]
-    pub fn contexts(&self) -> impl Iterator<Item = AtContextRef<'_>> {
-        self.trace.as_ref().into_iter().flat_map(|t| t.contexts())
+    pub fn at_kv(mut self, key: &'static str, value: impl fmt::Display) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || {
+            Some(AtContext::KeyValue {
+                key: Cow::Borrowed(key),
+                value: Cow::Owned(alloc::format!("{}", value)),
+            })
+        });
+        self
     }
 
-    /// Iterate over frames (location + contexts pairs), oldest first.
+    /// Add a [`Note`](AtLevel::Note)-level breadcrumb to the last location frame.
     ///
-    /// This is the recommended way to traverse a trace. Each frame contains
-    /// a location (or None for skipped-frames marker) and its associated contexts.
+    /// Notes render as `note: msg` and can be hidden with
+    /// [`AtTraceFormat::min_level()`] while staying attached to the trace.
     ///
     /// ## Example
     ///
     /// ```rust
-    /// use whereat::at;
+    /// use whereat::{at, AtLevel, AtTraceFormat};
     ///
     /// #[derive(Debug)]
     /// struct MyError;
     ///
-    /// let err = at(MyError)
-    ///     .at_str("loading config")
+    /// let err = at(MyError).at_note("checked 3 mirrors");
+    /// assert!(format!("{:?}", err).contains("╰─ note: checked 3 mirrors"));
+    ///
+    /// let quiet = AtTraceFormat::new().min_level(AtLevel::Warn);
+    /// assert!(!err.debug_with(quiet).to_string().contains("mirrors"));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_note(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Leveled(AtLevel::Note, msg.into())));
+        self
+    }
+
+    /// Add a [`Warn`](AtLevel::Warn)-level context to the last location frame.
+    ///
+    /// Renders as `warn: msg`. See [`at_note()`](Self::at_note).
+    #[track_caller]
+    #[inline]
+    pub fn at_warn(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Leveled(AtLevel::Warn, msg.into())));
+        self
+    }
+
+    /// Attach a "see also" link (runbook, dashboard, docs) to the last location frame.
+    ///
+    /// Renders as `see: label (url)`; the HTML formatter emits an `<a>` element.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// struct QueueFull;
+    ///
+    /// fn enqueue() -> Result<(), At<QueueFull>> {
+    ///     Err(at(QueueFull)).at_link("runbook", "https://wiki.example.com/queue-full")
+    /// }
+    ///
+    /// let err = enqueue().unwrap_err();
+    /// assert!(format!("{:?}", err).contains("╰─ see: runbook (https://wiki.example.com/queue-full)"));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_link(
+        mut self,
+        label: impl Into<Cow<'static, str>>,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || {
+            Some(AtContext::Link {
+                label: label.into(),
+                url: url.into(),
+            })
+        });
+        self
+    }
+
+    /// Attach an actionable remediation hint to the last location frame.
+    ///
+    /// Hints are kept apart from diagnostic context: formatters collect them into a
+    /// `help:` section after the trace instead of listing them under a frame.
+    /// Read them back with [`suggestions()`](Self::suggestions).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// struct NotInitialized;
+    ///
+    /// fn open_repo() -> Result<(), At<NotInitialized>> {
+    ///     Err(at(NotInitialized)).at_help("try running `init` first")
+    /// }
+    ///
+    /// let err = open_repo().unwrap_err();
+    /// assert!(format!("{:?}", err).contains("help: try running `init` first"));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_help(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Suggestion(msg.into())));
+        self
+    }
+
+    /// Capture a full `std::backtrace::Backtrace` and attach it to the last location frame.
+    ///
+    /// Meant for the boundary of rare, hard-to-reproduce errors: cheap `.at()`
+    /// frames along the hot path, one full backtrace where the error is
+    /// handled. The backtrace is always captured, regardless of
+    /// `RUST_BACKTRACE`, and formatters print it after the frames. An error
+    /// keeps only its first backtrace; later calls add nothing. Requires the
+    /// `std-backtrace` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::backtrace::BacktraceStatus;
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct Corrupted;
+    ///
+    /// let err = at(Corrupted).at_str("reading index").at_backtrace().at_backtrace();
+    /// let backtrace = err.backtrace().unwrap();
+    /// assert_ne!(backtrace.status(), BacktraceStatus::Disabled);
+    /// assert_eq!(format!("{:?}", err).matches("\n    backtrace:\n").count(), 1);
+    /// ```
+    #[cfg(feature = "std-backtrace")]
+    #[track_caller]
+    #[inline]
+    pub fn at_backtrace(mut self) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        if !trace.has_backtrace() {
+            trace.try_add_context_with(loc, || {
+                let backtrace = try_box(std::backtrace::Backtrace::force_capture())?;
+                Some(AtContext::Backtrace(backtrace))
+            });
+        }
+        self
+    }
+
+    /// Attach a high-priority message that formatters show under the error header.
+    ///
+    /// The message stays attached to the last location frame, but `Debug`,
+    /// `full_trace()` and the other formatters print it directly below the
+    /// error line instead of under that frame, so the most important detail
+    /// isn't buried mid-trace. Read them back with [`pinned()`](Self::pinned).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// struct QuotaExceeded;
+    ///
+    /// fn upload() -> Result<(), At<QuotaExceeded>> {
+    ///     Err(at(QuotaExceeded))
+    ///         .at_str("writing chunk 3")
+    ///         .at_pinned("tenant acme is on the free plan")
+    /// }
+    ///
+    /// let err = upload().unwrap_err().at().at_str("handling request");
+    /// let output = format!("{:?}", err);
+    /// assert!(output.contains("\n    tenant acme is on the free plan\n\n    at "));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_pinned(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        let loc = Location::caller();
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        trace.try_add_context_with(loc, || Some(AtContext::Pinned(msg.into())));
+        self
+    }
+
+    /// Add the caller's location to the trace only if `condition` is true.
+    ///
+    /// Lets hot paths gate tracing on a runtime flag without wrapping every
+    /// `map_err` in an `if` block. When `condition` is false, this is a no-op.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let verbose = false;
+    /// let err = at(MyError).at_if(verbose);
+    /// assert_eq!(err.frame_count(), 1);
+    ///
+    /// let err = err.at_if(true);
+    /// assert_eq!(err.frame_count(), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn at_if(self, condition: bool) -> Self {
+        if condition { self.at() } else { self }
+    }
+
+    /// Add a static string context to the last location frame only if `condition` is true.
+    ///
+    /// Conditional counterpart of [`at_str()`](Self::at_str). When `condition`
+    /// is false, this is a no-op (no frame is created, even if the trace is empty).
+    #[track_caller]
+    #[inline]
+    pub fn at_str_if(self, condition: bool, msg: &'static str) -> Self {
+        if condition { self.at_str(msg) } else { self }
+    }
+
+    /// Add the caller's location to the trace in debug builds only.
+    ///
+    /// Compiled to a no-op when `debug_assertions` are off, so it costs nothing
+    /// (not even a branch) in release builds. Useful in hot inner loops where
+    /// traces are invaluable during development but unwanted in production.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError).debug_at();
+    /// let expected = if cfg!(debug_assertions) { 2 } else { 1 };
+    /// assert_eq!(err.frame_count(), expected);
+    /// ```
+    #[track_caller]
+    #[inline(always)]
+    pub fn debug_at(self) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.at()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            self
+        }
+    }
+
+    /// Add a static string context to the last location frame in debug builds only.
+    ///
+    /// Debug-only counterpart of [`at_str()`](Self::at_str); a no-op in release builds.
+    #[track_caller]
+    #[inline(always)]
+    pub fn debug_at_str(self, msg: &'static str) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.at_str(msg)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = msg;
+            self
+        }
+    }
+
+    /// Add a lazily-computed string context to the last location frame in debug builds only.
+    ///
+    /// Debug-only counterpart of [`at_string()`](Self::at_string). In release
+    /// builds the closure is never called.
+    #[track_caller]
+    #[inline(always)]
+    pub fn debug_at_string(self, f: impl FnOnce() -> String) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.at_string(f)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = f;
+            self
+        }
+    }
+
+    /// Add a skip marker (`[...]`) to the trace.
+    ///
+    /// Use this to indicate that some frames were skipped, either because
+    /// tracing started late in the call stack or because intermediate frames
+    /// are not meaningful.
+    #[doc(hidden)]
+    #[inline]
+    pub fn at_skipped_frames(mut self) -> Self {
+        let Some(trace) = self.capture_trace() else {
+            return self;
+        };
+        let _ = trace.try_push_skipped();
+        self
+    }
+
+    /// Set the crate info for this trace.
+    ///
+    /// This is used by `at!()` to provide repository metadata for GitHub links.
+    /// Calling this creates the trace if it doesn't exist yet.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// // Requires define_at_crate_info!() setup
+    /// whereat::define_at_crate_info!();
+    ///
+    /// #[derive(Debug)]
+    /// enum MyError { Oops }
+    ///
+    /// let err = At::wrap(MyError::Oops)
+    ///     .set_crate_info(crate::at_crate_info())
+    ///     .at();
+    /// ```
+    #[inline]
+    pub fn set_crate_info(mut self, info: &'static AtCrateInfo) -> Self {
+        let Some(trace) = self.edit_trace() else {
+            return self;
+        };
+        trace.set_crate_info(info);
+        self
+    }
+
+    /// Get the crate info for this trace, if set.
+    #[inline]
+    pub fn crate_info(&self) -> Option<&'static AtCrateInfo> {
+        self.trace.as_ref().and_then(|t| t.crate_info())
+    }
+
+    /// Get the most recently attached correlation id, if any.
+    ///
+    /// See [`at_correlation()`](Self::at_correlation).
+    #[inline]
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.trace.as_ref().and_then(|t| t.correlation_id())
+    }
+
+    /// Iterate over remediation hints added with [`at_help()`](Self::at_help), oldest first.
+    pub fn suggestions(&self) -> impl Iterator<Item = &str> {
+        self.trace
+            .as_ref()
+            .into_iter()
+            .flat_map(|t| t.suggestions())
+    }
+
+    /// Iterate over pinned messages added with [`at_pinned()`](Self::at_pinned), oldest first.
+    pub fn pinned(&self) -> impl Iterator<Item = &str> {
+        self.trace.as_ref().into_iter().flat_map(|t| t.pinned())
+    }
+
+    /// The backtrace captured by [`at_backtrace()`](Self::at_backtrace), if any.
+    #[cfg(feature = "std-backtrace")]
+    #[inline]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.trace.as_ref().and_then(|t| t.backtrace())
+    }
+
+    /// Get the unique id assigned when this error's first location was captured.
+    ///
+    /// Show it to users and log the full trace; the id correlates the two.
+    /// Returns `None` if no location has been captured (e.g. [`At::wrap`]).
+    #[cfg(feature = "error-id")]
+    #[inline]
+    pub fn error_id(&self) -> Option<crate::AtErrorId> {
+        self.trace.as_ref().and_then(|t| t.error_id())
+    }
+
+    /// Get the id of the tokio task this error was first captured on.
+    ///
+    /// See [`AtTrace::task_id()`].
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn task_id(&self) -> Option<tokio::task::Id> {
+        self.trace.as_ref().and_then(|t| t.task_id())
+    }
+
+    /// Check whether this error was traced on more than one tokio task.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn crossed_task_boundary(&self) -> bool {
+        self.trace
+            .as_ref()
+            .is_some_and(|t| t.crossed_task_boundary())
+    }
+
+    /// Get a reference to the inner error.
+    #[inline]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Get a mutable reference to the inner error.
+    #[inline]
+    pub fn error_mut(&mut self) -> &mut E {
+        &mut self.error
+    }
+
+    /// Consume self and return the inner error, discarding the trace.
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+
+    /// Check if the trace is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.trace.is_empty()
+    }
+
+    /// Iterate over all traced locations, oldest first.
+    ///
+    /// Skipped frame markers (`[...]`) are not included in this iteration.
+    /// Use [`frames()`](Self::frames) for full iteration including contexts.
+    #[inline]
+    #[allow(dead_code)] // Used in tests
+    pub(crate) fn locations(&self) -> impl Iterator<Item = &'static Location<'static>> + '_ {
+        self.trace
+            .as_ref()
+            .into_iter()
+            .flat_map(|t| t.iter())
+            .flatten() // Filter out None (skipped frame markers)
+    }
+
+    /// Get the first (oldest) location in the trace, if any.
+    #[inline]
+    #[allow(dead_code)] // Used in tests
+    pub(crate) fn first_location(&self) -> Option<&'static Location<'static>> {
+        self.locations().next()
+    }
+
+    /// Get the last (most recent) location in the trace, if any.
+    #[inline]
+    #[allow(dead_code)] // Used in tests
+    pub(crate) fn last_location(&self) -> Option<&'static Location<'static>> {
+        self.locations().last()
+    }
+
+    /// Get the trace, or None if it was never allocated (or was taken).
+    ///
+    /// Gives read-only access to the raw frame/context association through
+    /// [`AtTrace::frames()`] and [`AtTrace::context_entries()`], for building
+    /// custom formatters.
+    #[inline]
+    pub fn trace(&self) -> Option<&AtTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Get a reference to the underlying trace, if any.
+    #[inline]
+    #[allow(dead_code)] // Used in format module
+    pub(crate) fn trace_ref(&self) -> Option<&AtTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Like [`at()`](Self::at), for a location captured earlier (e.g. by an adapter).
+    #[inline]
+    pub(crate) fn at_location(mut self, loc: &'static Location<'static>) -> Self {
+        if let Some(trace) = self.capture_trace() {
+            let _ = trace.try_push(loc);
+        }
+        self
+    }
+
+    /// Add context to the last frame, pushing `loc` first if the trace is empty.
+    #[inline]
+    pub(crate) fn at_context_location(
+        mut self,
+        loc: &'static Location<'static>,
+        f: impl FnOnce() -> Option<AtContext>,
+    ) -> Self {
+        if let Some(trace) = self.capture_trace() {
+            trace.try_add_context_with(loc, f);
+        }
+        self
+    }
+
+    /// Iterate over all context entries, newest first.
+    ///
+    /// Each call to `at_str()`, `at_string()`, `at_data()`, or `at_debug()` creates
+    /// a context entry. Use [`AtContextRef`] methods to inspect context data.
+    ///
+    /// **Note:** Prefer [`frames()`](Self::frames) for unified iteration over
+    /// locations with their contexts.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError)
+    ///     .at_str("loading config")
+    ///     .at_str("initializing");
+    ///
+    /// let texts: Vec<_> = err.contexts()
+    ///     .filter_map(|ctx| ctx.as_text())
+    ///     .collect();
+    /// assert_eq!(texts, vec!["initializing", "loading config"]); // newest first
+    /// ```
+    #[inline]
+    pub fn contexts(&self) -> impl Iterator<Item = AtContextRef<'_>> {
+        self.trace.as_ref().into_iter().flat_map(|t| t.contexts())
+    }
+
+    /// Iterate over text contexts (`at_str`, `at_string`), newest first.
+    ///
+    /// Shorthand for filtering [`contexts()`](Self::contexts) by kind, as are
+    /// [`errors()`](Self::errors), [`links()`](Self::links) and [`crates()`](Self::crates).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError)
+    ///     .at_str("loading config")
+    ///     .at_debug(|| 42)
+    ///     .at_error(std::fmt::Error);
+    ///
+    /// assert_eq!(err.texts().collect::<Vec<_>>(), ["loading config"]);
+    /// assert_eq!(err.errors().count(), 1);
+    /// ```
+    #[inline]
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.contexts().filter_map(|ctx| ctx.as_text())
+    }
+
+    /// Iterate over attached source errors (`at_error`), newest first.
+    #[inline]
+    pub fn errors(&self) -> impl Iterator<Item = &(dyn core::error::Error + Send + Sync)> {
+        self.contexts().filter_map(|ctx| ctx.as_error())
+    }
+
+    /// Iterate over attached errors and their causes, newest attachment first.
+    ///
+    /// Each error added with `at_error` is followed by its own
+    /// [`source()`](core::error::Error::source) chain. The inner error's chain
+    /// is not included; reach it with `error().source()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError;
+    ///
+    /// let io = std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml");
+    /// let err = at(ConfigError)
+    ///     .at_error(io)
+    ///     .at_error(std::fmt::Error);
+    ///
+    /// let messages: Vec<_> = err.sources().map(|e| e.to_string()).collect();
+    /// assert_eq!(messages, ["an error occurred when formatting an argument", "config.toml"]);
+    /// let io = err.downcast_source::<std::io::Error>().unwrap();
+    /// assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+    /// ```
+    pub fn sources(&self) -> impl Iterator<Item = &(dyn core::error::Error + 'static)> {
+        self.attached_errors()
+            .flat_map(|err| core::iter::successors(Some(err), |e| e.source()))
+    }
+
+    /// Attached errors as `'static` trait objects, newest first.
+    fn attached_errors(&self) -> impl Iterator<Item = &(dyn core::error::Error + 'static)> {
+        self.contexts().filter_map(|ctx| match ctx.inner {
+            AtContext::Error(e) => Some(&**e as &(dyn core::error::Error + 'static)),
+            _ => None,
+        })
+    }
+
+    /// The first error of type `T` in [`sources()`](Self::sources).
+    #[inline]
+    pub fn downcast_source<T: core::error::Error + 'static>(&self) -> Option<&T> {
+        self.sources().find_map(|e| e.downcast_ref())
+    }
+
+    /// Iterate over `(label, url)` links (`at_link`), newest first.
+    #[inline]
+    pub fn links(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.contexts().filter_map(|ctx| ctx.as_link())
+    }
+
+    /// Iterate over `(key, value)` pairs, newest first.
+    #[inline]
+    pub fn kvs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.contexts().filter_map(|ctx| ctx.as_kv())
+    }
+
+    /// The value of the newest `key` pair (`at_kv`), if any.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct NotFound;
+    ///
+    /// let err: At<NotFound> = at(NotFound).at_kv("user_id", 42).at_kv("path", "/etc");
+    /// assert_eq!(err.get_context_value("user_id"), Some("42"));
+    /// assert_eq!(err.get_context_value("tenant"), None);
+    /// ```
+    #[inline]
+    pub fn get_context_value(&self, key: &str) -> Option<&str> {
+        self.kvs().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// The newest typed context of type `T` (`at_data`, `at_debug`), from any frame.
+    ///
+    /// Shorthand for `contexts().find_map(|ctx| ctx.downcast_ref::<T>())`.
+    /// Attached errors are found with [`downcast_source()`](Self::downcast_source).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct RequestId(u64);
+    ///
+    /// fn fetch() -> Result<(), At<Timeout>> {
+    ///     Err(at(Timeout)).at_debug(|| RequestId(7))
+    /// }
+    ///
+    /// fn handler() -> Result<(), At<Timeout>> {
+    ///     fetch().at().at_str("in handler")
+    /// }
+    ///
+    /// let err = handler().unwrap_err();
+    /// assert_eq!(err.find_context::<RequestId>(), Some(&RequestId(7)));
+    /// ```
+    #[inline]
+    pub fn find_context<T: 'static>(&self) -> Option<&T> {
+        self.contexts().find_map(|ctx| ctx.downcast_ref())
+    }
+
+    /// Every typed context of type `T`, from all frames, newest first.
+    #[inline]
+    pub fn find_all_contexts<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.contexts().filter_map(|ctx| ctx.downcast_ref())
+    }
+
+    /// Iterate over crate boundary markers (`at_crate`), newest first.
+    ///
+    /// The crate set by `at!()` is stored separately; see [`crate_info()`](Self::crate_info).
+    #[inline]
+    pub fn crates(&self) -> impl Iterator<Item = &'static AtCrateInfo> + '_ {
+        self.contexts().filter_map(|ctx| ctx.as_crate_info())
+    }
+
+    /// Iterate over every context with its frame index and location, in the given order.
+    ///
+    /// Yields `(frame_index, location, context)`; the location is `None` for
+    /// contexts on a `[...]` marker. See [`AtTrace::contexts_with_frames()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtOrder};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError).at_str("reading").at().at_kv("attempt", 2);
+    /// for (frame, location, ctx) in err.contexts_with_frames(AtOrder::OldestFirst) {
+    ///     let loc = location.unwrap();
+    ///     println!("#{} {}:{} {}", frame, loc.file(), loc.line(), ctx);
+    /// }
+    /// let frames: Vec<_> = err.contexts_with_frames(AtOrder::OldestFirst).map(|e| e.0).collect();
+    /// assert_eq!(frames, [0, 1]);
+    /// ```
+    #[inline]
+    pub fn contexts_with_frames(
+        &self,
+        order: AtOrder,
+    ) -> impl Iterator<Item = (usize, Option<&'static Location<'static>>, AtContextRef<'_>)> {
+        self.trace
+            .as_ref()
+            .into_iter()
+            .flat_map(move |t| t.contexts_with_frames(order))
+    }
+
+    /// Iterate over frames (location + contexts pairs), oldest first.
+    ///
+    /// This is the recommended way to traverse a trace. Each frame contains
+    /// a location (or None for skipped-frames marker) and its associated contexts.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::at;
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError)
+    ///     .at_str("loading config")
     ///     .at();
     ///
     /// for frame in err.frames() {
@@ -655,10 +1545,77 @@ impl<E> At<E> {
         self.trace.as_ref().map_or(0, |t| t.frame_count())
     }
 
+    /// Frame and context counts plus approximate heap use, in one value.
+    ///
+    /// Handy for asserting what a layer lets through, e.g. "at most 3 frames
+    /// and no Debug contexts". All zero if no trace has been allocated.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtContextKind};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError).at_str("reading config").at();
+    /// let stats = err.trace_stats();
+    /// assert!(stats.frames() <= 3);
+    /// assert_eq!(stats.contexts_of(AtContextKind::Debug), 0);
+    /// ```
+    pub fn trace_stats(&self) -> AtTraceStats {
+        self.trace.as_ref().map(AtTrace::stats).unwrap_or_default()
+    }
+
     // ========================================================================
     // Trace manipulation methods
     // ========================================================================
 
+    /// Attach context to an existing frame (index 0 is the origin, oldest first).
+    ///
+    /// Context methods like [`at_str()`](Self::at_str) always target the last
+    /// frame. Use this when post-processing learns something that belongs to an
+    /// earlier frame. Returns `false` if the context could not be attached
+    /// (see [`AtTrace::annotate()`]).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtContextOwned};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let mut err = at(MyError).at().at().at_str("in handler");
+    ///
+    /// // Later: resolved which tenant the failing path belongs to
+    /// assert!(err.annotate(0, AtContextOwned::string("tenant: acme".into())));
+    ///
+    /// let origin = err.frames().next().unwrap();
+    /// assert_eq!(origin.contexts().next().unwrap().as_text(), Some("tenant: acme"));
+    /// assert!(!err.annotate(99, AtContextOwned::text("out of range")));
+    /// ```
+    #[inline]
+    pub fn annotate(&mut self, frame_index: usize, context: crate::AtContextOwned) -> bool {
+        self.trace
+            .as_mut()
+            .is_some_and(|t| t.annotate(frame_index, context))
+    }
+
+    /// Remove and return all contexts, keeping the locations.
+    ///
+    /// Returns `(frame_index, context)` pairs, oldest first. Crate boundary
+    /// markers are kept so repository links stay correct. Useful at a boundary
+    /// that moves rich context into a response/report and propagates a slimmer error.
+    ///
+    /// See [`AtContextOwned`](crate::AtContextOwned) for an example.
+    #[inline]
+    pub fn take_contexts(&mut self) -> Vec<(usize, crate::AtContextOwned)> {
+        self.trace
+            .as_mut()
+            .map_or_else(Vec::new, |t| t.drain_contexts())
+    }
+
     /// Pop the most recent location and its contexts from the trace.
     ///
     /// Returns `None` if the trace is empty.
@@ -670,7 +1627,9 @@ impl<E> At<E> {
     /// Push a segment (location + contexts) to the end of the trace.
     #[inline]
     pub fn at_push(&mut self, segment: AtFrameOwned) {
-        self.ensure_trace().push(segment);
+        if let Some(trace) = self.edit_trace() {
+            trace.push(segment);
+        }
     }
 
     /// Pop the oldest location and its contexts from the trace.
@@ -684,7 +1643,44 @@ impl<E> At<E> {
     /// Insert a segment (location + contexts) at the beginning of the trace.
     #[inline]
     pub fn at_first_insert(&mut self, segment: AtFrameOwned) {
-        self.ensure_trace().push_first(segment);
+        if let Some(trace) = self.edit_trace() {
+            trace.push_first(segment);
+        }
+    }
+
+    /// Collapse runs of identical consecutive frames into one frame with a repeat count.
+    ///
+    /// For errors that bubble through recursion or a retry loop calling `.at()`
+    /// at the same site. Formatters show the count on the location line, e.g.
+    /// `at src/retry.rs:42:9 (x17)`; [`AtFrame::repeat_count()`] returns it. See
+    /// [`AtTrace::compress()`] for the details.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct Timeout;
+    ///
+    /// fn retry(err: At<Timeout>, attempts: u32) -> At<Timeout> {
+    ///     (0..attempts).fold(err, |err, _| err.at())
+    /// }
+    ///
+    /// let err = retry(at(Timeout), 17);
+    /// assert_eq!(err.frame_count(), 18);
+    ///
+    /// let err = err.dedup_trace();
+    /// assert_eq!(err.frame_count(), 2);
+    /// assert_eq!(err.frames().last().unwrap().repeat_count(), 17);
+    /// assert!(format!("{:?}", err).contains(" (x17)\n"));
+    /// ```
+    #[inline]
+    pub fn dedup_trace(mut self) -> Self {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.compress();
+        }
+        self
     }
 
     /// Take the entire trace, leaving self with an empty trace.
@@ -734,6 +1730,75 @@ impl<E> At<E> {
         }
     }
 
+    /// Convert the error type with `From`, keeping the trace and recording the
+    /// conversion site as a new frame.
+    ///
+    /// [`map_error()`](Self::map_error) keeps the trace but not where the
+    /// conversion happened; this is `map_error(E2::from)` followed by
+    /// [`at()`](Self::at). For `Result`s use
+    /// [`ResultAtExt::map_err_into()`](crate::ResultAtExt::map_err_into).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct IoError;
+    /// #[derive(Debug)]
+    /// struct AppError(IoError);
+    ///
+    /// impl From<IoError> for AppError {
+    ///     fn from(e: IoError) -> Self { AppError(e) }
+    /// }
+    ///
+    /// let err: At<IoError> = at(IoError).at_str("reading config");
+    /// let err: At<AppError> = err.map_into(); // boundary recorded here
+    /// assert_eq!(err.frame_count(), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn map_into<E2: From<E>>(self) -> At<E2> {
+        self.map_error(E2::from).at()
+    }
+
+    /// Fallibly convert the error type while preserving the trace.
+    ///
+    /// `f` returns `Ok(E2)` when the conversion succeeds, or hands the
+    /// original error back as `Err(E)`. The trace is kept on both branches.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct Payload(String);
+    /// #[derive(Debug)]
+    /// struct Code(u16);
+    ///
+    /// let parse = |p: Payload| p.0.parse().map(Code).map_err(|_| p);
+    ///
+    /// let ok: At<Code> = at(Payload("404".into())).at().try_map_error(parse).unwrap();
+    /// assert_eq!(ok.error().0, 404);
+    /// assert_eq!(ok.frame_count(), 2);
+    ///
+    /// let err: At<Payload> = at(Payload("oops".into())).try_map_error(parse).unwrap_err();
+    /// assert_eq!(err.error().0, "oops");
+    /// assert_eq!(err.frame_count(), 1);
+    /// ```
+    #[inline]
+    pub fn try_map_error<E2, F>(self, f: F) -> Result<At<E2>, At<E>>
+    where
+        F: FnOnce(E) -> Result<E2, E>,
+    {
+        let At { error, trace } = self;
+        match f(error) {
+            Ok(error) => Ok(At { error, trace }),
+            Err(error) => Err(At { error, trace }),
+        }
+    }
+
     /// Convert to an `AtTraceable` type, transferring the trace.
     ///
     /// The closure receives the inner error and should return an error type
@@ -777,48 +1842,257 @@ impl<E> At<E> {
     }
 }
 
+// ============================================================================
+// Downcasting dynamic errors
+// ============================================================================
+
+impl At<Box<dyn core::error::Error + Send + Sync>> {
+    /// Narrow a boxed dynamic error to a concrete type, keeping the trace.
+    ///
+    /// On success the error is unboxed into an `At<T>` with the same frames and
+    /// contexts. On failure the original `At` is returned unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::error::Error;
+    /// use whereat::{at, At};
+    ///
+    /// fn read() -> Result<(), At<Box<dyn Error + Send + Sync>>> {
+    ///     let io = std::io::Error::other("disk full");
+    ///     Err(at(Box::new(io) as Box<dyn Error + Send + Sync>).at_str("reading index"))
+    /// }
+    ///
+    /// let err = read().unwrap_err();
+    /// let io: At<std::io::Error> = err.downcast().unwrap();
+    /// assert_eq!(io.error().kind(), std::io::ErrorKind::Other);
+    /// assert_eq!(io.frame_count(), 1);
+    /// ```
+    pub fn downcast<T: core::error::Error + 'static>(self) -> Result<At<T>, Self> {
+        let At { error, trace } = self;
+        match error.downcast::<T>() {
+            Ok(concrete) => Ok(At {
+                error: *concrete,
+                trace,
+            }),
+            Err(error) => Err(At { error, trace }),
+        }
+    }
+
+    /// Check whether the inner error is a `T`.
+    #[inline]
+    pub fn is<T: core::error::Error + 'static>(&self) -> bool {
+        self.error.is::<T>()
+    }
+
+    /// Borrow the inner error as a `T`, if it is one.
+    #[inline]
+    pub fn downcast_ref<T: core::error::Error + 'static>(&self) -> Option<&T> {
+        self.error.downcast_ref()
+    }
+
+    /// Mutably borrow the inner error as a `T`, if it is one.
+    #[inline]
+    pub fn downcast_mut<T: core::error::Error + 'static>(&mut self) -> Option<&mut T> {
+        self.error.downcast_mut()
+    }
+}
+
+impl At<Box<dyn core::error::Error>> {
+    /// Narrow a boxed dynamic error to a concrete type, keeping the trace.
+    ///
+    /// See the `Send + Sync` variant of this method for details.
+    pub fn downcast<T: core::error::Error + 'static>(self) -> Result<At<T>, Self> {
+        let At { error, trace } = self;
+        match error.downcast::<T>() {
+            Ok(concrete) => Ok(At {
+                error: *concrete,
+                trace,
+            }),
+            Err(error) => Err(At { error, trace }),
+        }
+    }
+
+    /// Check whether the inner error is a `T`.
+    #[inline]
+    pub fn is<T: core::error::Error + 'static>(&self) -> bool {
+        self.error.is::<T>()
+    }
+
+    /// Borrow the inner error as a `T`, if it is one.
+    #[inline]
+    pub fn downcast_ref<T: core::error::Error + 'static>(&self) -> Option<&T> {
+        self.error.downcast_ref()
+    }
+
+    /// Mutably borrow the inner error as a `T`, if it is one.
+    #[inline]
+    pub fn downcast_mut<T: core::error::Error + 'static>(&mut self) -> Option<&mut T> {
+        self.error.downcast_mut()
+    }
+}
+
 // ============================================================================
 // Debug impl for At<E>
 // ============================================================================
 
 impl<E: fmt::Debug> fmt::Debug for At<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(
+            &AtDebugDisplay {
+                at: self,
+                format: AtTraceFormat::DEFAULT,
+            },
+            f,
+        )
+    }
+}
+
+impl<E: fmt::Debug> At<E> {
+    /// Format like `{:?}`, with [`AtTraceFormat`] options applied.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtLevel, AtTraceFormat};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError).at_note("verbose").at_str("loading config");
+    /// let output = err
+    ///     .debug_with(AtTraceFormat::new().min_level(AtLevel::Warn))
+    ///     .to_string();
+    /// assert!(output.contains("loading config"));
+    /// assert!(!output.contains("verbose"));
+    /// ```
+    #[inline]
+    pub fn debug_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+        AtDebugDisplay { at: self, format }
+    }
+
+    /// Format like `{:?}`, with sensitive contexts (`at_secret`) revealed.
+    ///
+    /// Shorthand for `debug_with(AtTraceFormat::new().redact(false))`. Meant
+    /// for local debugging; don't send this output to shared logs.
+    #[inline]
+    pub fn display_unredacted(&self) -> impl fmt::Display + '_ {
+        self.debug_with(AtTraceFormat::new().redact(false))
+    }
+}
+
+/// Formatter behind `Debug` and `debug_with()`.
+struct AtDebugDisplay<'a, E> {
+    at: &'a At<E>,
+    format: AtTraceFormat,
+}
+
+impl<E: fmt::Debug> fmt::Display for AtDebugDisplay<'_, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Error header
-        writeln!(f, "Error: {:?}", self.error)?;
+        writeln!(f, "Error: {:?}", self.at.error)?;
 
-        let Some(trace) = self.trace.as_ref() else {
+        let Some(trace) = self.at.trace.as_ref() else {
             return Ok(());
         };
 
+        #[cfg(feature = "error-id")]
+        if let Some(id) = trace.error_id() {
+            writeln!(f, "    error id: {}", id)?;
+        }
+
+        for msg in trace.pinned() {
+            writeln!(f, "    {}", msg)?;
+        }
+
         writeln!(f)?;
 
         // Simple iteration: walk locations, show all contexts at each index
         // None = skipped frame marker
-        for (i, loc_opt) in trace.iter().enumerate() {
-            match loc_opt {
+        for (i, frame) in trace.frames().enumerate() {
+            match frame.location() {
                 Some(loc) => {
-                    writeln!(f, "    at {}:{}", loc.file(), loc.line())?;
+                    let repeat = RepeatSuffix(trace.repeat_count(i));
+                    write!(f, "    at ")?;
+                    self.format.write_location(f, &frame, loc)?;
+                    writeln!(f, "{}", repeat)?;
+                    if let Some(n) = trace.suppressed_count().filter(|_| i == 0) {
+                        writeln!(f, "       ╰─ warn: {}", SuppressedNote(n))?;
+                    }
                     for context in trace.contexts_at(i) {
-                        match context {
-                            AtContext::Text(msg) => writeln!(f, "       ╰─ {}", msg)?,
-                            AtContext::FunctionName(name) => writeln!(f, "       ╰─ in {}", name)?,
-                            AtContext::Debug(t) => writeln!(f, "       ╰─ {:?}", &**t)?,
-                            AtContext::Display(t) => writeln!(f, "       ╰─ {}", &**t)?,
-                            AtContext::Error(e) => writeln!(f, "       ╰─ caused by: {}", e)?,
-                            AtContext::Crate(_) => {} // Crate boundaries don't display in basic Debug
-                        }
+                        write_context_line(f, &self.format, context)?;
                     }
                 }
                 None => {
-                    writeln!(f, "    [...]")?;
+                    writeln!(f, "    {}", SkipMarker(trace.repeat_count(i)))?;
                 }
             }
         }
 
+        for hint in trace.suggestions() {
+            writeln!(f, "    help: {}", hint)?;
+        }
+        write_backtrace(f, trace, "    ", "\n")?;
+
         Ok(())
     }
 }
 
+/// Write a context as a `╰─` line under its frame, as `Debug` shows it.
+///
+/// Contexts shown elsewhere (crate boundaries, hints, pinned messages,
+/// backtraces and repeat counts) and those `format` hides write nothing.
+fn write_context_line(
+    f: &mut fmt::Formatter<'_>,
+    format: &AtTraceFormat,
+    context: &AtContext,
+) -> fmt::Result {
+    if !format.shows(context) {
+        return Ok(());
+    }
+    match context {
+        AtContext::Text(msg) => writeln!(f, "       ╰─ {}", msg)?,
+        AtContext::FunctionName(name) => writeln!(f, "       ╰─ in {}", name)?,
+        AtContext::Debug(t) => {
+            write!(f, "       ╰─ ")?;
+            format.write_debug(f, &**t, "          ")?;
+            writeln!(f)?
+        }
+        AtContext::Secret(t) => {
+            write!(f, "       ╰─ ")?;
+            format.write_secret(f, &**t, "          ")?;
+            writeln!(f)?
+        }
+        AtContext::ExpectedActual(pair) => {
+            write!(f, "       ╰─ ")?;
+            pair.write_block(f, "          ")?;
+            writeln!(f)?
+        }
+        AtContext::Bytes(bytes) => {
+            write!(f, "       ╰─ ")?;
+            bytes.write_dump(f, "          ")?;
+            writeln!(f)?
+        }
+        AtContext::Remote(trace) => {
+            write!(f, "       ╰─ ")?;
+            trace.write_block(f, "          ")?;
+            writeln!(f)?
+        }
+        AtContext::Display(t) => writeln!(f, "       ╰─ {}", &**t)?,
+        AtContext::Error(e) => writeln!(f, "       ╰─ caused by: {}", e)?,
+        AtContext::Leveled(level, msg) => writeln!(f, "       ╰─ {}: {}", level, msg)?,
+        AtContext::Link { label, url } => writeln!(f, "       ╰─ see: {} ({})", label, url)?,
+        AtContext::SpanId(id) => writeln!(f, "       ╰─ span: {}", id)?,
+        AtContext::KeyValue { key, value } => writeln!(f, "       ╰─ {}={}", key, value)?,
+        AtContext::Crate(_) => {} // Shown as links or headers, if at all
+        AtContext::Suggestion(_) => {} // Shown in the help section
+        AtContext::Pinned(_) => {} // Shown under the header
+        AtContext::Backtrace(_) => {} // Shown after the frames
+        AtContext::Repeated(_) => {} // Shown on the location line
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Enhanced display with AtCrateInfo from trace
 // ============================================================================
@@ -849,13 +2123,134 @@ impl<E: fmt::Debug> At<E> {
     /// ```
     #[inline]
     pub fn display_with_meta(&self) -> impl fmt::Display + '_ {
-        DisplayWithMeta { traced: self }
+        self.display_with_meta_with(AtTraceFormat::DEFAULT)
+    }
+
+    /// Like [`display_with_meta()`](Self::display_with_meta), with [`AtTraceFormat`] options applied.
+    #[inline]
+    pub fn display_with_meta_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+        DisplayWithMeta {
+            traced: self,
+            sectioned: false,
+            format,
+        }
+    }
+
+    /// Like [`display_with_meta()`](Self::display_with_meta), with frames grouped under a header per crate.
+    ///
+    /// A `── name ──` header is written whenever the effective crate changes,
+    /// so traces crossing many crate boundaries read as a few labelled blocks
+    /// instead of interleaved frames.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{At, AtCrateInfo};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// static CLIENT: AtCrateInfo = AtCrateInfo::builder().name("payments-client").build();
+    /// static APP: AtCrateInfo = AtCrateInfo::builder().name("myapp").build();
+    ///
+    /// let err = At::wrap(MyError)
+    ///     .set_crate_info(&CLIENT)
+    ///     .at()
+    ///     .at()
+    ///     .at_crate(&APP)
+    ///     .at();
+    ///
+    /// let output = err.display_by_crate().to_string();
+    /// let headers: Vec<_> = output.lines().filter(|l| l.starts_with("──")).collect();
+    /// assert_eq!(headers, ["── payments-client ──", "── myapp ──"]);
+    /// ```
+    #[inline]
+    pub fn display_by_crate(&self) -> impl fmt::Display + '_ {
+        DisplayWithMeta {
+            traced: self,
+            sectioned: true,
+            format: AtTraceFormat::DEFAULT,
+        }
+    }
+
+    /// Link to the API docs of the error's type, anchored at the failing variant.
+    ///
+    /// Built from the `"docs_url"` [metadata](AtCrateInfo::get_meta) of the
+    /// trace's crate (set by `at!()`), which points at the crate root's docs,
+    /// e.g. `https://docs.rs/mylib/latest/mylib/`. The type's module path
+    /// comes from `type_name::<E>()` and the variant from the error's `Debug`
+    /// output; an error whose `Debug` output starts with the type's own name
+    /// links to a struct page. [`display_with_meta_with()`](Self::display_with_meta_with)
+    /// prints the link as a `see:` line when [`AtTraceFormat::docs_link()`] is on.
+    ///
+    /// The variant is a guess: it is the leading identifier of the `Debug`
+    /// output, which is only the variant name for derived `Debug` on an enum.
+    /// A hand-written `Debug`, a struct that isn't printed under its own name,
+    /// or a type alias or re-export (where `type_name` differs from the
+    /// documented path) produces a link to a page or anchor that doesn't
+    /// exist. Build the URL yourself from the `"docs_url"` metadata for such
+    /// types.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{At, AtCrateInfo, AtTraceFormat};
+    ///
+    /// mod errors {
+    ///     #[derive(Debug)]
+    ///     pub enum ConfigError { NotFound { path: String } }
+    /// }
+    ///
+    /// static INFO: AtCrateInfo = AtCrateInfo::builder()
+    ///     .name("mylib")
+    ///     .meta(&[("docs_url", "https://docs.rs/mylib/latest/mylib/")])
+    ///     .build();
+    ///
+    /// let err = At::wrap(errors::ConfigError::NotFound { path: "app.toml".into() })
+    ///     .set_crate_info(&INFO)
+    ///     .at();
+    /// // https://docs.rs/mylib/latest/mylib/errors/enum.ConfigError.html#variant.NotFound
+    /// let url = err.docs_url().unwrap();
+    /// assert!(url.starts_with("https://docs.rs/mylib/latest/mylib/"));
+    /// assert!(url.ends_with("/errors/enum.ConfigError.html#variant.NotFound"));
+    /// assert!(!err.display_with_meta().to_string().contains("see:"));
+    ///
+    /// let output = err.display_with_meta_with(AtTraceFormat::new().docs_link(true));
+    /// assert!(output.to_string().contains("\n  see: https://docs.rs/"));
+    /// ```
+    pub fn docs_url(&self) -> Option<String> {
+        let base = self.crate_info()?.get_meta("docs_url")?;
+        let debug = alloc::format!("{:?}", self.error);
+        Some(docs_link(base, core::any::type_name::<E>(), &debug))
+    }
+}
+
+/// `base` plus the module path and page of `type_name`, anchored at the variant in `debug`.
+fn docs_link(base: &str, type_name: &str, debug: &str) -> String {
+    let path = type_name.split('<').next().unwrap_or(type_name);
+    let segments: Vec<&str> = path.split("::").collect();
+    let (name, modules) = segments.split_last().unwrap_or((&path, &[]));
+    let mut url = String::from(base.trim_end_matches('/'));
+    // The first segment is the crate, which `base` already points at
+    for module in modules.iter().skip(1) {
+        url.push('/');
+        url.push_str(module);
+    }
+    let variant = variant_name(debug);
+    if variant.is_empty() || variant == *name {
+        url.push_str(&alloc::format!("/struct.{}.html", name));
+    } else {
+        url.push_str(&alloc::format!("/enum.{}.html#variant.{}", name, variant));
     }
+    url
 }
 
 /// Wrapper for displaying At<E> with AtCrateInfo enhancements.
 struct DisplayWithMeta<'a, E> {
     traced: &'a At<E>,
+    /// Write a header per crate instead of the `crate:` line.
+    sectioned: bool,
+    format: AtTraceFormat,
 }
 
 impl<E: fmt::Debug> fmt::Display for DisplayWithMeta<'_, E> {
@@ -872,47 +2267,82 @@ impl<E: fmt::Debug> fmt::Display for DisplayWithMeta<'_, E> {
         let initial_crate = trace.crate_info();
 
         // Show crate info if available
-        if let Some(info) = initial_crate {
+        if let Some(info) = initial_crate.filter(|_| !self.sectioned) {
             writeln!(f, "  crate: {}", info.name())?;
         }
+        // Guessing the variant formats the error again, so only when asked
+        let docs_url = self.format.docs_link.then(|| self.traced.docs_url());
+        if let Some(url) = docs_url.flatten() {
+            writeln!(f, "  see: {}", url)?;
+        }
+
+        #[cfg(feature = "error-id")]
+        if let Some(id) = trace.error_id() {
+            writeln!(f, "  error id: {}", id)?;
+        }
+
+        for msg in trace.pinned() {
+            writeln!(f, "    {}", msg)?;
+        }
 
         writeln!(f)?;
 
         // Cache GitHub base URL - rebuild when crate boundary changes
         let mut link_template: Option<String> = initial_crate.and_then(build_link_base);
+        let mut current_crate = initial_crate;
+        let mut section: Option<&str> = None;
 
         // Walk locations, updating GitHub base when we encounter crate boundaries
         // None = skipped frame marker
-        for (i, loc_opt) in trace.iter().enumerate() {
+        for (i, frame) in trace.frames().enumerate() {
             // Check for crate boundary at this location - rebuild URL only when crate changes
             for context in trace.contexts_at(i) {
                 if let AtContext::Crate(info) = context {
                     link_template = build_link_base(info);
+                    current_crate = Some(info);
+                }
+            }
+
+            let new_section = current_crate
+                .map(|info| info.name())
+                .filter(|&name| self.sectioned && section != Some(name));
+            if let Some(name) = new_section {
+                if i > 0 {
+                    writeln!(f)?;
                 }
+                writeln!(f, "── {} ──", name)?;
+                section = Some(name);
             }
 
-            match loc_opt {
+            match frame.location() {
                 Some(loc) => {
-                    write_location_meta(f, loc, link_template.as_deref())?;
+                    write!(f, "    at ")?;
+                    self.format.write_location(f, &frame, loc)?;
+                    writeln!(f, "{}", RepeatSuffix(trace.repeat_count(i)))?;
+                    if let Some(template) = link_template.as_deref() {
+                        let link = crate::crate_info::fill_location_link(template, loc);
+                        writeln!(f, "       {}", link)?;
+                    }
 
                     // Show non-crate contexts
+                    if let Some(n) = trace.suppressed_count().filter(|_| i == 0) {
+                        writeln!(f, "       ╰─ warn: {}", SuppressedNote(n))?;
+                    }
                     for context in trace.contexts_at(i) {
-                        match context {
-                            AtContext::Text(msg) => writeln!(f, "       ╰─ {}", msg)?,
-                            AtContext::FunctionName(name) => writeln!(f, "       ╰─ in {}", name)?,
-                            AtContext::Debug(t) => writeln!(f, "       ╰─ {:?}", &**t)?,
-                            AtContext::Display(t) => writeln!(f, "       ╰─ {}", &**t)?,
-                            AtContext::Error(e) => writeln!(f, "       ╰─ caused by: {}", e)?,
-                            AtContext::Crate(_) => {} // Already handled above
-                        }
+                        write_context_line(f, &self.format, context)?;
                     }
                 }
                 None => {
-                    writeln!(f, "    [...]")?;
+                    writeln!(f, "    {}", SkipMarker(trace.repeat_count(i)))?;
                 }
             }
         }
 
+        for hint in trace.suggestions() {
+            writeln!(f, "    help: {}", hint)?;
+        }
+        write_backtrace(f, trace, "    ", "\n")?;
+
         Ok(())
     }
 }
@@ -921,8 +2351,8 @@ impl<E: fmt::Debug> fmt::Display for DisplayWithMeta<'_, E> {
 /// Returns the formatted URL base or None if repo/commit unavailable.
 ///
 /// The format string can contain placeholders: `{repo}`, `{commit}`, `{path}`.
-/// The `{file}` and `{line}` placeholders are handled by `write_location_meta`.
-fn build_link_base(info: &AtCrateInfo) -> Option<String> {
+/// The `{file}`, `{line}` and `{column}` placeholders are filled in by `fill_location_link`.
+pub(crate) fn build_link_base(info: &AtCrateInfo) -> Option<String> {
     match (info.repo(), info.commit()) {
         (Some(repo), Some(commit)) => {
             let repo = repo.trim_end_matches('/');
@@ -930,7 +2360,7 @@ fn build_link_base(info: &AtCrateInfo) -> Option<String> {
             let format = info.link_format();
 
             // Build the base URL by replacing {repo}, {commit}, {path}
-            // Leave {file} and {line} for write_location_meta
+            // Leave {file}, {line} and {column} for fill_location_link
             let mut result =
                 String::with_capacity(format.len() + repo.len() + commit.len() + path.len());
             let mut chars = format.chars().peekable();
@@ -950,7 +2380,7 @@ fn build_link_base(info: &AtCrateInfo) -> Option<String> {
                         "repo" => result.push_str(repo),
                         "commit" => result.push_str(commit),
                         "path" => result.push_str(path),
-                        // Keep {file} and {line} as-is for later substitution
+                        // Keep {file}, {line} and {column} as-is for later substitution
                         other => {
                             result.push('{');
                             result.push_str(other);
@@ -967,30 +2397,6 @@ fn build_link_base(info: &AtCrateInfo) -> Option<String> {
     }
 }
 
-/// Helper to write a location with optional repository link.
-///
-/// The `link_template` should have {repo}, {commit}, {path} already substituted,
-/// but {file} and {line} still present as placeholders.
-fn write_location_meta(
-    f: &mut fmt::Formatter<'_>,
-    loc: &'static Location<'static>,
-    link_template: Option<&str>,
-) -> fmt::Result {
-    writeln!(f, "    at {}:{}", loc.file(), loc.line())?;
-    if let Some(template) = link_template {
-        // Convert backslashes to forward slashes for Windows paths
-        let file = loc.file().replace('\\', "/");
-        let line = loc.line();
-
-        // Replace {file} and {line} placeholders
-        let link = template
-            .replace("{file}", &file)
-            .replace("{line}", &line.to_string());
-        writeln!(f, "       {}", link)?;
-    }
-    Ok(())
-}
-
 // ============================================================================
 // Formatting methods for At<E>
 // ============================================================================
@@ -1026,7 +2432,13 @@ impl<E: fmt::Display> At<E> {
     /// ```
     #[inline]
     pub fn full_trace(&self) -> impl fmt::Display + '_ {
-        AtFullTraceDisplay { at: self }
+        self.full_trace_with(AtTraceFormat::DEFAULT)
+    }
+
+    /// Like [`full_trace()`](Self::full_trace), with [`AtTraceFormat`] options applied.
+    #[inline]
+    pub fn full_trace_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+        AtFullTraceDisplay { at: self, format }
     }
 
     /// Format with trace locations only (message + locations, no context strings).
@@ -1061,6 +2473,36 @@ impl<E: fmt::Display> At<E> {
         AtLastErrorTraceDisplay { at: self }
     }
 
+    /// Format with consecutive frames in the same file grouped onto one line.
+    ///
+    /// Like [`last_error_trace()`](Self::last_error_trace), contexts are omitted.
+    /// Errors that bounce around inside one module read much shorter.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError(&'static str);
+    ///
+    /// impl std::fmt::Display for MyError {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "{}", self.0)
+    ///     }
+    /// }
+    ///
+    /// let err: At<MyError> = at(MyError("failed")).at().at();
+    /// println!("{}", err.grouped_trace());
+    /// // Output:
+    /// // failed
+    /// //     at src/main.rs: lines 10 → 10 → 10
+    /// ```
+    #[inline]
+    pub fn grouped_trace(&self) -> impl fmt::Display + '_ {
+        AtGroupedTraceDisplay { at: self }
+    }
+
     /// Format just the error message (no trace).
     ///
     /// Returns a formatter that only displays the error message via `Display`.
@@ -1076,46 +2518,14 @@ impl<E: fmt::Display> At<E> {
 /// Formatter that shows error message + full trace with all contexts.
 struct AtFullTraceDisplay<'a, E> {
     at: &'a At<E>,
+    format: AtTraceFormat,
 }
 
 impl<E: fmt::Display> fmt::Display for AtFullTraceDisplay<'_, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Show the error message
         write!(f, "{}", self.at.error)?;
-
-        // Show trace frames
-        if let Some(trace) = self.at.trace.as_ref() {
-            for frame in trace.frames() {
-                if let Some(loc) = frame.location() {
-                    write!(f, "\n    at {}:{}:{}", loc.file(), loc.line(), loc.column())?;
-                } else {
-                    write!(f, "\n    [...]")?;
-                }
-
-                // Show contexts for this frame
-                for ctx in frame.contexts() {
-                    if let Some(text) = ctx.as_text() {
-                        write!(f, "\n        {}", text)?;
-                    } else if let Some(fn_name) = ctx.as_function_name() {
-                        write!(f, "\n        in {}", fn_name)?;
-                    } else if let Some(err) = ctx.as_error() {
-                        write!(f, "\n        caused by: {}", err)?;
-                        // Write nested error chain
-                        let mut source = err.source();
-                        let mut depth = 2;
-                        while let Some(src) = source {
-                            let indent = "    ".repeat(depth);
-                            write!(f, "\n{}caused by: {}", indent, src)?;
-                            source = src.source();
-                            depth += 1;
-                        }
-                    } else {
-                        write!(f, "\n        {}", ctx)?;
-                    }
-                }
-            }
-        }
-        Ok(())
+        write_traceable_body(f, self.at.trace.as_ref(), &self.format, None, None, false)
     }
 }
 
@@ -1129,13 +2539,19 @@ impl<E: fmt::Display> fmt::Display for AtLastErrorTraceDisplay<'_, E> {
         // Show the error message
         write!(f, "{}", self.at.error)?;
 
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.at.error_id() {
+            write!(f, "\n    error id: {}", id)?;
+        }
+
         // Show trace frames (locations only, no contexts)
         if let Some(trace) = self.at.trace.as_ref() {
             for frame in trace.frames() {
                 if let Some(loc) = frame.location() {
                     write!(f, "\n    at {}:{}:{}", loc.file(), loc.line(), loc.column())?;
+                    write!(f, "{}", RepeatSuffix(frame.repeat_count()))?;
                 } else {
-                    write!(f, "\n    [...]")?;
+                    write!(f, "\n    {}", SkipMarker(frame.repeat_count()))?;
                 }
             }
         }
@@ -1143,6 +2559,27 @@ impl<E: fmt::Display> fmt::Display for AtLastErrorTraceDisplay<'_, E> {
     }
 }
 
+/// Formatter that shows error message + locations grouped by file.
+struct AtGroupedTraceDisplay<'a, E> {
+    at: &'a At<E>,
+}
+
+impl<E: fmt::Display> fmt::Display for AtGroupedTraceDisplay<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.at.error)?;
+
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.at.error_id() {
+            write!(f, "\n    error id: {}", id)?;
+        }
+
+        if let Some(trace) = self.at.trace.as_ref() {
+            crate::trace::write_grouped_frames(f, trace)?;
+        }
+        Ok(())
+    }
+}
+
 /// Formatter that shows just the error message (no trace).
 struct AtLastErrorDisplay<'a, E> {
     at: &'a At<E>,
@@ -1168,9 +2605,117 @@ impl<E: fmt::Display> fmt::Display for At<E> {
 // Error impl for At<E>
 // ============================================================================
 
+/// `source()` is the inner error's source, or if it has none, the most
+/// recently attached error (`at_error`), so generic chain walkers such as
+/// `anyhow::Error::chain()` still reach it. See [`At::sources()`] for all
+/// attached errors.
 impl<E: core::error::Error> core::error::Error for At<E> {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
-        self.error.source()
+        self.error
+            .source()
+            .or_else(|| self.attached_errors().next())
+    }
+}
+
+// ============================================================================
+// AtDisplayError - Error adapter for Display-only types
+// ============================================================================
+
+/// An `At<E>` that implements `Error` and [`AtTraceable`](crate::AtTraceable).
+///
+/// `At<E>` is only an `Error` when `E` is. Use [`At::into_std_error()`] to
+/// return a traced `Display`-only value where `Box<dyn Error>` is required.
+/// `Display` shows the error message and `Debug` the full trace, as for `At<E>`.
+///
+/// The adapter also lets `At<E>` reach code generic over `AtTraceable`, with
+/// `fmt_message()` delegating to `E`'s `Display`. `At<E>` can't implement the
+/// trait directly: `Result<T, At<E>>` would then match both [`ResultAtExt`](crate::ResultAtExt)
+/// and [`ResultAtTraceableExt`](crate::ResultAtTraceableExt), making `.at()`
+/// ambiguous wherever both are imported (e.g. via the prelude).
+///
+/// ## Example
+///
+/// ```rust
+/// use std::error::Error;
+/// use std::fmt;
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct Rejected(u32); // Display but no Error impl
+///
+/// impl fmt::Display for Rejected {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "rejected with code {}", self.0)
+///     }
+/// }
+///
+/// fn check() -> Result<(), Box<dyn Error + Send + Sync>> {
+///     Err(at(Rejected(7)).into_std_error())?
+/// }
+///
+/// let err = check().unwrap_err();
+/// assert_eq!(err.to_string(), "rejected with code 7");
+/// ```
+pub struct AtDisplayError<E>(At<E>);
+
+impl<E> AtDisplayError<E> {
+    /// Get a reference to the wrapped `At<E>`.
+    #[inline]
+    pub fn as_at(&self) -> &At<E> {
+        &self.0
+    }
+
+    /// Unwrap back into the `At<E>`.
+    #[inline]
+    pub fn into_at(self) -> At<E> {
+        self.0
+    }
+}
+
+impl<E> At<E> {
+    /// Wrap in an adapter that implements `Error` when `E` is only `Debug + Display`.
+    ///
+    /// See [`AtDisplayError`].
+    #[inline]
+    pub fn into_std_error(self) -> AtDisplayError<E> {
+        AtDisplayError(self)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for AtDisplayError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for AtDisplayError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for AtDisplayError<E> {}
+
+impl<E: fmt::Display> crate::AtTraceable for AtDisplayError<E> {
+    #[inline]
+    fn trace_mut(&mut self) -> &mut AtTrace {
+        self.0.trace.get_or_insert_mut()
+    }
+
+    #[inline]
+    fn trace(&self) -> Option<&AtTrace> {
+        self.0.trace.as_ref()
+    }
+
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0.error, f)
+    }
+}
+
+impl<E> From<AtDisplayError<E>> for At<E> {
+    #[inline]
+    fn from(adapter: AtDisplayError<E>) -> Self {
+        adapter.0
     }
 }
 
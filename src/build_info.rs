@@ -0,0 +1,181 @@
+//! Build configuration captured at compile time.
+//!
+//! [`AtBuildInfo`] records the crate name and version, target triple, profile
+//! and a chosen set of feature flags, so field bug reports say which build
+//! produced them. [`at_cfg!`](crate::at_cfg) attaches one as a context;
+//! [`at_build_info!`](crate::at_build_info) just builds the value.
+
+use core::fmt;
+
+/// Compile-time build configuration of the crate that created it.
+///
+/// Created by [`at_build_info!`](crate::at_build_info) or attached by
+/// [`at_cfg!`](crate::at_cfg). The crate name, version, profile and features
+/// are those of the calling crate; the target triple is the one whereat was
+/// compiled for, which is the same within a build.
+///
+/// Displays on one line, e.g.
+/// `myapp 1.2.0 (x86_64-unknown-linux-gnu, release, features: serde, tracing)`.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at_build_info, AtBuildInfo};
+///
+/// let info: AtBuildInfo = at_build_info!(features = ["serde"]);
+/// assert_eq!(info.crate_name(), env!("CARGO_PKG_NAME"));
+/// assert_eq!(info.is_debug(), cfg!(debug_assertions));
+/// assert!(!info.target().is_empty());
+/// assert!(info.to_string().contains(", features: "));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AtBuildInfo {
+    crate_name: &'static str,
+    version: &'static str,
+    debug: bool,
+    /// Requested features and whether each was enabled, or `None` if none were requested.
+    features: Option<&'static [(&'static str, bool)]>,
+}
+
+impl AtBuildInfo {
+    /// Used by [`at_build_info!`](crate::at_build_info); values come from the caller's crate.
+    #[doc(hidden)]
+    #[inline]
+    pub const fn __new(
+        crate_name: &'static str,
+        version: &'static str,
+        debug: bool,
+        features: Option<&'static [(&'static str, bool)]>,
+    ) -> Self {
+        Self {
+            crate_name,
+            version,
+            debug,
+            features,
+        }
+    }
+
+    /// The crate name (`CARGO_PKG_NAME`).
+    #[inline]
+    pub const fn crate_name(&self) -> &'static str {
+        self.crate_name
+    }
+
+    /// The crate version (`CARGO_PKG_VERSION`).
+    #[inline]
+    pub const fn version(&self) -> &'static str {
+        self.version
+    }
+
+    /// The target triple, e.g. `x86_64-unknown-linux-gnu`.
+    #[inline]
+    pub const fn target(&self) -> &'static str {
+        env!("WHEREAT_TARGET")
+    }
+
+    /// Whether the crate was built with debug assertions.
+    #[inline]
+    pub const fn is_debug(&self) -> bool {
+        self.debug
+    }
+
+    /// `"debug"` or `"release"`, going by debug assertions.
+    #[inline]
+    pub const fn profile(&self) -> &'static str {
+        if self.debug { "debug" } else { "release" }
+    }
+
+    /// The requested features that were enabled, in the order given.
+    pub fn features(&self) -> impl Iterator<Item = &'static str> {
+        self.features
+            .unwrap_or(&[])
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+    }
+}
+
+impl fmt::Display for AtBuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({}, {}",
+            self.crate_name,
+            self.version,
+            self.target(),
+            self.profile()
+        )?;
+        if self.features.is_some() {
+            write!(f, ", features:")?;
+            let mut any = false;
+            for (i, feature) in self.features().enumerate() {
+                write!(f, "{}{}", if i == 0 { " " } else { ", " }, feature)?;
+                any = true;
+            }
+            if !any {
+                write!(f, " none")?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+/// Build an [`AtBuildInfo`] for the calling crate.
+///
+/// Feature flags can't be listed generically, so pass the ones worth
+/// reporting: `at_build_info!(features = ["serde", "tracing"])`. Each is
+/// checked with `cfg!(feature = ...)` in the calling crate.
+#[macro_export]
+macro_rules! at_build_info {
+    () => {
+        $crate::AtBuildInfo::__new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            cfg!(debug_assertions),
+            ::core::option::Option::None,
+        )
+    };
+    (features = [$($feature:literal),* $(,)?] $(,)?) => {
+        $crate::AtBuildInfo::__new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            cfg!(debug_assertions),
+            ::core::option::Option::Some(&[$(($feature, cfg!(feature = $feature))),*]),
+        )
+    };
+}
+
+/// Attach the calling crate's build configuration to a Result's error trace.
+///
+/// Adds one [`AtBuildInfo`] context (crate version, target triple, profile and
+/// any listed features) to the last frame, so reports from the field carry the
+/// build they came from. Retrieve it with `downcast_ref::<AtBuildInfo>()`.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, at_cfg, At, AtBuildInfo};
+///
+/// #[derive(Debug)]
+/// struct ConfigError;
+///
+/// fn load() -> Result<(), At<ConfigError>> {
+///     at_cfg!(Err(at(ConfigError)), features = ["std"])
+/// }
+///
+/// let err = load().unwrap_err();
+/// let info = err.contexts().find_map(|c| c.downcast_ref::<AtBuildInfo>()).unwrap();
+/// assert_eq!(info.profile(), if cfg!(debug_assertions) { "debug" } else { "release" });
+/// assert!(format!("{:?}", err).contains(info.target()));
+/// ```
+#[macro_export]
+macro_rules! at_cfg {
+    ($result:expr $(,)?) => {
+        $crate::ResultAtExt::at_data($result, || $crate::at_build_info!())
+    };
+    ($result:expr, features = [$($feature:literal),* $(,)?] $(,)?) => {
+        $crate::ResultAtExt::at_data($result, || {
+            $crate::at_build_info!(features = [$($feature),*])
+        })
+    };
+}
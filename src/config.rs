@@ -0,0 +1,529 @@
+//! Process-wide runtime switches for trace capture.
+//!
+//! [`set_enabled()`] turns capture off entirely; [`set_sample_rate()`] keeps
+//! traces for only one in N errors; [`configure()`] sets a whole
+//! [`AtTracePolicy`] (capture, depth limit, contexts) at once. All are plain
+//! atomics, so each capture pays a few relaxed loads. With the `portable-atomic` feature the
+//! atomics come from the [`portable-atomic`](https://docs.rs/portable-atomic)
+//! crate, for targets that lack even atomic loads and stores. Targets without
+//! native atomic read-modify-write (e.g. `thumbv6m`) advance counters with a
+//! load and a store, with or without the feature.
+//!
+//! With `std`, [`set_rate_limit()`] additionally caps how many errors per
+//! origin location get a full trace within a time window, and
+//! [`set_skip_files()`] keeps frames from helper files out of every trace.
+
+#[cfg(all(feature = "error-id", not(feature = "portable-atomic")))]
+pub(crate) use core::sync::atomic::AtomicUsize;
+#[cfg(not(feature = "portable-atomic"))]
+pub(crate) use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+#[cfg(all(feature = "error-id", feature = "portable-atomic"))]
+pub(crate) use portable_atomic::AtomicUsize;
+#[cfg(feature = "portable-atomic")]
+pub(crate) use portable_atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::trace::AT_MAX_FRAMES;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static MAX_DEPTH: AtomicU32 = AtomicU32::new(AT_MAX_FRAMES as u32);
+static CONTEXTS: AtomicBool = AtomicBool::new(true);
+static SAMPLE_RATE: AtomicU32 = AtomicU32::new(1);
+static SAMPLE_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// What traces record, set process-wide with [`configure()`].
+///
+/// Built with `const` chained setters, like
+/// [`AtTraceFormat`](crate::AtTraceFormat). Unlike the capture-level cargo
+/// features, a policy can be changed without recompiling, e.g. from a config
+/// file at startup.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, AtTracePolicy};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// // Production: locations only, at most 16 frames, no context allocation
+/// whereat::configure(AtTracePolicy::new().max_depth(16).capture_contexts(false));
+///
+/// let err = at(MyError).at_string(|| unreachable!("never called")).at();
+/// assert_eq!(err.frame_count(), 2);
+/// assert_eq!(err.contexts().count(), 0);
+/// # whereat::configure(AtTracePolicy::new());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AtTracePolicy {
+    capture: bool,
+    max_depth: usize,
+    contexts: bool,
+}
+
+impl AtTracePolicy {
+    /// The default policy: capture everything, up to [`AT_MAX_FRAMES`] frames.
+    pub const DEFAULT: Self = Self {
+        capture: true,
+        max_depth: AT_MAX_FRAMES,
+        contexts: true,
+    };
+
+    /// Create the default policy.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Record frames and contexts at all. Same as [`set_enabled()`].
+    #[inline]
+    pub const fn capture(mut self, enabled: bool) -> Self {
+        self.capture = enabled;
+        self
+    }
+
+    /// Keep at most `frames` frames per trace, clamped to `1..=AT_MAX_FRAMES`.
+    ///
+    /// A full trace keeps its oldest frames and folds the ones after them
+    /// into a `[... k frames omitted]` marker, as at [`AT_MAX_FRAMES`]. Below
+    /// 3 frames there is no room for a marker and new frames are dropped.
+    #[inline]
+    pub const fn max_depth(mut self, frames: usize) -> Self {
+        self.max_depth = if frames == 0 {
+            1
+        } else if frames > AT_MAX_FRAMES {
+            AT_MAX_FRAMES
+        } else {
+            frames
+        };
+        self
+    }
+
+    /// Record contexts (`at_str()`, `at_string()`, `at_data()`, ...).
+    ///
+    /// When off, context methods still record their frame but attach
+    /// nothing: closures are not called and no context storage is allocated,
+    /// as with the `trace-locations-only` feature. Crate boundaries are still
+    /// recorded because they determine where each location links to.
+    #[inline]
+    pub const fn capture_contexts(mut self, enabled: bool) -> Self {
+        self.contexts = enabled;
+        self
+    }
+
+    /// Whether frames and contexts are recorded at all.
+    #[inline]
+    pub const fn captures(&self) -> bool {
+        self.capture
+    }
+
+    /// The frame limit per trace.
+    #[inline]
+    pub const fn depth_limit(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Whether contexts are recorded.
+    #[inline]
+    pub const fn captures_contexts(&self) -> bool {
+        self.contexts
+    }
+}
+
+impl Default for AtTracePolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Set the process-wide [`AtTracePolicy`].
+///
+/// Takes effect for every capture from then on, in all threads; traces
+/// recorded earlier keep what they have. Sampling, rate limits and skipped
+/// files are set separately and still apply.
+#[inline]
+pub fn configure(policy: AtTracePolicy) {
+    ENABLED.store(policy.capture, Ordering::Relaxed);
+    MAX_DEPTH.store(policy.max_depth as u32, Ordering::Relaxed);
+    CONTEXTS.store(policy.contexts, Ordering::Relaxed);
+}
+
+/// The current process-wide [`AtTracePolicy`].
+#[inline]
+pub fn trace_policy() -> AtTracePolicy {
+    AtTracePolicy {
+        capture: is_enabled(),
+        max_depth: max_depth(),
+        contexts: contexts_enabled(),
+    }
+}
+
+/// The frame limit set by [`configure()`].
+#[inline]
+pub(crate) fn max_depth() -> usize {
+    MAX_DEPTH.load(Ordering::Relaxed) as usize
+}
+
+/// Whether [`configure()`] allows contexts.
+#[inline]
+pub(crate) fn contexts_enabled() -> bool {
+    CONTEXTS.load(Ordering::Relaxed)
+}
+
+/// Enable or disable trace capture process-wide.
+///
+/// While disabled, no frames or contexts are recorded: new errors carry no
+/// trace, and existing traces stop growing. Errors still propagate normally.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// whereat::set_enabled(false);
+/// assert_eq!(at(MyError).at_str("ignored").frame_count(), 0);
+///
+/// whereat::set_enabled(true);
+/// assert_eq!(at(MyError).frame_count(), 1);
+/// ```
+#[inline]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether trace capture is enabled.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Keep traces for one in every `one_in` new errors.
+///
+/// `0` and `1` trace every error (the default). The decision is made when an
+/// error would record its first frame and sticks for that error: a traced
+/// error records every later frame, a sampled-out one records none.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// whereat::set_sample_rate(4);
+/// let traced = (0..100).filter(|_| at(MyError).frame_count() > 0).count();
+/// assert_eq!(traced, 25);
+/// # whereat::set_sample_rate(1);
+/// ```
+#[inline]
+pub fn set_sample_rate(one_in: u32) {
+    SAMPLE_RATE.store(one_in, Ordering::Relaxed);
+}
+
+/// The current sampling rate (see [`set_sample_rate()`]).
+#[inline]
+pub fn sample_rate() -> u32 {
+    SAMPLE_RATE.load(Ordering::Relaxed).max(1)
+}
+
+/// Whether an empty trace may record its first frame now.
+#[inline]
+pub(crate) fn should_start_trace() -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    let rate = SAMPLE_RATE.load(Ordering::Relaxed);
+    rate <= 1 || next_sample() % rate == 0
+}
+
+/// Advance the sampling counter, returning its previous value.
+#[cfg(target_has_atomic = "32")]
+#[inline]
+fn next_sample() -> u32 {
+    SAMPLE_COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Advance the sampling counter, returning its previous value.
+///
+/// Without native read-modify-write atomics (e.g. `thumbv6m`) this is a load
+/// and a store: an error created in an interrupt handler between the two may
+/// reuse a count, which only skews the sampling ratio slightly.
+#[cfg(not(target_has_atomic = "32"))]
+#[inline]
+fn next_sample() -> u32 {
+    let n = SAMPLE_COUNTER.load(Ordering::Relaxed);
+    SAMPLE_COUNTER.store(n.wrapping_add(1), Ordering::Relaxed);
+    n
+}
+
+// ============================================================================
+// Per-location rate limiting (std)
+// ============================================================================
+
+#[cfg(feature = "std")]
+mod rate_limit {
+    use core::panic::Location;
+    use core::sync::atomic::AtomicPtr;
+    use core::time::Duration;
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    use super::{AtomicBool, AtomicU32, Ordering};
+
+    /// Whether a limit is set, so the unlimited path touches nothing else.
+    static ACTIVE: AtomicBool = AtomicBool::new(false);
+    static MAX: AtomicU32 = AtomicU32::new(0);
+    static WINDOW_MS: AtomicU32 = AtomicU32::new(0);
+
+    /// How many origins are counted at once; errors from further origins get
+    /// a full trace.
+    const SLOTS: usize = 128;
+    /// How many slots an origin may probe before giving up.
+    const PROBES: usize = 8;
+
+    /// Counts for one origin, claimed by the first error from it.
+    struct Slot {
+        origin: AtomicPtr<Location<'static>>,
+        count: AtomicU32,
+        /// Window start, as returned by [`now_ms()`].
+        start: AtomicU32,
+    }
+
+    impl Slot {
+        const fn new() -> Self {
+            Self {
+                origin: AtomicPtr::new(core::ptr::null_mut()),
+                count: AtomicU32::new(0),
+                start: AtomicU32::new(0),
+            }
+        }
+    }
+
+    static TABLE: [Slot; SLOTS] = [const { Slot::new() }; SLOTS];
+
+    /// Give only the first `max_per_location` errors from each origin a full trace per `window`.
+    ///
+    /// Later errors from the same origin within the window keep their origin
+    /// frame plus a `warn: N similar errors suppressed` note, and record no
+    /// further frames or contexts, so a hot failure loop can't cause an
+    /// allocation storm. The origin is where an error's trace starts (usually
+    /// the `at()` call).
+    ///
+    /// Counting takes no lock: each origin gets an atomic counter in a fixed
+    /// table of 128, and the clock is only read once an origin is over its
+    /// limit, to see whether its window has elapsed. So the window restarts
+    /// lazily, with the first error over the limit after it elapsed, and
+    /// errors from origins beyond the first 128 seen are not limited.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// fn hot() -> At<MyError> {
+    ///     at(MyError).at_str("full context")
+    /// }
+    ///
+    /// whereat::set_rate_limit(2, Duration::from_secs(60));
+    /// let errors: Vec<_> = (0..5).map(|_| hot().at()).collect();
+    ///
+    /// assert_eq!(errors[1].frame_count(), 2);
+    /// assert!(!errors[1].trace().unwrap().is_rate_limited());
+    ///
+    /// let limited = errors[4].trace().unwrap();
+    /// assert_eq!(limited.suppressed_count(), Some(3));
+    /// assert_eq!(errors[4].frame_count(), 1);
+    /// assert_eq!(errors[4].texts().count(), 0);
+    /// assert!(format!("{:?}", errors[4]).contains("warn: 3 similar errors suppressed"));
+    /// # whereat::clear_rate_limit();
+    /// ```
+    pub fn set_rate_limit(max_per_location: u32, window: Duration) {
+        ACTIVE.store(false, Ordering::Relaxed);
+        reset();
+        MAX.store(max_per_location, Ordering::Relaxed);
+        let window = window.as_millis().min(i32::MAX as u128) as u32;
+        WINDOW_MS.store(window, Ordering::Relaxed);
+        ACTIVE.store(true, Ordering::Relaxed);
+    }
+
+    /// Remove the rate limit set by [`set_rate_limit()`], forgetting all counts.
+    pub fn clear_rate_limit() {
+        ACTIVE.store(false, Ordering::Relaxed);
+        reset();
+    }
+
+    fn reset() {
+        for slot in &TABLE {
+            slot.origin.store(core::ptr::null_mut(), Ordering::Relaxed);
+            slot.count.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Count an error starting at `origin`.
+    ///
+    /// Returns how many errors from this origin have been suppressed in the
+    /// current window, including this one, or 0 if it gets a full trace.
+    pub(crate) fn check(origin: &'static Location<'static>) -> u32 {
+        if !ACTIVE.load(Ordering::Relaxed) {
+            return 0;
+        }
+        let Some(slot) = slot_for(origin) else {
+            return 0;
+        };
+        let count = slot.count.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+        let max = MAX.load(Ordering::Relaxed);
+        if count <= max {
+            return 0;
+        }
+        let now = now_ms();
+        let start = slot.start.load(Ordering::Relaxed);
+        if now.wrapping_sub(start) >= WINDOW_MS.load(Ordering::Relaxed) {
+            // The window elapsed: this error is the first of a new one
+            slot.start.store(now, Ordering::Relaxed);
+            slot.count.store(1, Ordering::Relaxed);
+            return 1u32.saturating_sub(max);
+        }
+        count - max
+    }
+
+    /// The counter slot for `origin`, claiming a free one on its first error.
+    fn slot_for(origin: &'static Location<'static>) -> Option<&'static Slot> {
+        let ptr = origin as *const Location<'static> as *mut Location<'static>;
+        let hash = (ptr as usize).wrapping_mul(0x9E37_79B9_7F4A_7C15_u64 as usize);
+        let first = hash >> (usize::BITS - SLOTS.trailing_zeros());
+        for i in 0..PROBES {
+            let slot = &TABLE[(first + i) % SLOTS];
+            let current = slot.origin.load(Ordering::Relaxed);
+            if current == ptr {
+                return Some(slot);
+            }
+            if current.is_null() {
+                match slot.origin.compare_exchange(
+                    core::ptr::null_mut(),
+                    ptr,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        slot.start.store(now_ms(), Ordering::Relaxed);
+                        return Some(slot);
+                    }
+                    Err(winner) if winner == ptr => return Some(slot),
+                    Err(_) => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Milliseconds since the first call, wrapping every ~49 days.
+    fn now_ms() -> u32 {
+        static EPOCH: OnceLock<Instant> = OnceLock::new();
+        EPOCH.get_or_init(Instant::now).elapsed().as_millis() as u32
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) use rate_limit::check as rate_limit_check;
+#[cfg(feature = "std")]
+pub use rate_limit::{clear_rate_limit, set_rate_limit};
+
+// ============================================================================
+// Skipped files (std)
+// ============================================================================
+
+#[cfg(feature = "std")]
+mod skip_files {
+    use core::panic::Location;
+    use std::sync::RwLock;
+
+    use super::{AtomicBool, Ordering};
+
+    /// Whether any patterns are set, so the common path never takes the lock.
+    static ACTIVE: AtomicBool = AtomicBool::new(false);
+    static PATTERNS: RwLock<&'static [&'static str]> = RwLock::new(&[]);
+
+    /// Never record frames whose file matches one of `patterns`.
+    ///
+    /// Patterns are matched against [`Location::file()`] (usually a path
+    /// relative to the workspace root, e.g. `src/db/exec.rs`); `*` matches any
+    /// run of characters, including `/`. Replaces any previously set list.
+    ///
+    /// Use this for ubiquitous wrappers that would otherwise appear in every
+    /// trace. Contexts added from a skipped file go to the previous frame, or
+    /// are dropped if the trace is still empty. If you own the wrapper,
+    /// marking it `#[track_caller]` is cheaper: its callers' locations are
+    /// then recorded instead, with no runtime check.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// // Usually patterns like "src/db/exec.rs" or "*/helpers/*"
+    /// whereat::set_skip_files(&[file!()]);
+    /// let err = at(MyError).at_str("dropped with its frame").at();
+    /// assert_eq!(err.frame_count(), 0);
+    ///
+    /// whereat::clear_skip_files();
+    /// assert_eq!(at(MyError).frame_count(), 1);
+    /// ```
+    pub fn set_skip_files(patterns: &'static [&'static str]) {
+        let mut current = PATTERNS.write().unwrap_or_else(|e| e.into_inner());
+        *current = patterns;
+        ACTIVE.store(!patterns.is_empty(), Ordering::Relaxed);
+    }
+
+    /// Remove the patterns set by [`set_skip_files()`].
+    pub fn clear_skip_files() {
+        set_skip_files(&[]);
+    }
+
+    /// Check if frames at `location` should not be recorded.
+    #[inline]
+    pub(crate) fn is_skipped(location: &'static Location<'static>) -> bool {
+        if !ACTIVE.load(Ordering::Relaxed) {
+            return false;
+        }
+        let patterns = PATTERNS.read().unwrap_or_else(|e| e.into_inner());
+        patterns.iter().any(|p| glob_match(p, location.file()))
+    }
+
+    /// Match `text` against `pattern`, where `*` matches any run of characters.
+    fn glob_match(pattern: &str, text: &str) -> bool {
+        let mut parts = pattern.split('*');
+        let Some(first) = parts.next() else {
+            return text.is_empty();
+        };
+        let Some(mut rest) = text.strip_prefix(first) else {
+            return false;
+        };
+        let mut parts = parts.peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                return rest.ends_with(part);
+            }
+            match rest.find(part) {
+                Some(i) => rest = &rest[i + part.len()..],
+                None => return false,
+            }
+        }
+        // No `*` at all: the whole text must have been the prefix
+        rest.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) use skip_files::is_skipped;
+#[cfg(feature = "std")]
+pub use skip_files::{clear_skip_files, set_skip_files};
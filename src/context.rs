@@ -5,9 +5,12 @@
 
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
-use core::fmt;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write as _};
 
-use crate::AtCrateInfo;
+use crate::trace::AT_MAX_CONTEXT_BYTES;
+use crate::{AtCrateInfo, AtFrameDetached, AtTraceDetached};
 
 // ============================================================================
 // AtDebugAny Trait - combines Any + Debug in a single trait object
@@ -62,6 +65,143 @@ impl<T: core::any::Any + fmt::Display + Send + Sync> AtDisplayAny for T {
     }
 }
 
+// ============================================================================
+// AtLevel - context severity
+// ============================================================================
+
+/// Severity of a context entry, used to hide verbose breadcrumbs.
+///
+/// Contexts added with [`at_note()`](crate::At::at_note) are [`Note`](Self::Note)
+/// and with [`at_warn()`](crate::At::at_warn) are [`Warn`](Self::Warn). Every other
+/// context is [`Error`](Self::Error), so a threshold never hides it. Filter with
+/// [`AtTraceFormat::min_level()`](crate::AtTraceFormat::min_level).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AtLevel {
+    /// Verbose breadcrumb, useful when debugging.
+    Note,
+    /// Something unusual that may explain the error.
+    Warn,
+    /// Regular context (the level of all unleveled contexts).
+    #[default]
+    Error,
+}
+
+impl AtLevel {
+    /// The lowercase label used by formatters (`"note"`, `"warn"`, `"error"`).
+    #[inline]
+    pub const fn label(self) -> &'static str {
+        match self {
+            AtLevel::Note => "note",
+            AtLevel::Warn => "warn",
+            AtLevel::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for AtLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+// ============================================================================
+// AtContextKind - public discriminant of a context entry
+// ============================================================================
+
+/// What kind of context an entry is, for formatters that render each kind differently.
+///
+/// Returned by [`AtContextRef::kind()`]. New kinds may be added in minor
+/// releases, so match with a wildcard arm; unknown kinds can fall back to the
+/// context's `Display` output.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AtContextKind {
+    /// Text from `at_str()` / `at_string()`.
+    Text,
+    /// A function name from `at_fn()` / `at_named()`.
+    FunctionName,
+    /// Typed data from `at_debug()`, rendered with `{:?}`.
+    Debug,
+    /// Typed data from `at_data()`, rendered with `{}`.
+    Display,
+    /// A crate boundary marker from `at_crate()`.
+    Crate,
+    /// An attached source error from `at_error()`.
+    Error,
+    /// A `help:` hint from `at_help()`.
+    Suggestion,
+    /// Text with a severity from `at_note()` / `at_warn()`.
+    Leveled,
+    /// A "see also" link from `at_link()`.
+    Link,
+    /// The `tracing` span id recorded at capture time.
+    SpanId,
+    /// A `key=value` pair, e.g. a `tracing` span field.
+    KeyValue,
+    /// A high-priority message from `at_pinned()`, shown under the error header.
+    Pinned,
+    /// An expected/actual pair from `at_expected_actual()`.
+    ExpectedActual,
+    /// Raw bytes from `at_bytes()`, rendered as a hex dump.
+    Bytes,
+    /// A `std::backtrace::Backtrace` from `at_backtrace()`, shown after the frames.
+    Backtrace,
+    /// How many identical consecutive frames were collapsed by `dedup_trace()`.
+    Repeated,
+    /// Sensitive typed data from `at_secret()`, rendered as `[redacted]`.
+    Secret,
+    /// A trace from another process, attached with `at_remote_trace()`.
+    Remote,
+}
+
+impl AtContextKind {
+    /// The `snake_case` name used in JSON output, e.g. `"text"` or `"key_value"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            AtContextKind::Text => "text",
+            AtContextKind::FunctionName => "function_name",
+            AtContextKind::Debug => "debug",
+            AtContextKind::Display => "display",
+            AtContextKind::Crate => "crate",
+            AtContextKind::Error => "error",
+            AtContextKind::Suggestion => "suggestion",
+            AtContextKind::Leveled => "leveled",
+            AtContextKind::Link => "link",
+            AtContextKind::SpanId => "span_id",
+            AtContextKind::KeyValue => "key_value",
+            AtContextKind::Pinned => "pinned",
+            AtContextKind::ExpectedActual => "expected_actual",
+            AtContextKind::Bytes => "bytes",
+            AtContextKind::Backtrace => "backtrace",
+            AtContextKind::Repeated => "repeated",
+            AtContextKind::Secret => "secret",
+            AtContextKind::Remote => "remote",
+        }
+    }
+
+    /// Every kind, in declaration order (`kind as usize` indexes into it).
+    pub(crate) const ALL: [AtContextKind; 18] = [
+        AtContextKind::Text,
+        AtContextKind::FunctionName,
+        AtContextKind::Debug,
+        AtContextKind::Display,
+        AtContextKind::Crate,
+        AtContextKind::Error,
+        AtContextKind::Suggestion,
+        AtContextKind::Leveled,
+        AtContextKind::Link,
+        AtContextKind::SpanId,
+        AtContextKind::KeyValue,
+        AtContextKind::Pinned,
+        AtContextKind::ExpectedActual,
+        AtContextKind::Bytes,
+        AtContextKind::Backtrace,
+        AtContextKind::Repeated,
+        AtContextKind::Secret,
+        AtContextKind::Remote,
+    ];
+}
+
 // ============================================================================
 // AtContext Enum (internal)
 // ============================================================================
@@ -90,9 +230,76 @@ pub(crate) enum AtContext {
     /// A source error attached as context.
     /// Allows embedding error chains within the trace.
     Error(Box<dyn core::error::Error + Send + Sync>),
+    /// An actionable remediation hint, rendered in a separate "help:" section.
+    Suggestion(Cow<'static, str>),
+    /// A high-priority message, rendered directly under the error header.
+    Pinned(Cow<'static, str>),
+    /// A text message with an explicit severity (from `at_note`/`at_warn`).
+    Leveled(AtLevel, Cow<'static, str>),
+    /// A "see also" link to a runbook, dashboard, or docs page.
+    Link {
+        label: Cow<'static, str>,
+        url: Cow<'static, str>,
+    },
+    /// The `tracing` span that was current when the frame was captured.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    SpanId(u64),
+    /// A structured `key=value` pair (e.g. a `tracing` span field).
+    KeyValue {
+        key: Cow<'static, str>,
+        value: Cow<'static, str>,
+    },
+    /// An expected value and the actual one, rendered as an aligned block.
+    ExpectedActual(Box<AtExpectedActual>),
+    /// A bounded copy of raw bytes, rendered as a hex dump.
+    Bytes(Box<AtBytes>),
+    /// A captured backtrace (`std-backtrace`), rendered after the frames.
+    #[cfg_attr(not(feature = "std-backtrace"), allow(dead_code))]
+    Backtrace(Box<dyn AtDisplayAny>),
+    /// Repeat count of a frame that absorbed identical consecutive frames.
+    /// Rendered on the frame's location line as `(x17)`.
+    Repeated(u32),
+    /// Sensitive typed data, rendered as [`REDACTED`] unless a format reveals it.
+    Secret(Box<dyn AtDebugAny>),
+    /// A trace received from another process, rendered as an indented block.
+    Remote(Box<AtTraceDetached>),
 }
 
+/// What sensitive contexts (`at_secret`) render as.
+pub(crate) const REDACTED: &str = "[redacted]";
+
 impl AtContext {
+    /// Approximate heap bytes owned by this context (not counting the entry itself).
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let cow = |s: &Cow<'static, str>| match s {
+            Cow::Owned(s) => s.capacity(),
+            Cow::Borrowed(_) => 0,
+        };
+        match self {
+            AtContext::Text(s) | AtContext::Suggestion(s) | AtContext::Pinned(s) => cow(s),
+            AtContext::Leveled(_, s) => cow(s),
+            AtContext::Link { label, url } => cow(label) + cow(url),
+            AtContext::KeyValue { key, value } => cow(key) + cow(value),
+            AtContext::Debug(t) | AtContext::Secret(t) => core::mem::size_of_val(&**t),
+            AtContext::Display(t) | AtContext::Backtrace(t) => core::mem::size_of_val(&**t),
+            AtContext::Error(e) => core::mem::size_of_val(&**e),
+            AtContext::ExpectedActual(pair) => {
+                core::mem::size_of::<AtExpectedActual>()
+                    + core::mem::size_of_val(&*pair.expected)
+                    + core::mem::size_of_val(&*pair.actual)
+            }
+            AtContext::Bytes(bytes) => core::mem::size_of::<AtBytes>() + bytes.data.capacity(),
+            AtContext::Remote(trace) => {
+                core::mem::size_of::<AtTraceDetached>()
+                    + trace.frame_count() * core::mem::size_of::<AtFrameDetached>()
+            }
+            AtContext::FunctionName(_)
+            | AtContext::Crate(_)
+            | AtContext::SpanId(_)
+            | AtContext::Repeated(_) => 0,
+        }
+    }
+
     pub(crate) fn as_text(&self) -> Option<&str> {
         match self {
             AtContext::Text(s) => Some(s),
@@ -107,6 +314,69 @@ impl AtContext {
         }
     }
 
+    pub(crate) fn as_suggestion(&self) -> Option<&str> {
+        match self {
+            AtContext::Suggestion(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_pinned(&self) -> Option<&str> {
+        match self {
+            AtContext::Pinned(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_leveled(&self) -> Option<(AtLevel, &str)> {
+        match self {
+            AtContext::Leveled(level, s) => Some((*level, s)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_link(&self) -> Option<(&str, &str)> {
+        match self {
+            AtContext::Link { label, url } => Some((label, url)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_kv(&self) -> Option<(&str, &str)> {
+        match self {
+            AtContext::KeyValue { key, value } => Some((key, value)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_expected_actual(&self) -> Option<&AtExpectedActual> {
+        match self {
+            AtContext::ExpectedActual(pair) => Some(pair),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> Option<&AtBytes> {
+        match self {
+            AtContext::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_remote_trace(&self) -> Option<&AtTraceDetached> {
+        match self {
+            AtContext::Remote(trace) => Some(trace),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn level(&self) -> AtLevel {
+        match self {
+            AtContext::Leveled(level, _) => *level,
+            _ => AtLevel::Error,
+        }
+    }
+
     pub(crate) fn as_crate_info(&self) -> Option<&'static AtCrateInfo> {
         match self {
             AtContext::Crate(info) => Some(info),
@@ -114,6 +384,14 @@ impl AtContext {
         }
     }
 
+    #[cfg(feature = "tracing")]
+    pub(crate) fn as_span_id(&self) -> Option<u64> {
+        match self {
+            AtContext::SpanId(id) => Some(*id),
+            _ => None,
+        }
+    }
+
     pub(crate) fn as_error(&self) -> Option<&(dyn core::error::Error + Send + Sync)> {
         match self {
             AtContext::Error(e) => Some(&**e),
@@ -126,11 +404,21 @@ impl AtContext {
             AtContext::Text(_)
             | AtContext::FunctionName(_)
             | AtContext::Crate(_)
-            | AtContext::Error(_) => None,
+            | AtContext::Error(_)
+            | AtContext::Suggestion(_)
+            | AtContext::Pinned(_)
+            | AtContext::Leveled(..)
+            | AtContext::Link { .. }
+            | AtContext::SpanId(_)
+            | AtContext::KeyValue { .. }
+            | AtContext::ExpectedActual(_)
+            | AtContext::Bytes(_)
+            | AtContext::Repeated(_)
+            | AtContext::Remote(_) => None,
             // Must use (**b) to call as_any on the trait object, not the Box
             // (Box<dyn AtDebugAny> itself implements AtDebugAny through the blanket impl)
-            AtContext::Debug(b) => (**b).as_any().downcast_ref(),
-            AtContext::Display(b) => (**b).as_any().downcast_ref(),
+            AtContext::Debug(b) | AtContext::Secret(b) => (**b).as_any().downcast_ref(),
+            AtContext::Display(b) | AtContext::Backtrace(b) => (**b).as_any().downcast_ref(),
         }
     }
 
@@ -139,16 +427,29 @@ impl AtContext {
             AtContext::Text(_)
             | AtContext::FunctionName(_)
             | AtContext::Crate(_)
-            | AtContext::Error(_) => None,
-            AtContext::Debug(b) => Some((**b).type_name()),
-            AtContext::Display(b) => Some((**b).type_name()),
+            | AtContext::Error(_)
+            | AtContext::Suggestion(_)
+            | AtContext::Pinned(_)
+            | AtContext::Leveled(..)
+            | AtContext::Link { .. }
+            | AtContext::SpanId(_)
+            | AtContext::KeyValue { .. }
+            | AtContext::ExpectedActual(_)
+            | AtContext::Bytes(_)
+            | AtContext::Repeated(_)
+            | AtContext::Remote(_) => None,
+            AtContext::Debug(b) | AtContext::Secret(b) => Some((**b).type_name()),
+            AtContext::Display(b) | AtContext::Backtrace(b) => Some((**b).type_name()),
         }
     }
 
     pub(crate) fn is_display(&self) -> bool {
         matches!(
             self,
-            AtContext::Text(_) | AtContext::Display(_) | AtContext::Error(_)
+            AtContext::Text(_)
+                | AtContext::Display(_)
+                | AtContext::Error(_)
+                | AtContext::Leveled(..)
         )
     }
 
@@ -160,9 +461,33 @@ impl AtContext {
         matches!(self, AtContext::Crate(_))
     }
 
+    /// Whether this is an id from `at_correlation()`, kept at every capture level.
+    pub(crate) fn is_correlation(&self) -> bool {
+        matches!(self, AtContext::Display(b) if (**b).as_any().is::<AtCorrelationId>())
+    }
+
     pub(crate) fn is_error(&self) -> bool {
         matches!(self, AtContext::Error(_))
     }
+
+    pub(crate) fn is_suggestion(&self) -> bool {
+        matches!(self, AtContext::Suggestion(_))
+    }
+
+    pub(crate) fn is_pinned(&self) -> bool {
+        matches!(self, AtContext::Pinned(_))
+    }
+
+    pub(crate) fn is_backtrace(&self) -> bool {
+        matches!(self, AtContext::Backtrace(_))
+    }
+
+    pub(crate) fn repeat_count(&self) -> Option<u32> {
+        match self {
+            AtContext::Repeated(n) => Some(*n),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for AtContext {
@@ -174,6 +499,18 @@ impl fmt::Debug for AtContext {
             AtContext::Display(t) => write!(f, "{}", &**t), // Display types use Display even in Debug
             AtContext::Crate(info) => write!(f, "[crate: {}]", info.name()),
             AtContext::Error(e) => write!(f, "caused by: {}", e),
+            AtContext::Suggestion(s) => write!(f, "help: {}", s),
+            AtContext::Pinned(s) => write!(f, "pinned: {:?}", s),
+            AtContext::Leveled(level, s) => write!(f, "{}: {:?}", level, s),
+            AtContext::Link { label, url } => write!(f, "see: {} ({})", label, url),
+            AtContext::SpanId(id) => write!(f, "span: {}", id),
+            AtContext::KeyValue { key, value } => write!(f, "{}={}", key, value),
+            AtContext::ExpectedActual(pair) => write!(f, "{}", pair),
+            AtContext::Bytes(bytes) => write!(f, "{}", bytes),
+            AtContext::Backtrace(bt) => write!(f, "backtrace:\n{}", &**bt),
+            AtContext::Repeated(n) => write!(f, "repeated {} times", n),
+            AtContext::Secret(_) => f.write_str(REDACTED),
+            AtContext::Remote(trace) => write!(f, "remote trace:\n{}", trace),
         }
     }
 }
@@ -187,6 +524,18 @@ impl fmt::Display for AtContext {
             AtContext::Display(t) => write!(f, "{}", &**t),
             AtContext::Crate(info) => write!(f, "[crate: {}]", info.name()),
             AtContext::Error(e) => write!(f, "caused by: {}", e),
+            AtContext::Suggestion(s) => write!(f, "help: {}", s),
+            AtContext::Pinned(s) => write!(f, "{}", s),
+            AtContext::Leveled(level, s) => write!(f, "{}: {}", level, s),
+            AtContext::Link { label, url } => write!(f, "see: {} ({})", label, url),
+            AtContext::SpanId(id) => write!(f, "span: {}", id),
+            AtContext::KeyValue { key, value } => write!(f, "{}={}", key, value),
+            AtContext::ExpectedActual(pair) => write!(f, "{}", pair),
+            AtContext::Bytes(bytes) => write!(f, "{}", bytes),
+            AtContext::Backtrace(bt) => write!(f, "backtrace:\n{}", &**bt),
+            AtContext::Repeated(n) => write!(f, "repeated {} times", n),
+            AtContext::Secret(_) => f.write_str(REDACTED),
+            AtContext::Remote(trace) => write!(f, "remote trace:\n{}", trace),
         }
     }
 }
@@ -306,6 +655,117 @@ impl<'a> AtContextRef<'a> {
     pub fn is_error(&self) -> bool {
         self.inner.is_error()
     }
+
+    /// Get the hint text, if this is a suggestion context (from `at_help`).
+    #[inline]
+    pub fn as_suggestion(&self) -> Option<&'a str> {
+        self.inner.as_suggestion()
+    }
+
+    /// Check if this is a suggestion context.
+    #[inline]
+    pub fn is_suggestion(&self) -> bool {
+        self.inner.is_suggestion()
+    }
+
+    /// Get the message, if this is a pinned context (from `at_pinned`).
+    #[inline]
+    pub fn as_pinned(&self) -> Option<&'a str> {
+        self.inner.as_pinned()
+    }
+
+    /// Check if this is a pinned context.
+    #[inline]
+    pub fn is_pinned(&self) -> bool {
+        self.inner.is_pinned()
+    }
+
+    /// Get the level and text, if this context was added with `at_note` or `at_warn`.
+    #[inline]
+    pub fn as_leveled(&self) -> Option<(AtLevel, &'a str)> {
+        self.inner.as_leveled()
+    }
+
+    /// Get the label and URL, if this is a link context (from `at_link`).
+    #[inline]
+    pub fn as_link(&self) -> Option<(&'a str, &'a str)> {
+        self.inner.as_link()
+    }
+
+    /// Get as a `(key, value)` pair, if this is a key-value context.
+    #[inline]
+    pub fn as_kv(&self) -> Option<(&'a str, &'a str)> {
+        self.inner.as_kv()
+    }
+
+    /// Get the pair, if this context was added with `at_expected_actual`.
+    #[inline]
+    pub fn as_expected_actual(&self) -> Option<&'a AtExpectedActual> {
+        self.inner.as_expected_actual()
+    }
+
+    /// Get the bytes, if this context was added with `at_bytes`.
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&'a AtBytes> {
+        self.inner.as_bytes()
+    }
+
+    /// Get the trace, if this context was added with `at_remote_trace`.
+    #[inline]
+    pub fn as_remote_trace(&self) -> Option<&'a AtTraceDetached> {
+        self.inner.as_remote_trace()
+    }
+
+    /// Get the `tracing` span id, if this context was recorded at capture time.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn as_span_id(&self) -> Option<u64> {
+        self.inner.as_span_id()
+    }
+
+    /// The kind of this context.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtContextKind};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError).at_str("loading").at_debug(|| 7u8).at_help("retry later");
+    /// let kinds: Vec<_> = err.contexts().map(|c| c.kind()).collect();
+    /// assert_eq!(kinds, [AtContextKind::Suggestion, AtContextKind::Debug, AtContextKind::Text]);
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> AtContextKind {
+        match self.inner {
+            AtContext::Text(_) => AtContextKind::Text,
+            AtContext::FunctionName(_) => AtContextKind::FunctionName,
+            AtContext::Debug(_) => AtContextKind::Debug,
+            AtContext::Display(_) => AtContextKind::Display,
+            AtContext::Crate(_) => AtContextKind::Crate,
+            AtContext::Error(_) => AtContextKind::Error,
+            AtContext::Suggestion(_) => AtContextKind::Suggestion,
+            AtContext::Pinned(_) => AtContextKind::Pinned,
+            AtContext::Leveled(..) => AtContextKind::Leveled,
+            AtContext::Link { .. } => AtContextKind::Link,
+            AtContext::SpanId(_) => AtContextKind::SpanId,
+            AtContext::KeyValue { .. } => AtContextKind::KeyValue,
+            AtContext::ExpectedActual(_) => AtContextKind::ExpectedActual,
+            AtContext::Bytes(_) => AtContextKind::Bytes,
+            AtContext::Backtrace(_) => AtContextKind::Backtrace,
+            AtContext::Repeated(_) => AtContextKind::Repeated,
+            AtContext::Secret(_) => AtContextKind::Secret,
+            AtContext::Remote(_) => AtContextKind::Remote,
+        }
+    }
+
+    /// The severity of this context ([`AtLevel::Error`] unless set via `at_note`/`at_warn`).
+    #[inline]
+    pub fn level(&self) -> AtLevel {
+        self.inner.level()
+    }
 }
 
 impl fmt::Debug for AtContextRef<'_> {
@@ -319,3 +779,490 @@ impl fmt::Display for AtContextRef<'_> {
         fmt::Display::fmt(self.inner, f)
     }
 }
+
+// ============================================================================
+// AtContextOwned - Owned context detached from a trace
+// ============================================================================
+
+/// A context entry owned outside of any trace.
+///
+/// Returned by [`At::take_contexts()`](crate::At::take_contexts) and
+/// [`AtTrace::drain_contexts()`](crate::AtTrace::drain_contexts). Use
+/// [`as_ref()`](Self::as_ref) for the same accessors as [`AtContextRef`].
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// let mut err = at(MyError).at_str("rendering invoice").at_debug(|| 42u32);
+/// let contexts = err.take_contexts();
+///
+/// assert_eq!(contexts.len(), 2);
+/// assert_eq!(contexts[0].1.as_ref().as_text(), Some("rendering invoice"));
+/// assert_eq!(contexts[1].1.as_ref().downcast_ref::<u32>(), Some(&42));
+/// assert_eq!(err.frame_count(), 1); // locations are kept
+/// ```
+pub struct AtContextOwned {
+    pub(crate) inner: AtContext,
+}
+
+impl AtContextOwned {
+    /// A static string context, like [`at_str()`](crate::At::at_str).
+    #[inline]
+    pub fn text(msg: &'static str) -> Self {
+        Self {
+            inner: AtContext::Text(Cow::Borrowed(msg)),
+        }
+    }
+
+    /// An owned string context, like [`at_string()`](crate::At::at_string).
+    #[inline]
+    pub fn string(msg: String) -> Self {
+        Self {
+            inner: AtContext::Text(Cow::Owned(msg)),
+        }
+    }
+
+    /// Typed context formatted via Display, like [`at_data()`](crate::At::at_data).
+    #[inline]
+    pub fn data<T: fmt::Display + Send + Sync + 'static>(data: T) -> Self {
+        Self {
+            inner: AtContext::Display(Box::new(data)),
+        }
+    }
+
+    /// Typed context formatted via Debug, like [`at_debug()`](crate::At::at_debug).
+    #[inline]
+    pub fn debug<T: fmt::Debug + Send + Sync + 'static>(data: T) -> Self {
+        Self {
+            inner: AtContext::Debug(Box::new(data)),
+        }
+    }
+
+    /// Sensitive typed context, like [`at_secret()`](crate::At::at_secret).
+    #[inline]
+    pub fn secret<T: fmt::Debug + Send + Sync + 'static>(data: T) -> Self {
+        Self {
+            inner: AtContext::Secret(Box::new(data)),
+        }
+    }
+
+    /// A source error, like [`at_error()`](crate::At::at_error).
+    #[inline]
+    pub fn error<E: core::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        Self {
+            inner: AtContext::Error(Box::new(err)),
+        }
+    }
+
+    /// A remediation hint, like [`at_help()`](crate::At::at_help).
+    #[inline]
+    pub fn help(msg: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            inner: AtContext::Suggestion(msg.into()),
+        }
+    }
+
+    /// A high-priority message, like [`at_pinned()`](crate::At::at_pinned).
+    #[inline]
+    pub fn pinned(msg: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            inner: AtContext::Pinned(msg.into()),
+        }
+    }
+
+    /// A text context with a severity, like [`at_note()`](crate::At::at_note).
+    #[inline]
+    pub fn leveled(level: AtLevel, msg: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            inner: AtContext::Leveled(level, msg.into()),
+        }
+    }
+
+    /// A "see also" link, like [`at_link()`](crate::At::at_link).
+    #[inline]
+    pub fn link(label: impl Into<Cow<'static, str>>, url: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            inner: AtContext::Link {
+                label: label.into(),
+                url: url.into(),
+            },
+        }
+    }
+
+    /// An expected/actual pair, like [`at_expected_actual()`](crate::At::at_expected_actual).
+    #[inline]
+    pub fn expected_actual<T, U>(expected: T, actual: U) -> Self
+    where
+        T: fmt::Debug + Send + Sync + 'static,
+        U: fmt::Debug + Send + Sync + 'static,
+    {
+        Self {
+            inner: AtContext::ExpectedActual(Box::new(AtExpectedActual::new(expected, actual))),
+        }
+    }
+
+    /// A bounded copy of raw bytes, like [`at_bytes()`](crate::At::at_bytes).
+    #[inline]
+    pub fn bytes(label: &'static str, bytes: &[u8]) -> Self {
+        Self {
+            inner: AtContext::Bytes(Box::new(AtBytes::copy_from(label, bytes))),
+        }
+    }
+
+    /// Borrow as an [`AtContextRef`] for inspection.
+    #[inline]
+    pub fn as_ref(&self) -> AtContextRef<'_> {
+        AtContextRef { inner: &self.inner }
+    }
+}
+
+impl fmt::Debug for AtContextOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Display for AtContextOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+// ============================================================================
+// AtCorrelationId - externally supplied request/correlation id
+// ============================================================================
+
+/// An externally supplied correlation or request id attached to a trace.
+///
+/// Attach with [`at_correlation()`](crate::At::at_correlation) and read back with
+/// [`correlation_id()`](crate::At::correlation_id). Stored as typed context, so it
+/// shows up in every trace formatter as `correlation id: <id>` and can be found via
+/// [`AtContextRef::downcast_ref`].
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, AtCorrelationId};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// let err = at(MyError).at_correlation("req-7f3a");
+/// assert_eq!(err.correlation_id(), Some("req-7f3a"));
+///
+/// let ctx = err.contexts().next().unwrap();
+/// let id = ctx.downcast_ref::<AtCorrelationId>().unwrap();
+/// assert_eq!(id.to_string(), "correlation id: req-7f3a");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AtCorrelationId(Cow<'static, str>);
+
+impl AtCorrelationId {
+    /// Create a correlation id from a static or owned string.
+    #[inline]
+    pub fn new(id: impl Into<Cow<'static, str>>) -> Self {
+        Self(id.into())
+    }
+
+    /// The raw id, without the `correlation id:` label.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AtCorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "correlation id: {}", self.0)
+    }
+}
+
+// ============================================================================
+// AtExpectedActual - an expected/actual value pair
+// ============================================================================
+
+/// An expected and an actual value, attached with [`at_expected_actual()`](crate::At::at_expected_actual).
+///
+/// Both values keep their types and can be read back with
+/// [`expected()`](Self::expected) / [`actual()`](Self::actual). Trace formatters
+/// render the pair as an aligned block; when both are strings, a caret marks
+/// the first character where they differ:
+///
+/// ```text
+///     at src/validate.rs:42:9
+///        ╰─ expected: "2024-01-15"
+///           actual:   "2024-01-51"
+///                             ^
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct ValidationError;
+///
+/// let err = at(ValidationError).at_expected_actual(3usize, 5usize);
+/// let pair = err.contexts().find_map(|c| c.as_expected_actual()).unwrap();
+/// assert_eq!(pair.expected::<usize>(), Some(&3));
+/// assert_eq!(pair.actual::<usize>(), Some(&5));
+/// assert_eq!(pair.to_string(), "expected: 3, actual: 5");
+/// ```
+pub struct AtExpectedActual {
+    expected: Box<dyn AtDebugAny>,
+    actual: Box<dyn AtDebugAny>,
+}
+
+impl AtExpectedActual {
+    pub(crate) fn new<T, U>(expected: T, actual: U) -> Self
+    where
+        T: fmt::Debug + Send + Sync + 'static,
+        U: fmt::Debug + Send + Sync + 'static,
+    {
+        Self {
+            expected: Box::new(expected),
+            actual: Box::new(actual),
+        }
+    }
+
+    /// The expected value, if it is a `T`.
+    #[inline]
+    pub fn expected<T: 'static>(&self) -> Option<&T> {
+        (*self.expected).as_any().downcast_ref()
+    }
+
+    /// The actual value, if it is a `T`.
+    #[inline]
+    pub fn actual<T: 'static>(&self) -> Option<&T> {
+        (*self.actual).as_any().downcast_ref()
+    }
+
+    /// The expected value, for `Debug` formatting.
+    #[inline]
+    pub fn expected_debug(&self) -> &dyn fmt::Debug {
+        &*self.expected
+    }
+
+    /// The actual value, for `Debug` formatting.
+    #[inline]
+    pub fn actual_debug(&self) -> &dyn fmt::Debug {
+        &*self.actual
+    }
+
+    /// Write the aligned block. The caller writes the first line's prefix;
+    /// continuation lines start with `indent`.
+    pub(crate) fn write_block<W: fmt::Write>(&self, f: &mut W, indent: &str) -> fmt::Result {
+        let expected = alloc::format!("{:?}", self.expected);
+        let actual = alloc::format!("{:?}", self.actual);
+        write!(f, "expected: {}\n{}actual:   {}", expected, indent, actual)?;
+        if self.is_str_pair() {
+            let first_diff = expected
+                .chars()
+                .zip(actual.chars())
+                .position(|(e, a)| e != a)
+                .or_else(|| {
+                    let (e, a) = (expected.chars().count(), actual.chars().count());
+                    (e != a).then(|| e.min(a))
+                });
+            if let Some(col) = first_diff {
+                write!(f, "\n{}{:width$}^", indent, "", width = 10 + col)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_str_pair(&self) -> bool {
+        fn is_str(value: &dyn AtDebugAny) -> bool {
+            let any = value.as_any();
+            any.is::<String>() || any.is::<&'static str>() || any.is::<Cow<'static, str>>()
+        }
+        is_str(&*self.expected) && is_str(&*self.actual)
+    }
+}
+
+impl fmt::Debug for AtExpectedActual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtExpectedActual")
+            .field("expected", &self.expected)
+            .field("actual", &self.actual)
+            .finish()
+    }
+}
+
+impl fmt::Display for AtExpectedActual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected: {:?}, actual: {:?}",
+            self.expected, self.actual
+        )
+    }
+}
+
+// ============================================================================
+// AtBytes - a bounded copy of raw bytes
+// ============================================================================
+
+/// Raw bytes attached with [`at_bytes()`](crate::At::at_bytes).
+///
+/// Only the first [`AT_MAX_CONTEXT_BYTES`] bytes are copied; [`len()`](Self::len)
+/// keeps the original length. Trace formatters render a hex/ASCII dump, 16
+/// bytes per row, and the `serde` output carries the bytes as base64:
+///
+/// ```text
+///     at src/parser.rs:88:9
+///        ╰─ frame header (6 bytes):
+///           0000  47 45 54 20 2f 0a                                |GET /.|
+/// ```
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct ParseError;
+///
+/// let packet = [0xde, 0xad, 0xbe, 0xef];
+/// let err = at(ParseError).at_bytes("packet", &packet);
+/// let bytes = err.contexts().find_map(|c| c.as_bytes()).unwrap();
+/// assert_eq!(bytes.label(), "packet");
+/// assert_eq!(bytes.bytes(), &packet);
+/// assert!(!bytes.is_truncated());
+/// assert_eq!(bytes.to_string(), "packet (4 bytes): de ad be ef");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtBytes {
+    label: &'static str,
+    data: Vec<u8>,
+    len: usize,
+}
+
+impl AtBytes {
+    /// Copy at most [`AT_MAX_CONTEXT_BYTES`] of `bytes`.
+    pub(crate) fn copy_from(label: &'static str, bytes: &[u8]) -> Self {
+        let kept = &bytes[..bytes.len().min(AT_MAX_CONTEXT_BYTES)];
+        Self {
+            label,
+            data: kept.to_vec(),
+            len: bytes.len(),
+        }
+    }
+
+    /// Like [`copy_from`](Self::copy_from), but returns `None` on allocation failure.
+    pub(crate) fn try_copy_from(label: &'static str, bytes: &[u8]) -> Option<Self> {
+        let kept = &bytes[..bytes.len().min(AT_MAX_CONTEXT_BYTES)];
+        let mut data = Vec::new();
+        data.try_reserve_exact(kept.len()).ok()?;
+        data.extend_from_slice(kept);
+        Some(Self {
+            label,
+            data,
+            len: bytes.len(),
+        })
+    }
+
+    /// The label passed to `at_bytes`.
+    #[inline]
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// The copied bytes: the whole input, or its first [`AT_MAX_CONTEXT_BYTES`].
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The length of the original input, including any bytes not copied.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the input was longer than [`AT_MAX_CONTEXT_BYTES`].
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.data.len() < self.len
+    }
+
+    fn write_header<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
+        if self.is_truncated() {
+            write!(
+                f,
+                "{} (first {} of {} bytes)",
+                self.label,
+                self.data.len(),
+                self.len
+            )
+        } else {
+            write!(f, "{} ({} bytes)", self.label, self.len)
+        }
+    }
+
+    /// Write the hex dump. The caller writes the first line's prefix;
+    /// each row starts on a new line with `indent`.
+    pub(crate) fn write_dump<W: fmt::Write>(&self, f: &mut W, indent: &str) -> fmt::Result {
+        self.write_header(f)?;
+        f.write_char(':')?;
+        for (row, chunk) in self.data.chunks(16).enumerate() {
+            write!(f, "\n{}{:04x} ", indent, row * 16)?;
+            for i in 0..16 {
+                match chunk.get(i) {
+                    Some(byte) => write!(f, " {:02x}", byte)?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            f.write_str("  |")?;
+            for &byte in chunk {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                f.write_char(c)?;
+            }
+            f.write_char('|')?;
+        }
+        Ok(())
+    }
+
+    /// The copied bytes as standard base64 (with padding).
+    pub(crate) fn to_base64(&self) -> String {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut out = String::with_capacity(self.data.len().div_ceil(3) * 4);
+        for chunk in self.data.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63]));
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for AtBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_header(f)?;
+        f.write_char(':')?;
+        for byte in &self.data {
+            write!(f, " {:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
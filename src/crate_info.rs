@@ -5,12 +5,16 @@
 //!
 //! ## Link Format
 //!
-//! By default, links use GitHub's format: `{repo}/blob/{commit}/{path}{file}#L{line}`
+//! By default, links use GitHub's format: `{repo}/blob/{commit}/{path}{file}#L{line}C{column}`,
+//! which lands on the exact column within the line.
 //!
-//! For other forges, use `.link_format()`:
+//! For other forges, use `.link_format()`. Their anchors stop at the line:
 //! - **GitLab**: `{repo}/-/blob/{commit}/{path}{file}#L{line}`
 //! - **Gitea/Forgejo**: `{repo}/src/commit/{commit}/{path}{file}#L{line}`
 //! - **Bitbucket**: `{repo}/src/{commit}/{path}{file}#lines-{line}`
+//! - **SourceHut**: `{repo}/tree/{commit}/item/{path}{file}#L{line}`
+//!
+//! Any other layout can be given as a template with the same placeholders.
 
 use alloc::boxed::Box;
 use alloc::string::String;
@@ -48,9 +52,9 @@ use alloc::vec::Vec;
 ///     .meta(&[("team", "platform"), ("service", "auth")])
 ///     .build();
 /// ```
-/// Default link format for GitHub: `{repo}/blob/{commit}/{path}{file}#L{line}`
+/// Default link format for GitHub: `{repo}/blob/{commit}/{path}{file}#L{line}C{column}`
 #[doc(hidden)]
-pub const GITHUB_LINK_FORMAT: &str = "{repo}/blob/{commit}/{path}{file}#L{line}";
+pub const GITHUB_LINK_FORMAT: &str = "{repo}/blob/{commit}/{path}{file}#L{line}C{column}";
 
 /// Link format for GitLab: `{repo}/-/blob/{commit}/{path}{file}#L{line}`
 #[doc(hidden)]
@@ -64,6 +68,21 @@ pub const GITEA_LINK_FORMAT: &str = "{repo}/src/commit/{commit}/{path}{file}#L{l
 #[doc(hidden)]
 pub const BITBUCKET_LINK_FORMAT: &str = "{repo}/src/{commit}/{path}{file}#lines-{line}";
 
+/// Link format for SourceHut: `{repo}/tree/{commit}/item/{path}{file}#L{line}`
+#[doc(hidden)]
+pub const SOURCEHUT_LINK_FORMAT: &str = "{repo}/tree/{commit}/item/{path}{file}#L{line}";
+
+/// Fill the per-location placeholders (`{file}`, `{line}`, `{column}`) of a link
+/// template whose `{repo}`, `{commit}` and `{path}` are already substituted.
+pub(crate) fn fill_location_link(template: &str, loc: &core::panic::Location<'_>) -> String {
+    use alloc::string::ToString;
+    // Convert backslashes to forward slashes for Windows paths
+    template
+        .replace("{file}", &loc.file().replace('\\', "/"))
+        .replace("{line}", &loc.line().to_string())
+        .replace("{column}", &loc.column().to_string())
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct AtCrateInfo {
     name: &'static str,
@@ -72,7 +91,7 @@ pub struct AtCrateInfo {
     crate_path: Option<&'static str>,
     module: &'static str,
     meta: &'static [(&'static str, &'static str)],
-    /// Link format string with placeholders: `{repo}`, `{commit}`, `{path}`, `{file}`, `{line}`
+    /// Link format string with placeholders: `{repo}`, `{commit}`, `{path}`, `{file}`, `{line}`, `{column}`
     link_format: &'static str,
 }
 
@@ -127,7 +146,7 @@ impl AtCrateInfo {
 
     /// Link format string for generating repository links.
     ///
-    /// Contains placeholders: `{repo}`, `{commit}`, `{path}`, `{file}`, `{line}`
+    /// Contains placeholders: `{repo}`, `{commit}`, `{path}`, `{file}`, `{line}`, `{column}`
     ///
     /// Default is [`GITHUB_LINK_FORMAT`].
     pub const fn link_format(&self) -> &'static str {
@@ -280,6 +299,7 @@ impl AtCrateInfoBuilder {
     /// - `{path}` - Crate path within repo (e.g., `crates/mylib/`)
     /// - `{file}` - Source file path (e.g., `src/lib.rs`)
     /// - `{line}` - Line number
+    /// - `{column}` - Column number (1-based)
     ///
     /// ## Predefined formats
     ///
@@ -287,6 +307,7 @@ impl AtCrateInfoBuilder {
     /// - [`GITLAB_LINK_FORMAT`]
     /// - [`GITEA_LINK_FORMAT`]
     /// - [`BITBUCKET_LINK_FORMAT`]
+    /// - [`SOURCEHUT_LINK_FORMAT`]
     ///
     /// ## Example
     ///
@@ -421,6 +442,7 @@ impl AtCrateInfoBuilder {
     /// - URLs containing `gitlab.com` or `gitlab.` → [`GITLAB_LINK_FORMAT`]
     /// - URLs containing `gitea.` or `forgejo.` or `codeberg.org` → [`GITEA_LINK_FORMAT`]
     /// - URLs containing `bitbucket.org` → [`BITBUCKET_LINK_FORMAT`]
+    /// - URLs containing `sr.ht` → [`SOURCEHUT_LINK_FORMAT`]
     /// - Unknown hosts → [`GITHUB_LINK_FORMAT`] (default)
     ///
     /// ## Example
@@ -460,6 +482,8 @@ fn detect_link_format(repo_url: &str) -> &'static str {
         GITEA_LINK_FORMAT
     } else if url_lower.contains("bitbucket.org") || url_lower.contains("bitbucket.") {
         BITBUCKET_LINK_FORMAT
+    } else if url_lower.contains("sr.ht") {
+        SOURCEHUT_LINK_FORMAT
     } else {
         // Default to GitHub format for unknown hosts
         GITHUB_LINK_FORMAT
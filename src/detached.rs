@@ -0,0 +1,274 @@
+//! Owned traces that don't borrow `&'static Location` data.
+//!
+//! [`AtTrace`](crate::AtTrace) stores pointers to compiler-generated locations,
+//! which is what keeps it cheap. Frames that only exist at runtime (symbolized
+//! backtraces, traces received from another process) are stored here instead,
+//! as owned strings.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+// ============================================================================
+// AtFrameDetached - one owned frame
+// ============================================================================
+
+/// A single frame with owned location data.
+///
+/// A frame may have a location, a function name, both, or neither. A frame with
+/// neither is a skipped-frames marker (`[...]`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AtFrameDetached {
+    file: Option<String>,
+    line: u32,
+    column: u32,
+    function: Option<String>,
+    contexts: Vec<String>,
+}
+
+impl AtFrameDetached {
+    /// Create a frame at `file:line:column`.
+    pub fn new(file: impl Into<String>, line: u32, column: u32) -> Self {
+        Self {
+            file: Some(file.into()),
+            line,
+            column,
+            ..Self::default()
+        }
+    }
+
+    /// Create a skipped-frames marker.
+    pub fn skipped() -> Self {
+        Self::default()
+    }
+
+    /// Set the function name.
+    pub fn with_function(mut self, function: impl Into<String>) -> Self {
+        self.function = Some(function.into());
+        self
+    }
+
+    /// Add a context line.
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.contexts.push(context.into());
+        self
+    }
+
+    /// The source file, if known.
+    #[inline]
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line number (0 if unknown).
+    #[inline]
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The column number (0 if unknown).
+    #[inline]
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// The function name, if known.
+    #[inline]
+    pub fn function(&self) -> Option<&str> {
+        self.function.as_deref()
+    }
+
+    /// Context lines attached to this frame.
+    pub fn contexts(&self) -> impl Iterator<Item = &str> {
+        self.contexts.iter().map(String::as_str)
+    }
+
+    /// Check if this is a skipped-frames marker.
+    #[inline]
+    pub fn is_skipped(&self) -> bool {
+        self.file.is_none() && self.function.is_none()
+    }
+}
+
+// ============================================================================
+// AtTraceDetached - owned trace
+// ============================================================================
+
+/// A trace whose frames own their data.
+///
+/// Displays in the same layout as `At<E>`'s `Debug` trace. Build one with
+/// [`from_frames()`](Self::from_frames), or under `std` from a
+/// [`Backtrace`](std::backtrace::Backtrace) via [`AtTrace::from_backtrace()`](crate::AtTrace::from_backtrace).
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{AtFrameDetached, AtTraceDetached};
+///
+/// let trace = AtTraceDetached::from_frames([
+///     AtFrameDetached::new("src/db.rs", 142, 9).with_context("user_id = 42"),
+///     AtFrameDetached::new("src/api.rs", 89, 5).with_function("handle_request"),
+/// ]);
+///
+/// let text = trace.to_string();
+/// assert!(text.contains("    at src/db.rs:142:9\n       ╰─ user_id = 42"));
+/// assert!(text.contains("    at src/api.rs:89:5\n       ╰─ in handle_request"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AtTraceDetached {
+    frames: Vec<AtFrameDetached>,
+}
+
+impl AtTraceDetached {
+    /// Create an empty trace.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a trace from frames, oldest first.
+    pub fn from_frames(frames: impl IntoIterator<Item = AtFrameDetached>) -> Self {
+        Self {
+            frames: frames.into_iter().collect(),
+        }
+    }
+
+    /// Append a frame.
+    #[inline]
+    pub fn push(&mut self, frame: AtFrameDetached) {
+        self.frames.push(frame);
+    }
+
+    /// Iterate over frames, oldest first.
+    pub fn frames(&self) -> impl Iterator<Item = &AtFrameDetached> {
+        self.frames.iter()
+    }
+
+    /// Get the number of frames.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if the trace has no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl AtTraceDetached {
+    /// Write each line of the trace as `{start}{line}{end}`, without the
+    /// four-space frame indent.
+    fn write_lines<W: fmt::Write>(&self, f: &mut W, start: &str, end: &str) -> fmt::Result {
+        for frame in &self.frames {
+            match (frame.file(), frame.function()) {
+                (None, None) => {
+                    write!(f, "{}[...]{}", start, end)?;
+                    continue;
+                }
+                (Some(file), _) => write!(
+                    f,
+                    "{}at {}:{}:{}{}",
+                    start, file, frame.line, frame.column, end
+                )?,
+                (None, Some(_)) => write!(f, "{}at <unknown>{}", start, end)?,
+            }
+            if let Some(function) = frame.function() {
+                write!(f, "{}   ╰─ in {}{}", start, function, end)?;
+            }
+            for ctx in frame.contexts() {
+                write!(f, "{}   ╰─ {}{}", start, ctx, end)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write `remote trace:` followed by the frames, each line starting with
+    /// `indent` (for rendering as a context of another trace).
+    pub(crate) fn write_block<W: fmt::Write>(&self, f: &mut W, indent: &str) -> fmt::Result {
+        f.write_str("remote trace:")?;
+        self.write_lines(f, &alloc::format!("\n{}", indent), "")
+    }
+}
+
+impl fmt::Display for AtTraceDetached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_lines(f, "    ", "\n")
+    }
+}
+
+// ============================================================================
+// std::backtrace conversion
+// ============================================================================
+
+/// Parse the `Display` output of a captured `std::backtrace::Backtrace`.
+///
+/// The std API exposes frames only as text on stable, currently in this shape:
+///
+/// ```text
+///    0: my_crate::load
+///              at ./src/lib.rs:10:5
+///    1: <unknown>
+/// ```
+///
+/// The layout is not a stable interface, so this is best-effort: lines in
+/// any other shape are ignored.
+#[cfg(feature = "std")]
+pub(crate) fn from_backtrace(backtrace: &std::backtrace::Backtrace) -> AtTraceDetached {
+    use alloc::string::ToString;
+    use std::backtrace::BacktraceStatus;
+
+    let mut trace = AtTraceDetached::new();
+    if backtrace.status() != BacktraceStatus::Captured {
+        return trace;
+    }
+
+    let text = backtrace.to_string();
+    // Backtraces list the most recent call first; traces are oldest first
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some(frame) = trace.frames.last_mut() {
+                if frame.file.is_none() {
+                    let (file, line, column) = parse_location(location);
+                    frame.file = Some(file.to_string());
+                    frame.line = line;
+                    frame.column = column;
+                }
+            }
+        } else if let Some((index, function)) = line.split_once(": ") {
+            if index.bytes().all(|b| b.is_ascii_digit()) {
+                let mut frame = AtFrameDetached::default();
+                if function != "<unknown>" {
+                    frame.function = Some(function.to_string());
+                }
+                trace.frames.push(frame);
+            }
+        }
+    }
+    // Unresolved frames carry nothing displayable; don't turn them into [...] markers
+    trace.frames.retain(|frame| !frame.is_skipped());
+    trace.frames.reverse();
+    trace
+}
+
+/// Split `path:line:column` from the right so Windows drive letters survive.
+#[cfg(feature = "std")]
+fn parse_location(location: &str) -> (&str, u32, u32) {
+    let mut parts = location.rsplitn(3, ':');
+    let last = parts.next().unwrap_or("");
+    let middle = parts.next();
+    let rest = parts.next();
+    match (rest, middle.and_then(|m| m.parse().ok()), last.parse().ok()) {
+        (Some(file), Some(line), Some(column)) => (file, line, column),
+        _ => match (middle, last.parse().ok()) {
+            // `path:line` without a column
+            (Some(_), Some(line)) => {
+                let file = location.rsplit_once(':').map_or(location, |(file, _)| file);
+                (file, line, 0)
+            }
+            _ => (location, 0, 0),
+        },
+    }
+}
@@ -0,0 +1,162 @@
+//! Object-safe access to traced errors of any type.
+//!
+//! [`AtTraceable`] has `impl Trait` returns and consuming methods, so it can't
+//! be used as `dyn AtTraceable`. [`AtErrorDyn`] is the read-only subset that
+//! can: a plugin host can keep `Box<dyn AtErrorDyn + Send + Sync>` values from
+//! many error types and render them all the same way.
+
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::trace::write_traceable_body;
+use crate::{At, AtCrateInfo, AtFrame, AtTrace, AtTraceFormat, AtTraceable};
+
+/// A traced error behind a trait object.
+///
+/// Implemented for every `At<E: Display>` and every [`AtTraceable`] type.
+/// Import it only where you handle trait objects: for concrete types its
+/// method names overlap with `AtTraceable`'s.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, AtErrorDyn, AtTrace, AtTraceable};
+///
+/// #[derive(Debug)]
+/// struct NotFound;
+/// impl std::fmt::Display for NotFound {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "not found")
+///     }
+/// }
+///
+/// struct PluginError { trace: AtTrace }
+/// impl AtTraceable for PluginError {
+///     fn trace_mut(&mut self) -> &mut AtTrace { &mut self.trace }
+///     fn trace(&self) -> Option<&AtTrace> { Some(&self.trace) }
+///     fn fmt_message(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "plugin crashed")
+///     }
+/// }
+///
+/// let errors: Vec<Box<dyn AtErrorDyn>> = vec![
+///     Box::new(at(NotFound).at_str("loading")),
+///     Box::new(PluginError { trace: AtTrace::new() }.at()),
+/// ];
+///
+/// let traces: Vec<String> = errors.iter().map(|e| e.full_trace().to_string()).collect();
+/// assert!(traces[0].starts_with("not found\n"));
+/// assert!(traces[0].contains("loading"));
+/// assert!(traces[1].starts_with("plugin crashed\n"));
+/// assert_eq!(errors[1].frames().count(), 1);
+/// assert_eq!(errors[0].message().to_string(), "not found");
+/// ```
+pub trait AtErrorDyn {
+    /// Format just the error message (without trace).
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Get the trace, if one has been allocated.
+    fn trace(&self) -> Option<&AtTrace>;
+
+    /// Remediation guidance, printed by `full_trace()` as a `help:` line.
+    fn help(&self) -> Option<&str> {
+        None
+    }
+
+    /// Diagnostic code, printed by `full_trace()` as a `code:` line.
+    fn error_code(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Iterate over frames, oldest first.
+    fn frames(&self) -> Box<dyn Iterator<Item = AtFrame<'_>> + '_> {
+        Box::new(self.trace().into_iter().flat_map(|t| t.frames()))
+    }
+
+    /// Get the crate info of the trace, if set.
+    fn crate_info(&self) -> Option<&'static AtCrateInfo> {
+        self.trace().and_then(|t| t.crate_info())
+    }
+}
+
+impl<E: fmt::Display> AtErrorDyn for At<E> {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.error(), f)
+    }
+
+    fn trace(&self) -> Option<&AtTrace> {
+        self.trace_ref()
+    }
+}
+
+impl<T: AtTraceable> AtErrorDyn for T {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        AtTraceable::fmt_message(self, f)
+    }
+
+    fn trace(&self) -> Option<&AtTrace> {
+        AtTraceable::trace(self)
+    }
+
+    fn help(&self) -> Option<&str> {
+        AtTraceable::help(self)
+    }
+
+    fn error_code(&self) -> Option<&'static str> {
+        AtTraceable::error_code(self)
+    }
+}
+
+macro_rules! dyn_formatters {
+    ($($bounds:tt)*) => {
+        impl<'a> dyn AtErrorDyn $($bounds)* + 'a {
+            /// Format just the error message.
+            pub fn message(&self) -> impl fmt::Display + '_ {
+                DynMessage(self)
+            }
+
+            /// Format the error message and the full trace with all contexts.
+            ///
+            /// Same layout as [`AtTraceable::full_trace()`].
+            pub fn full_trace(&self) -> impl fmt::Display + '_ {
+                self.full_trace_with(AtTraceFormat::DEFAULT)
+            }
+
+            /// Like [`full_trace()`](Self::full_trace), with [`AtTraceFormat`] options applied.
+            pub fn full_trace_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+                DynFullTrace { error: self, format }
+            }
+        }
+    };
+}
+
+dyn_formatters!();
+dyn_formatters!(+ Send);
+dyn_formatters!(+ Send + Sync);
+
+struct DynMessage<'a>(&'a dyn AtErrorDyn);
+
+impl fmt::Display for DynMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_message(f)
+    }
+}
+
+struct DynFullTrace<'a> {
+    error: &'a dyn AtErrorDyn,
+    format: AtTraceFormat,
+}
+
+impl fmt::Display for DynFullTrace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt_message(f)?;
+        write_traceable_body(
+            f,
+            self.error.trace(),
+            &self.format,
+            self.error.error_code(),
+            self.error.help(),
+            true,
+        )
+    }
+}
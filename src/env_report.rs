@@ -0,0 +1,164 @@
+//! Environment snapshot for bug reports.
+//!
+//! [`env_report()`] collects the boilerplate every CLI bug report needs: the
+//! binary name and version, the compiler it was built with, the OS, and a
+//! chosen set of environment variables. Attach it as a context or print it
+//! in a report section. Requires the `std` feature.
+
+use core::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::AtBuildInfo;
+
+/// A snapshot of the running binary and its environment.
+///
+/// Created by [`env_report()`]. `{}` formats it on one line (suitable as a
+/// context); `{:#}` formats a multi-line section for report output.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, at_build_info, At};
+///
+/// #[derive(Debug)]
+/// struct CliError;
+///
+/// let report = whereat::env_report()
+///     .build(at_build_info!())
+///     .var("RUST_LOG")
+///     .var("NO_COLOR");
+/// assert!(report.rustc_version().starts_with("rustc "));
+///
+/// let err = at(CliError).at_data(|| report.clone());
+/// assert!(format!("{:?}", err).contains("rustc "));
+///
+/// // Multi-line section, one field per line
+/// let section = format!("{:#}", report);
+/// assert!(section.contains("\nrustc:   rustc "));
+/// assert!(section.contains("\nenv:     RUST_LOG"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtEnvReport {
+    binary: String,
+    build: Option<AtBuildInfo>,
+    vars: Vec<(&'static str, Option<String>)>,
+}
+
+/// Snapshot the running binary's environment.
+///
+/// Records the executable name, the OS and architecture, and the `rustc`
+/// version whereat was compiled with (the same compiler as the binary).
+/// Add the binary's version and profile with [`build()`](AtEnvReport::build)
+/// and environment variables with [`var()`](AtEnvReport::var).
+pub fn env_report() -> AtEnvReport {
+    let binary = std::env::current_exe()
+        .ok()
+        .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+        .or_else(|| std::env::args().next())
+        .unwrap_or_default();
+    AtEnvReport {
+        binary,
+        build: None,
+        vars: Vec::new(),
+    }
+}
+
+impl AtEnvReport {
+    /// Include the binary's build configuration, from [`at_build_info!`](crate::at_build_info).
+    pub fn build(mut self, info: AtBuildInfo) -> Self {
+        self.build = Some(info);
+        self
+    }
+
+    /// Include an environment variable's current value (or that it is unset).
+    pub fn var(mut self, name: &'static str) -> Self {
+        let value = std::env::var_os(name).map(|v| v.to_string_lossy().into_owned());
+        self.vars.push((name, value));
+        self
+    }
+
+    /// The executable's file name.
+    pub fn binary(&self) -> &str {
+        &self.binary
+    }
+
+    /// The build configuration, if added with [`build()`](Self::build).
+    pub fn build_info(&self) -> Option<&AtBuildInfo> {
+        self.build.as_ref()
+    }
+
+    /// The compiler version, e.g. `rustc 1.85.0 (4d91de4e4 2025-02-17)`.
+    ///
+    /// Empty if it couldn't be determined at build time. The build script
+    /// only runs `rustc --version` when the `std` feature is on.
+    pub fn rustc_version(&self) -> &'static str {
+        option_env!("WHEREAT_RUSTC_VERSION").unwrap_or("")
+    }
+
+    /// The operating system, as in [`std::env::consts::OS`].
+    pub fn os(&self) -> &'static str {
+        std::env::consts::OS
+    }
+
+    /// The CPU architecture, as in [`std::env::consts::ARCH`].
+    pub fn arch(&self) -> &'static str {
+        std::env::consts::ARCH
+    }
+
+    /// The recorded environment variables, in the order added. `None` if unset.
+    pub fn vars(&self) -> impl Iterator<Item = (&'static str, Option<&str>)> {
+        self.vars
+            .iter()
+            .map(|(name, value)| (*name, value.as_deref()))
+    }
+
+    fn write_vars(&self, f: &mut fmt::Formatter<'_>, sep: &str) -> fmt::Result {
+        for (i, (name, value)) in self.vars().enumerate() {
+            if i > 0 {
+                f.write_str(sep)?;
+            }
+            match value {
+                Some(value) => write!(f, "{}={}", name, value)?,
+                None => write!(f, "{} unset", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AtEnvReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            writeln!(f, "binary:  {}", self.binary)?;
+            if let Some(build) = &self.build {
+                writeln!(f, "build:   {}", build)?;
+            }
+            writeln!(f, "rustc:   {}", self.rustc_version())?;
+            writeln!(f, "os:      {} {}", self.os(), self.arch())?;
+            if !self.vars.is_empty() {
+                write!(f, "env:     ")?;
+                self.write_vars(f, "\n         ")?;
+                writeln!(f)?;
+            }
+            return Ok(());
+        }
+
+        match &self.build {
+            Some(build) => write!(f, "{}", build)?,
+            None => write!(f, "{}", self.binary)?,
+        }
+        write!(
+            f,
+            "; {}; {} {}",
+            self.rustc_version(),
+            self.os(),
+            self.arch()
+        )?;
+        if !self.vars.is_empty() {
+            write!(f, "; ")?;
+            self.write_vars(f, ", ")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Per-error identifiers for correlating user-facing reports with full traces.
+//!
+//! Enabled by the `error-id` feature. Each trace gets an [`AtErrorId`] when its
+//! first location is captured. Show the id to users ("error id: 0F3K7ZQ2M9XVT")
+//! and log the full trace server-side; the id joins the two.
+
+use core::fmt;
+use core::num::NonZeroU64;
+
+use crate::config::{AtomicUsize, Ordering};
+
+/// A short, process-unique identifier assigned to an error at first capture.
+///
+/// Displayed as 13 characters of Crockford base32 (no `I`, `L`, `O`, `U`), which
+/// is easy to read aloud and copy from a screenshot.
+///
+/// Ids are unique within a process. With the `std` feature, the generator is
+/// seeded from the system clock and process id so ids also differ across runs;
+/// without `std` they are still unique per process but not random across runs.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// let err = at(MyError);
+/// let id = err.error_id().unwrap();
+/// assert_eq!(id.to_string().len(), 13);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AtErrorId(NonZeroU64);
+
+/// Crockford base32 alphabet.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Number of base32 characters needed for 64 bits.
+const ENCODED_LEN: usize = 13;
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl AtErrorId {
+    /// Generate a new id.
+    ///
+    /// Called automatically when a trace captures its first location.
+    pub fn generate() -> Self {
+        let n = next_count() as u64;
+        // splitmix64 over a seeded Weyl sequence: a bijection of the counter,
+        // so ids never repeat within a process, but look random.
+        let mut z = seed().wrapping_add(n.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Self(NonZeroU64::new(z).unwrap_or(NonZeroU64::MIN))
+    }
+
+    /// Create an id from a raw value (e.g. one stored in a log database).
+    ///
+    /// Returns `None` for zero.
+    #[inline]
+    pub const fn from_u64(value: u64) -> Option<Self> {
+        match NonZeroU64::new(value) {
+            Some(v) => Some(Self(v)),
+            None => None,
+        }
+    }
+
+    /// The raw 64-bit value.
+    #[inline]
+    pub const fn as_u64(self) -> u64 {
+        self.0.get()
+    }
+}
+
+/// Advance the id counter, returning its previous value.
+#[cfg(target_has_atomic = "ptr")]
+#[inline]
+fn next_count() -> usize {
+    COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Advance the id counter, returning its previous value.
+///
+/// Without native read-modify-write atomics (e.g. `thumbv6m`) this is a load
+/// and a store: an error created in an interrupt handler between the two may
+/// get the same id.
+#[cfg(not(target_has_atomic = "ptr"))]
+#[inline]
+fn next_count() -> usize {
+    let n = COUNTER.load(Ordering::Relaxed);
+    COUNTER.store(n.wrapping_add(1), Ordering::Relaxed);
+    n
+}
+
+#[cfg(feature = "std")]
+fn seed() -> u64 {
+    use std::sync::OnceLock;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static SEED: OnceLock<u64> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        nanos ^ (u64::from(std::process::id()) << 32)
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn seed() -> u64 {
+    // Address of a static varies between runs under ASLR; constant otherwise.
+    &COUNTER as *const AtomicUsize as usize as u64
+}
+
+impl fmt::Display for AtErrorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.0.get();
+        let mut buf = [0u8; ENCODED_LEN];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            let shift = 5 * (ENCODED_LEN - 1 - i);
+            *byte = ALPHABET[((value >> shift) & 0x1F) as usize];
+        }
+        // ALPHABET is ASCII, so this never fails
+        f.write_str(core::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for AtErrorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AtErrorId({})", self)
+    }
+}
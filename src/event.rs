@@ -0,0 +1,159 @@
+//! Emitting traced errors as `tracing` events (the `tracing` feature).
+//!
+//! [`At::record_as_event()`] logs an error as one event with the trace split
+//! into separate fields, so a structured subscriber (JSON, OpenTelemetry)
+//! can index the origin, the path and the contexts instead of one
+//! preformatted string. Field values are only formatted if the event is
+//! enabled.
+
+use core::fmt::{self, Write as _};
+
+use tracing::Level;
+
+use crate::At;
+use crate::context::AtContext;
+use crate::localize::variant_name;
+use crate::trace::SkipMarker;
+
+impl<E: fmt::Display + fmt::Debug> At<E> {
+    /// Emit this error as a `tracing` event at `level`, with target `"whereat"`.
+    ///
+    /// The message is the error's `Display` output. Fields:
+    ///
+    /// | Field | Value |
+    /// |-------|-------|
+    /// | `error.kind` | Variant (or type) name from `Debug`, e.g. `NotFound` |
+    /// | `error.fingerprint` | [`fingerprint()`](At::fingerprint) as 16 hex digits |
+    /// | `error.location` | Origin frame as `file:line:col` |
+    /// | `error.frames` | All frames, oldest first, `[...]` for skipped frames |
+    /// | `error.contexts` | Context messages in attachment order, separated by `; ` |
+    /// | `error.kvs` | [`kvs()`](At::kvs) as `key=value`, newest first, space-separated |
+    ///
+    /// Empty fields are written as empty strings.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// enum DbError { NotFound }
+    ///
+    /// impl core::fmt::Display for DbError {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str("user not found")
+    ///     }
+    /// }
+    ///
+    /// let err: At<DbError> = at(DbError::NotFound).at_str("loading profile").at_kv("user_id", 7);
+    /// err.record_as_event(tracing::Level::WARN);
+    /// ```
+    pub fn record_as_event(&self, level: Level) {
+        macro_rules! emit {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "whereat",
+                    $level,
+                    error.kind = %Kind(self),
+                    error.fingerprint = %format_args!("{:016x}", self.fingerprint()),
+                    error.location = %Origin(self),
+                    error.frames = %Frames(self),
+                    error.contexts = %Contexts(self),
+                    error.kvs = %Kvs(self),
+                    "{}",
+                    self.error()
+                )
+            };
+        }
+        match level {
+            Level::ERROR => emit!(Level::ERROR),
+            Level::WARN => emit!(Level::WARN),
+            Level::INFO => emit!(Level::INFO),
+            Level::DEBUG => emit!(Level::DEBUG),
+            Level::TRACE => emit!(Level::TRACE),
+        }
+    }
+}
+
+struct Kind<'a, E>(&'a At<E>);
+
+impl<E: fmt::Debug> fmt::Display for Kind<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(variant_name(&alloc::format!("{:?}", self.0.error())))
+    }
+}
+
+struct Origin<'a, E>(&'a At<E>);
+
+impl<E> fmt::Display for Origin<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.frames().find_map(|frame| frame.location()) {
+            Some(loc) => write!(f, "{}:{}:{}", loc.file(), loc.line(), loc.column()),
+            None => Ok(()),
+        }
+    }
+}
+
+struct Frames<'a, E>(&'a At<E>);
+
+impl<E> fmt::Display for Frames<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, frame) in self.0.frames().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match frame.location() {
+                Some(loc) => write!(f, "{}:{}:{}", loc.file(), loc.line(), loc.column())?,
+                None => write!(f, "{}", SkipMarker(frame.repeat_count()))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Contexts<'a, E>(&'a At<E>);
+
+impl<E> fmt::Display for Contexts<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Crate boundaries, span ids and key-value pairs have their own
+        // fields, repeat counts are shown in `error.frames`; backtraces
+        // and remote traces are too long for a one-line field
+        let contexts = self
+            .0
+            .trace_ref()
+            .into_iter()
+            .flat_map(|trace| trace.context_entries())
+            .filter(|(_, c)| {
+                !matches!(
+                    c.inner,
+                    AtContext::Crate(_)
+                        | AtContext::SpanId(_)
+                        | AtContext::KeyValue { .. }
+                        | AtContext::Backtrace(_)
+                        | AtContext::Remote(_)
+                        | AtContext::Repeated(_)
+                )
+            });
+        for (i, (_, ctx)) in contexts.enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", ctx)?;
+        }
+        Ok(())
+    }
+}
+
+struct Kvs<'a, E>(&'a At<E>);
+
+impl<E> fmt::Display for Kvs<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.kvs().enumerate() {
+            if i > 0 {
+                f.write_char(' ')?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Long-form explanations for diagnostic codes, like `rustc --explain`.
+//!
+//! Applications register `&'static` tables of `(code, explanation)` pairs at
+//! startup, usually one per crate, and look codes up with [`explain()`].
+//! Requires the `std` feature.
+
+use std::sync::RwLock;
+use std::vec::Vec;
+
+/// A table of `(code, explanation)` pairs.
+pub type AtExplanations = &'static [(&'static str, &'static str)];
+
+static REGISTRY: RwLock<Vec<AtExplanations>> = RwLock::new(Vec::new());
+
+/// Register a table of code explanations.
+///
+/// Tables registered later take precedence when codes collide, so an
+/// application can override a library's text. Registering the same table
+/// twice is a no-op.
+///
+/// ## Example
+///
+/// ```rust
+/// static EXPLANATIONS: whereat::AtExplanations = &[
+///     ("E1003", "The config file was found but could not be parsed.\n\nRun `app check-config`."),
+/// ];
+///
+/// whereat::register_explanations(EXPLANATIONS);
+/// assert!(whereat::explain("E1003").unwrap().starts_with("The config file"));
+/// assert_eq!(whereat::explain("E9999"), None);
+/// ```
+pub fn register_explanations(table: AtExplanations) {
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    if !registry.iter().any(|t| core::ptr::eq(*t, table)) {
+        registry.push(table);
+    }
+}
+
+/// Look up the long-form explanation for a diagnostic code.
+///
+/// Returns `None` if no registered table contains `code`. Suitable for
+/// implementing a CLI `--explain <CODE>` flag.
+pub fn explain(code: &str) -> Option<&'static str> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry.iter().rev().find_map(|table| {
+        table
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, text)| *text)
+    })
+}
@@ -4,9 +4,11 @@
 //! methods directly on `Result` types, avoiding verbose `map_err` boilerplate.
 //!
 //! - [`ErrorAtExt`]: Call `.start_at()` on `Error` types to wrap in `At<E>`
+//! - [`OptionAtExt`]: Call `.ok_or_at(err)` on `Option<T>` to start a trace on `None`
 //! - [`ResultAtExt`]: Call `.at()` on `Result<T, At<E>>` to extend the trace
 //! - [`ResultAtTraceableExt`]: Call `.at()` on `Result<T, E>` where E: AtTraceable
 
+use alloc::borrow::Cow;
 use alloc::string::String;
 use core::fmt;
 
@@ -71,6 +73,78 @@ impl<E: core::error::Error> ErrorAtExt for E {
     }
 }
 
+// ============================================================================
+// OptionAtExt Trait - for turning None into a traced error
+// ============================================================================
+
+/// Extension trait for converting `Option<T>` into `Result<T, At<E>>`.
+///
+/// Like [`Option::ok_or()`] followed by [`at()`](crate::at()), in one step,
+/// with the trace starting at the caller's location.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{At, OptionAtExt};
+///
+/// #[derive(Debug)]
+/// enum ConfigError { MissingKey(&'static str) }
+///
+/// fn port(vars: &[(&str, u16)]) -> Result<u16, At<ConfigError>> {
+///     let (_, port) = vars
+///         .iter()
+///         .find(|(k, _)| *k == "PORT")
+///         .ok_or_at_str(ConfigError::MissingKey("PORT"), "reading listener config")?;
+///     Ok(*port)
+/// }
+///
+/// let err = port(&[]).unwrap_err();
+/// assert_eq!(err.frame_count(), 1);
+/// assert_eq!(err.contexts().next().unwrap().as_text(), Some("reading listener config"));
+/// ```
+pub trait OptionAtExt<T> {
+    /// Return the value, or `err` traced at the caller's location.
+    #[track_caller]
+    fn ok_or_at<E>(self, err: E) -> Result<T, At<E>>;
+
+    /// Like [`ok_or_at()`](Self::ok_or_at), building the error only on `None`.
+    #[track_caller]
+    fn ok_or_at_with<E>(self, f: impl FnOnce() -> E) -> Result<T, At<E>>;
+
+    /// Like [`ok_or_at()`](Self::ok_or_at), with a static string context on the first frame.
+    #[track_caller]
+    fn ok_or_at_str<E>(self, err: E, msg: &'static str) -> Result<T, At<E>>;
+}
+
+impl<T> OptionAtExt<T> for Option<T> {
+    #[track_caller]
+    #[inline]
+    fn ok_or_at<E>(self, err: E) -> Result<T, At<E>> {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(At::wrap(err).at()),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn ok_or_at_with<E>(self, f: impl FnOnce() -> E) -> Result<T, At<E>> {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(At::wrap(f()).at()),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn ok_or_at_str<E>(self, err: E, msg: &'static str) -> Result<T, At<E>> {
+        match self {
+            Some(v) => Ok(v),
+            None => Err(At::wrap(err).at().at_str(msg)),
+        }
+    }
+}
+
 // ============================================================================
 // ResultAtExt Trait - for calling .at() on Results with At<E> errors
 // ============================================================================
@@ -99,10 +173,34 @@ pub trait ResultAtExt<T, E> {
     #[track_caller]
     fn at(self) -> Result<T, At<E>>;
 
+    /// Add the caller's location to the error trace if this is `Err` and `condition` is true.
+    #[track_caller]
+    fn at_if(self, condition: bool) -> Result<T, At<E>>;
+
     /// Add static string context to last location (or create one if empty).
     #[track_caller]
     fn at_str(self, msg: &'static str) -> Result<T, At<E>>;
 
+    /// Add static string context to last location if `condition` is true.
+    #[track_caller]
+    fn at_str_if(self, condition: bool, msg: &'static str) -> Result<T, At<E>>;
+
+    /// Add the caller's location to the error trace if this is `Err`, in debug builds only.
+    #[track_caller]
+    fn debug_at(self) -> Result<T, At<E>>;
+
+    /// Add static string context to last location if this is `Err`, in debug builds only.
+    #[track_caller]
+    fn debug_at_str(self, msg: &'static str) -> Result<T, At<E>>;
+
+    /// Add lazily-computed string context to last location if this is `Err`, in debug builds only.
+    #[track_caller]
+    fn debug_at_string(self, f: impl FnOnce() -> String) -> Result<T, At<E>>;
+
+    /// Add static string context to the oldest (origin) frame (or create one if empty).
+    #[track_caller]
+    fn at_origin_str(self, msg: &'static str) -> Result<T, At<E>>;
+
     /// Add lazily-computed string context to last location (or create one if empty).
     #[track_caller]
     fn at_string(self, f: impl FnOnce() -> String) -> Result<T, At<E>>;
@@ -121,6 +219,24 @@ pub trait ResultAtExt<T, E> {
         f: impl FnOnce() -> C,
     ) -> Result<T, At<E>>;
 
+    /// Add lazily-computed sensitive context, printed as `[redacted]` (see [`At::at_secret()`]).
+    #[track_caller]
+    fn at_secret<C: fmt::Debug + Send + Sync + 'static>(
+        self,
+        f: impl FnOnce() -> C,
+    ) -> Result<T, At<E>>;
+
+    /// Attach an expected and an actual value to the last location (or create one if empty).
+    #[track_caller]
+    fn at_expected_actual<X, Y>(self, expected: X, actual: Y) -> Result<T, At<E>>
+    where
+        X: fmt::Debug + Send + Sync + 'static,
+        Y: fmt::Debug + Send + Sync + 'static;
+
+    /// Attach a bounded copy of raw bytes to the last location (or create one if empty).
+    #[track_caller]
+    fn at_bytes(self, label: &'static str, bytes: &[u8]) -> Result<T, At<E>>;
+
     /// Add an error as context to the last location (or create one if empty).
     #[track_caller]
     fn at_error<Err: core::error::Error + Send + Sync + 'static>(
@@ -132,6 +248,43 @@ pub trait ResultAtExt<T, E> {
     #[track_caller]
     fn at_crate(self, info: &'static AtCrateInfo) -> Result<T, At<E>>;
 
+    /// Attach a correlation/request id to last location (or create one if empty).
+    #[track_caller]
+    fn at_correlation(self, id: impl Into<Cow<'static, str>>) -> Result<T, At<E>>;
+
+    /// Attach a `key=value` pair to the last location (or create one if empty).
+    #[track_caller]
+    fn at_kv(self, key: &'static str, value: impl fmt::Display) -> Result<T, At<E>>;
+
+    /// Add a note-level breadcrumb to last location (or create one if empty).
+    #[track_caller]
+    fn at_note(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>>;
+
+    /// Add a warn-level context to last location (or create one if empty).
+    #[track_caller]
+    fn at_warn(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>>;
+
+    /// Attach a "see also" link to last location (or create one if empty).
+    #[track_caller]
+    fn at_link(
+        self,
+        label: impl Into<Cow<'static, str>>,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Result<T, At<E>>;
+
+    /// Attach a remediation hint, shown in a `help:` section (or create a location if empty).
+    #[track_caller]
+    fn at_help(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>>;
+
+    /// Capture a full backtrace once per error, shown after the frames (or create a location if empty).
+    #[cfg(feature = "std-backtrace")]
+    #[track_caller]
+    fn at_backtrace(self) -> Result<T, At<E>>;
+
+    /// Attach a message shown directly under the error header (or create a location if empty).
+    #[track_caller]
+    fn at_pinned(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>>;
+
     /// Add a location frame with the caller's function name as context.
     ///
     /// Captures both file:line:col AND the function name at zero runtime cost.
@@ -172,6 +325,40 @@ pub trait ResultAtExt<T, E> {
     fn map_err_at<E2, F>(self, f: F) -> Result<T, At<E2>>
     where
         F: FnOnce(E) -> E2;
+
+    /// Convert the error type with `From`, keeping the trace and recording
+    /// the conversion site as a new frame.
+    ///
+    /// See [`At::map_into()`]. `map_err(Into::into)` on the inner error would
+    /// lose the location of the conversion boundary.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, ResultAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// struct DbError;
+    /// #[derive(Debug)]
+    /// struct ApiError(DbError);
+    ///
+    /// impl From<DbError> for ApiError {
+    ///     fn from(e: DbError) -> Self { ApiError(e) }
+    /// }
+    ///
+    /// fn query() -> Result<(), At<DbError>> {
+    ///     Err(at(DbError))
+    /// }
+    ///
+    /// fn handler() -> Result<(), At<ApiError>> {
+    ///     query().map_err_into()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// assert_eq!(handler().unwrap_err().frame_count(), 2);
+    /// ```
+    #[track_caller]
+    fn map_err_into<E2: From<E>>(self) -> Result<T, At<E2>>;
 }
 
 impl<T, E> ResultAtExt<T, E> for Result<T, At<E>> {
@@ -184,6 +371,15 @@ impl<T, E> ResultAtExt<T, E> for Result<T, At<E>> {
         }
     }
 
+    #[track_caller]
+    #[inline]
+    fn at_if(self, condition: bool) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_if(condition)),
+        }
+    }
+
     #[track_caller]
     #[inline]
     fn at_str(self, msg: &'static str) -> Result<T, At<E>> {
@@ -193,6 +389,51 @@ impl<T, E> ResultAtExt<T, E> for Result<T, At<E>> {
         }
     }
 
+    #[track_caller]
+    #[inline]
+    fn at_str_if(self, condition: bool, msg: &'static str) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_str_if(condition, msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at(self) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.debug_at()),
+        }
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at_str(self, msg: &'static str) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.debug_at_str(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at_string(self, f: impl FnOnce() -> String) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.debug_at_string(f)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_origin_str(self, msg: &'static str) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_origin_str(msg)),
+        }
+    }
+
     #[track_caller]
     #[inline]
     fn at_string(self, f: impl FnOnce() -> String) -> Result<T, At<E>> {
@@ -226,6 +467,40 @@ impl<T, E> ResultAtExt<T, E> for Result<T, At<E>> {
         }
     }
 
+    #[track_caller]
+    #[inline]
+    fn at_secret<C: fmt::Debug + Send + Sync + 'static>(
+        self,
+        f: impl FnOnce() -> C,
+    ) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_secret(f)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_expected_actual<X, Y>(self, expected: X, actual: Y) -> Result<T, At<E>>
+    where
+        X: fmt::Debug + Send + Sync + 'static,
+        Y: fmt::Debug + Send + Sync + 'static,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_expected_actual(expected, actual)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_bytes(self, label: &'static str, bytes: &[u8]) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_bytes(label, bytes)),
+        }
+    }
+
     #[track_caller]
     #[inline]
     fn at_error<Err: core::error::Error + Send + Sync + 'static>(
@@ -238,6 +513,83 @@ impl<T, E> ResultAtExt<T, E> for Result<T, At<E>> {
         }
     }
 
+    #[track_caller]
+    #[inline]
+    fn at_correlation(self, id: impl Into<Cow<'static, str>>) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_correlation(id)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_kv(self, key: &'static str, value: impl fmt::Display) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_kv(key, value)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_note(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_note(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_warn(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_warn(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_link(
+        self,
+        label: impl Into<Cow<'static, str>>,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_link(label, url)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_help(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_help(msg)),
+        }
+    }
+
+    #[cfg(feature = "std-backtrace")]
+    #[track_caller]
+    #[inline]
+    fn at_backtrace(self) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_backtrace()),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_pinned(self, msg: impl Into<Cow<'static, str>>) -> Result<T, At<E>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_pinned(msg)),
+        }
+    }
+
     #[track_caller]
     #[inline]
     fn at_crate(self, info: &'static AtCrateInfo) -> Result<T, At<E>> {
@@ -275,6 +627,15 @@ impl<T, E> ResultAtExt<T, E> for Result<T, At<E>> {
             Err(e) => Err(e.map_error(f)),
         }
     }
+
+    #[track_caller]
+    #[inline]
+    fn map_err_into<E2: From<E>>(self) -> Result<T, At<E2>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.map_into()),
+        }
+    }
 }
 
 // ============================================================================
@@ -325,10 +686,34 @@ pub trait ResultAtTraceableExt<T, E: AtTraceable> {
     #[track_caller]
     fn at(self) -> Result<T, E>;
 
+    /// Add the caller's location to the error trace if this is `Err` and `condition` is true.
+    #[track_caller]
+    fn at_if(self, condition: bool) -> Result<T, E>;
+
     /// Add static string context to last location (or create one if empty).
     #[track_caller]
     fn at_str(self, msg: &'static str) -> Result<T, E>;
 
+    /// Add static string context to last location if `condition` is true.
+    #[track_caller]
+    fn at_str_if(self, condition: bool, msg: &'static str) -> Result<T, E>;
+
+    /// Add the caller's location to the error trace if this is `Err`, in debug builds only.
+    #[track_caller]
+    fn debug_at(self) -> Result<T, E>;
+
+    /// Add static string context to last location if this is `Err`, in debug builds only.
+    #[track_caller]
+    fn debug_at_str(self, msg: &'static str) -> Result<T, E>;
+
+    /// Add lazily-computed string context to last location if this is `Err`, in debug builds only.
+    #[track_caller]
+    fn debug_at_string(self, f: impl FnOnce() -> String) -> Result<T, E>;
+
+    /// Add static string context to the oldest (origin) frame (or create one if empty).
+    #[track_caller]
+    fn at_origin_str(self, msg: &'static str) -> Result<T, E>;
+
     /// Add lazily-computed string context to last location (or create one if empty).
     #[track_caller]
     fn at_string(self, f: impl FnOnce() -> String) -> Result<T, E>;
@@ -345,6 +730,24 @@ pub trait ResultAtTraceableExt<T, E: AtTraceable> {
     fn at_debug<C: fmt::Debug + Send + Sync + 'static>(self, f: impl FnOnce() -> C)
     -> Result<T, E>;
 
+    /// Add lazily-computed sensitive context, printed as `[redacted]` (see [`At::at_secret()`]).
+    #[track_caller]
+    fn at_secret<C: fmt::Debug + Send + Sync + 'static>(
+        self,
+        f: impl FnOnce() -> C,
+    ) -> Result<T, E>;
+
+    /// Attach an expected and an actual value to the last location (or create one if empty).
+    #[track_caller]
+    fn at_expected_actual<X, Y>(self, expected: X, actual: Y) -> Result<T, E>
+    where
+        X: fmt::Debug + Send + Sync + 'static,
+        Y: fmt::Debug + Send + Sync + 'static;
+
+    /// Attach a bounded copy of raw bytes to the last location (or create one if empty).
+    #[track_caller]
+    fn at_bytes(self, label: &'static str, bytes: &[u8]) -> Result<T, E>;
+
     /// Add an error as context to the last location (or create one if empty).
     #[track_caller]
     fn at_error<Err: core::error::Error + Send + Sync + 'static>(self, err: Err) -> Result<T, E>;
@@ -353,6 +756,43 @@ pub trait ResultAtTraceableExt<T, E: AtTraceable> {
     #[track_caller]
     fn at_crate(self, info: &'static AtCrateInfo) -> Result<T, E>;
 
+    /// Attach a correlation/request id to last location (or create one if empty).
+    #[track_caller]
+    fn at_correlation(self, id: impl Into<Cow<'static, str>>) -> Result<T, E>;
+
+    /// Attach a `key=value` pair to the last location (or create one if empty).
+    #[track_caller]
+    fn at_kv(self, key: &'static str, value: impl fmt::Display) -> Result<T, E>;
+
+    /// Add a note-level breadcrumb to last location (or create one if empty).
+    #[track_caller]
+    fn at_note(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E>;
+
+    /// Add a warn-level context to last location (or create one if empty).
+    #[track_caller]
+    fn at_warn(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E>;
+
+    /// Attach a "see also" link to last location (or create one if empty).
+    #[track_caller]
+    fn at_link(
+        self,
+        label: impl Into<Cow<'static, str>>,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Result<T, E>;
+
+    /// Attach a remediation hint, shown in a `help:` section (or create a location if empty).
+    #[track_caller]
+    fn at_help(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E>;
+
+    /// Capture a full backtrace once per error, shown after the frames (or create a location if empty).
+    #[cfg(feature = "std-backtrace")]
+    #[track_caller]
+    fn at_backtrace(self) -> Result<T, E>;
+
+    /// Attach a message shown directly under the error header (or create a location if empty).
+    #[track_caller]
+    fn at_pinned(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E>;
+
     /// Add a location frame with the caller's function name as context.
     ///
     /// Captures both file:line:col AND the function name at zero runtime cost.
@@ -371,19 +811,82 @@ impl<T, E: AtTraceable> ResultAtTraceableExt<T, E> for Result<T, E> {
     #[track_caller]
     #[inline]
     fn at(self) -> Result<T, E> {
-        self.map_err(|e| e.at())
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at()),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_if(self, condition: bool) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_if(condition)),
+        }
     }
 
     #[track_caller]
     #[inline]
     fn at_str(self, msg: &'static str) -> Result<T, E> {
-        self.map_err(|e| e.at_str(msg))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_str(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_str_if(self, condition: bool, msg: &'static str) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_str_if(condition, msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at(self) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.debug_at()),
+        }
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at_str(self, msg: &'static str) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.debug_at_str(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at_string(self, f: impl FnOnce() -> String) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.debug_at_string(f)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_origin_str(self, msg: &'static str) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_origin_str(msg)),
+        }
     }
 
     #[track_caller]
     #[inline]
     fn at_string(self, f: impl FnOnce() -> String) -> Result<T, E> {
-        self.map_err(|e| e.at_string(f))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_string(f)),
+        }
     }
 
     #[track_caller]
@@ -392,7 +895,10 @@ impl<T, E: AtTraceable> ResultAtTraceableExt<T, E> for Result<T, E> {
         self,
         f: impl FnOnce() -> C,
     ) -> Result<T, E> {
-        self.map_err(|e| e.at_data(f))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_data(f)),
+        }
     }
 
     #[track_caller]
@@ -401,30 +907,156 @@ impl<T, E: AtTraceable> ResultAtTraceableExt<T, E> for Result<T, E> {
         self,
         f: impl FnOnce() -> C,
     ) -> Result<T, E> {
-        self.map_err(|e| e.at_debug(f))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_debug(f)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_secret<C: fmt::Debug + Send + Sync + 'static>(
+        self,
+        f: impl FnOnce() -> C,
+    ) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_secret(f)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_expected_actual<X, Y>(self, expected: X, actual: Y) -> Result<T, E>
+    where
+        X: fmt::Debug + Send + Sync + 'static,
+        Y: fmt::Debug + Send + Sync + 'static,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_expected_actual(expected, actual)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_bytes(self, label: &'static str, bytes: &[u8]) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_bytes(label, bytes)),
+        }
     }
 
     #[track_caller]
     #[inline]
     fn at_error<Err: core::error::Error + Send + Sync + 'static>(self, err: Err) -> Result<T, E> {
-        self.map_err(|e| e.at_error(err))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_error(err)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_correlation(self, id: impl Into<Cow<'static, str>>) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_correlation(id)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_kv(self, key: &'static str, value: impl fmt::Display) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_kv(key, value)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_note(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_note(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_warn(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_warn(msg)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_link(
+        self,
+        label: impl Into<Cow<'static, str>>,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_link(label, url)),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_help(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_help(msg)),
+        }
+    }
+
+    #[cfg(feature = "std-backtrace")]
+    #[track_caller]
+    #[inline]
+    fn at_backtrace(self) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_backtrace()),
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_pinned(self, msg: impl Into<Cow<'static, str>>) -> Result<T, E> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_pinned(msg)),
+        }
     }
 
     #[track_caller]
     #[inline]
     fn at_crate(self, info: &'static AtCrateInfo) -> Result<T, E> {
-        self.map_err(|e| e.at_crate(info))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_crate(info)),
+        }
     }
 
     #[track_caller]
     #[inline]
     fn at_fn<F: Fn()>(self, marker: F) -> Result<T, E> {
-        self.map_err(|e| e.at_fn(marker))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_fn(marker)),
+        }
     }
 
     #[track_caller]
     #[inline]
     fn at_named(self, name: &'static str) -> Result<T, E> {
-        self.map_err(|e| e.at_named(name))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.at_named(name)),
+        }
     }
 }
@@ -3,7 +3,8 @@
 //! This module provides colored terminal and HTML formatting for error traces.
 //! These are internal/unstable features enabled via `_termcolor` and `_html` flags.
 
-use crate::context::AtContext;
+use crate::context::{AtContext, REDACTED};
+use crate::trace::{RepeatSuffix, SkipMarker, SuppressedNote, write_backtrace};
 use crate::{At, AtCrateInfo};
 use alloc::format;
 use alloc::string::String;
@@ -16,6 +17,7 @@ use core::fmt::{self, Write as FmtWrite};
 #[cfg(feature = "_termcolor")]
 mod term {
     use super::*;
+    use crate::context::AtLevel;
     use alloc::string::ToString;
     use owo_colors::OwoColorize;
 
@@ -34,6 +36,16 @@ mod term {
                 return Ok(());
             };
 
+            #[cfg(feature = "error-id")]
+            if let Some(id) = trace.error_id() {
+                write!(f, "    {} ", "error id:".dimmed())?;
+                writeln!(f, "{}", id.to_string().bright_white())?;
+            }
+
+            for msg in trace.pinned() {
+                writeln!(f, "    {}", msg.bold())?;
+            }
+
             writeln!(f)?;
 
             // Track current crate for boundary display
@@ -62,40 +74,33 @@ mod term {
                         write!(f, "    {} ", "at".dimmed())?;
                         write!(f, "{}", loc.file().cyan())?;
                         write!(f, "{}", ":".dimmed())?;
-                        writeln!(f, "{}", loc.line().to_string().yellow())?;
+                        write!(f, "{}", loc.line().to_string().yellow())?;
+                        writeln!(f, "{}", RepeatSuffix(trace.repeat_count(i)).dimmed())?;
 
                         // Contexts with corner prefix (skip crate boundaries, already shown)
+                        if let Some(n) = trace.suppressed_count().filter(|_| i == 0) {
+                            write!(f, "       {} {} ", "╰─".dimmed(), "warn:".yellow())?;
+                            writeln!(f, "{}", SuppressedNote(n).green())?;
+                        }
                         for context in trace.contexts_at(i) {
-                            if matches!(context, AtContext::Crate(_)) {
-                                continue;
-                            }
-                            write!(f, "       {} ", "╰─".dimmed())?;
-                            match context {
-                                AtContext::Text(msg) => writeln!(f, "{}", msg.as_ref().green())?,
-                                AtContext::FunctionName(name) => {
-                                    write!(f, "{} ", "in".dimmed())?;
-                                    writeln!(f, "{}", name.bright_blue())?
-                                }
-                                AtContext::Debug(t) => {
-                                    writeln!(f, "{}", format!("{:?}", t).magenta())?
-                                }
-                                AtContext::Display(t) => {
-                                    writeln!(f, "{}", format!("{}", t).magenta())?
-                                }
-                                AtContext::Error(e) => {
-                                    write!(f, "{} ", "caused by:".dimmed())?;
-                                    writeln!(f, "{}", format!("{}", e).red())?
-                                }
-                                AtContext::Crate(_) => unreachable!(),
-                            }
+                            write_context(f, context)?;
                         }
                     }
                     None => {
-                        writeln!(f, "    {}", "[...]".dimmed())?;
+                        writeln!(f, "    {}", SkipMarker(trace.repeat_count(i)).dimmed())?;
                     }
                 }
             }
 
+            for hint in trace.suggestions() {
+                write!(f, "    {} ", "help:".cyan().bold())?;
+                writeln!(f, "{}", hint)?;
+            }
+
+            let mut backtrace = String::new();
+            write_backtrace(&mut backtrace, trace, "    ", "\n")?;
+            write!(f, "{}", backtrace.dimmed())?;
+
             Ok(())
         }
     }
@@ -103,6 +108,8 @@ mod term {
     /// Wrapper for colored terminal display with metadata.
     pub struct TermColorMetaDisplay<'a, E> {
         pub(super) traced: &'a At<E>,
+        /// Wrap `file:line` in OSC 8 hyperlinks instead of printing the URL.
+        pub(super) hyperlinks: bool,
     }
 
     impl<E: fmt::Debug> fmt::Display for TermColorMetaDisplay<'_, E> {
@@ -121,6 +128,16 @@ mod term {
                 writeln!(f, "{}", info.name().bright_blue())?;
             }
 
+            #[cfg(feature = "error-id")]
+            if let Some(id) = trace.error_id() {
+                write!(f, "  {} ", "error id:".dimmed())?;
+                writeln!(f, "{}", id.to_string().bright_white())?;
+            }
+
+            for msg in trace.pinned() {
+                writeln!(f, "    {}", msg.bold())?;
+            }
+
             writeln!(f)?;
 
             // Track current crate for boundary display
@@ -152,57 +169,135 @@ mod term {
                         write!(f, "    {} ", "at".dimmed())?;
 
                         // Show link if available
-                        if let Some(ref template) = link_template {
-                            let url = template
-                                .replace("{file}", loc.file())
-                                .replace("{line}", &loc.line().to_string());
+                        if let (Some(template), true) = (&link_template, self.hyperlinks) {
+                            let url = crate::crate_info::fill_location_link(template, loc);
+                            // OSC 8: terminals that support it make the text clickable,
+                            // others print it without the escapes
+                            write!(f, "\x1b]8;;{}\x1b\\", url)?;
+                            write!(f, "{}", loc.file().cyan().underline())?;
+                            write!(f, "{}", ":".dimmed())?;
+                            write!(f, "{}", loc.line().to_string().yellow())?;
+                            write!(f, "\x1b]8;;\x1b\\")?;
+                            writeln!(f, "{}", RepeatSuffix(trace.repeat_count(i)).dimmed())?;
+                        } else if let Some(ref template) = link_template {
+                            let url = crate::crate_info::fill_location_link(template, loc);
                             // File as link (underlined)
                             write!(f, "{}", loc.file().cyan().underline())?;
                             write!(f, "{}", ":".dimmed())?;
                             write!(f, "{}", loc.line().to_string().yellow())?;
+                            write!(f, "{}", RepeatSuffix(trace.repeat_count(i)).dimmed())?;
                             writeln!(f, " {}{}{}", "(".dimmed(), url.dimmed(), ")".dimmed())?;
                         } else {
                             write!(f, "{}", loc.file().cyan())?;
                             write!(f, "{}", ":".dimmed())?;
-                            writeln!(f, "{}", loc.line().to_string().yellow())?;
+                            write!(f, "{}", loc.line().to_string().yellow())?;
+                            writeln!(f, "{}", RepeatSuffix(trace.repeat_count(i)).dimmed())?;
                         }
 
                         // Contexts (skip crate boundaries, already shown)
+                        if let Some(n) = trace.suppressed_count().filter(|_| i == 0) {
+                            write!(f, "       {} {} ", "╰─".dimmed(), "warn:".yellow())?;
+                            writeln!(f, "{}", SuppressedNote(n).green())?;
+                        }
                         for context in trace.contexts_at(i) {
-                            if matches!(context, AtContext::Crate(_)) {
-                                continue;
-                            }
-                            write!(f, "       {} ", "╰─".dimmed())?;
-                            match context {
-                                AtContext::Text(msg) => writeln!(f, "{}", msg.as_ref().green())?,
-                                AtContext::FunctionName(name) => {
-                                    write!(f, "{} ", "in".dimmed())?;
-                                    writeln!(f, "{}", name.bright_blue())?
-                                }
-                                AtContext::Debug(t) => {
-                                    writeln!(f, "{}", format!("{:?}", t).magenta())?
-                                }
-                                AtContext::Display(t) => {
-                                    writeln!(f, "{}", format!("{}", t).magenta())?
-                                }
-                                AtContext::Error(e) => {
-                                    write!(f, "{} ", "caused by:".dimmed())?;
-                                    writeln!(f, "{}", format!("{}", e).red())?
-                                }
-                                AtContext::Crate(_) => unreachable!(),
-                            }
+                            write_context(f, context)?;
                         }
                     }
                     None => {
-                        writeln!(f, "    {}", "[...]".dimmed())?;
+                        writeln!(f, "    {}", SkipMarker(trace.repeat_count(i)).dimmed())?;
                     }
                 }
             }
 
+            for hint in trace.suggestions() {
+                write!(f, "    {} ", "help:".cyan().bold())?;
+                writeln!(f, "{}", hint)?;
+            }
+
+            let mut backtrace = String::new();
+            write_backtrace(&mut backtrace, trace, "    ", "\n")?;
+            write!(f, "{}", backtrace.dimmed())?;
+
             Ok(())
         }
     }
 
+    /// Write a context as a `╰─` line under its frame.
+    ///
+    /// Crate boundaries, hints, pinned messages, backtraces and repeat counts
+    /// are shown elsewhere and write nothing.
+    fn write_context(f: &mut fmt::Formatter<'_>, context: &AtContext) -> fmt::Result {
+        if matches!(
+            context,
+            AtContext::Crate(_)
+                | AtContext::Suggestion(_)
+                | AtContext::Pinned(_)
+                | AtContext::Backtrace(_)
+                | AtContext::Repeated(_)
+        ) {
+            return Ok(());
+        }
+        write!(f, "       {} ", "╰─".dimmed())?;
+        match context {
+            AtContext::Text(msg) => writeln!(f, "{}", msg.as_ref().green())?,
+            AtContext::FunctionName(name) => {
+                write!(f, "{} ", "in".dimmed())?;
+                writeln!(f, "{}", name.bright_blue())?
+            }
+            AtContext::Debug(t) => writeln!(f, "{}", format!("{:?}", t).magenta())?,
+            AtContext::ExpectedActual(pair) => {
+                let mut block = String::new();
+                pair.write_block(&mut block, "          ")?;
+                writeln!(f, "{}", block.magenta())?
+            }
+            AtContext::Bytes(bytes) => {
+                let mut dump = String::new();
+                bytes.write_dump(&mut dump, "          ")?;
+                writeln!(f, "{}", dump.magenta())?
+            }
+            AtContext::Remote(trace) => {
+                let mut block = String::new();
+                trace.write_block(&mut block, "          ")?;
+                writeln!(f, "{}", block.dimmed())?
+            }
+            AtContext::Display(t) => writeln!(f, "{}", format!("{}", t).magenta())?,
+            AtContext::Secret(_) => writeln!(f, "{}", REDACTED.dimmed())?,
+            AtContext::Error(e) => {
+                write!(f, "{} ", "caused by:".dimmed())?;
+                writeln!(f, "{}", format!("{}", e).red())?
+            }
+            AtContext::Leveled(level, msg) => {
+                let label = format!("{}:", level);
+                match level {
+                    AtLevel::Warn => write!(f, "{} ", label.yellow())?,
+                    _ => write!(f, "{} ", label.dimmed())?,
+                }
+                writeln!(f, "{}", msg.as_ref().green())?
+            }
+            AtContext::Link { label, url } => {
+                write!(f, "{} ", "see:".dimmed())?;
+                write!(f, "{} ", label.as_ref().bright_blue())?;
+                writeln!(f, "({})", url.as_ref().underline())?
+            }
+            AtContext::SpanId(id) => {
+                write!(f, "{} ", "span:".dimmed())?;
+                writeln!(f, "{}", id.dimmed())?
+            }
+            AtContext::KeyValue { key, value } => {
+                write!(f, "{}{}", key.as_ref().dimmed(), "=".dimmed())?;
+                writeln!(f, "{}", value.as_ref().green())?
+            }
+            AtContext::Crate(_)
+            | AtContext::Suggestion(_)
+            | AtContext::Pinned(_)
+            | AtContext::Backtrace(_)
+            | AtContext::Repeated(_) => {
+                unreachable!()
+            }
+        }
+        Ok(())
+    }
+
     fn build_link_base(info: &AtCrateInfo) -> Option<String> {
         let repo = info.repo()?;
         let commit = info.commit()?;
@@ -218,8 +313,43 @@ mod term {
 
         Some(base)
     }
+
+    /// Wrapper choosing colored or plain output for the current terminal.
+    #[cfg(feature = "std")]
+    pub struct PrettyDisplay<'a, E> {
+        pub(super) traced: &'a At<E>,
+        pub(super) color: bool,
+    }
+
+    #[cfg(feature = "std")]
+    impl<E: fmt::Debug> fmt::Display for PrettyDisplay<'_, E> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if self.color {
+                let display = TermColorMetaDisplay {
+                    traced: self.traced,
+                    hyperlinks: true,
+                };
+                fmt::Display::fmt(&display, f)
+            } else {
+                fmt::Display::fmt(&self.traced.display_with_meta(), f)
+            }
+        }
+    }
+
+    /// Whether stderr is a terminal that should get colors: not redirected,
+    /// `NO_COLOR` unset or empty, and `TERM` not `dumb`.
+    #[cfg(feature = "std")]
+    pub(super) fn stderr_supports_color() -> bool {
+        use std::io::IsTerminal;
+
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        let dumb = std::env::var_os("TERM").is_some_and(|v| v == "dumb");
+        !no_color && !dumb && std::io::stderr().is_terminal()
+    }
 }
 
+#[cfg(all(feature = "_termcolor", feature = "std"))]
+pub use term::PrettyDisplay;
 #[cfg(feature = "_termcolor")]
 pub use term::{TermColorDisplay, TermColorMetaDisplay};
 
@@ -230,7 +360,6 @@ pub use term::{TermColorDisplay, TermColorMetaDisplay};
 #[cfg(feature = "_html")]
 mod html {
     use super::*;
-    use alloc::string::ToString;
 
     /// CSS styles for HTML error output (Catppuccin Mocha theme).
     pub const HTML_STYLES: &str = r#"
@@ -255,6 +384,13 @@ mod html {
 .whereat-error .crate-name {
     color: #89b4fa;
 }
+.whereat-error .error-id {
+    color: #6c7086;
+    margin-bottom: 8px;
+}
+.whereat-error .error-id code {
+    color: #f9e2af;
+}
 .whereat-error .location {
     margin-left: 16px;
 }
@@ -283,6 +419,43 @@ mod html {
 .whereat-error .context-error {
     color: #f38ba8;
 }
+.whereat-error .level-note {
+    color: #6c7086;
+}
+.whereat-error .level-warn {
+    color: #fab387;
+}
+.whereat-error .context-link {
+    color: #89b4fa;
+}
+.whereat-error .context-span {
+    color: #6c7086;
+}
+.whereat-error .context-key {
+    color: #6c7086;
+}
+.whereat-error .remote-trace {
+    margin: 0 0 0 16px;
+    color: #a6adc8;
+}
+.whereat-error .context-secret {
+    color: #6c7086;
+    font-style: italic;
+}
+.whereat-error .pinned {
+    margin-left: 16px;
+    color: #f9e2af;
+    font-weight: bold;
+}
+.whereat-error .help {
+    margin-top: 8px;
+    color: #94e2d5;
+    font-weight: bold;
+}
+.whereat-error .help-text {
+    color: #cdd6f4;
+    font-weight: normal;
+}
 .whereat-error .skip-marker {
     margin-left: 16px;
     color: #6c7086;
@@ -339,6 +512,21 @@ mod html {
                 writeln!(f, "</span></div>")?;
             }
 
+            #[cfg(feature = "error-id")]
+            if let Some(id) = trace.error_id() {
+                writeln!(
+                    f,
+                    "<div class=\"error-id\">error id: <code>{}</code></div>",
+                    id
+                )?;
+            }
+
+            for msg in trace.pinned() {
+                write!(f, "<div class=\"pinned\">")?;
+                write_html_escaped(f, msg)?;
+                writeln!(f, "</div>")?;
+            }
+
             // Track current crate for boundary display
             let mut current_crate: Option<&str> = trace.crate_info().map(|i| i.name());
 
@@ -369,9 +557,7 @@ mod html {
                         write!(f, "<span class=\"at-prefix\">at </span>")?;
 
                         if let Some(ref template) = link_template {
-                            let url = template
-                                .replace("{file}", loc.file())
-                                .replace("{line}", &loc.line().to_string());
+                            let url = crate::crate_info::fill_location_link(template, loc);
                             write!(f, "<a href=\"")?;
                             write_html_escaped(f, &url)?;
                             write!(f, "\" target=\"_blank\">")?;
@@ -388,11 +574,32 @@ mod html {
                             write!(f, "<span class=\"at-prefix\">:</span>")?;
                             write!(f, "<span class=\"line\">{}</span>", loc.line())?;
                         }
+                        let repeat = trace.repeat_count(i);
+                        if repeat > 1 {
+                            write!(f, " <span class=\"repeat\">(x{})</span>", repeat)?;
+                        }
                         writeln!(f, "</div>")?;
 
+                        if let Some(n) = trace.suppressed_count().filter(|_| i == 0) {
+                            write!(f, "<div class=\"context\">╰─ ")?;
+                            write!(f, "<span class=\"level-warn\">warn:</span> ")?;
+                            writeln!(
+                                f,
+                                "<span class=\"context-text\">{}</span></div>",
+                                SuppressedNote(n)
+                            )?;
+                        }
+
                         // Contexts (skip crate boundaries, already shown)
                         for context in trace.contexts_at(i) {
-                            if matches!(context, AtContext::Crate(_)) {
+                            if matches!(
+                                context,
+                                AtContext::Crate(_)
+                                    | AtContext::Suggestion(_)
+                                    | AtContext::Pinned(_)
+                                    | AtContext::Backtrace(_)
+                                    | AtContext::Repeated(_)
+                            ) {
                                 continue;
                             }
                             write!(f, "<div class=\"context\">╰─ ")?;
@@ -412,26 +619,100 @@ mod html {
                                     write_html_escaped(f, &format!("{:?}", t))?;
                                     writeln!(f, "</span></div>")?;
                                 }
+                                AtContext::ExpectedActual(pair) => {
+                                    write!(f, "<span class=\"context-data\">")?;
+                                    write_html_escaped(f, &format!("{}", pair))?;
+                                    writeln!(f, "</span></div>")?;
+                                }
+                                AtContext::Bytes(bytes) => {
+                                    write!(f, "<span class=\"context-data\">")?;
+                                    write_html_escaped(f, &format!("{}", bytes))?;
+                                    writeln!(f, "</span></div>")?;
+                                }
+                                AtContext::Remote(trace) => {
+                                    write!(f, "remote trace:<pre class=\"remote-trace\">")?;
+                                    write_html_escaped(f, &format!("{}", trace))?;
+                                    writeln!(f, "</pre></div>")?;
+                                }
                                 AtContext::Display(t) => {
                                     write!(f, "<span class=\"context-data\">")?;
                                     write_html_escaped(f, &format!("{}", t))?;
                                     writeln!(f, "</span></div>")?;
                                 }
+                                AtContext::Secret(_) => {
+                                    writeln!(
+                                        f,
+                                        "<span class=\"context-secret\">{}</span></div>",
+                                        REDACTED
+                                    )?;
+                                }
                                 AtContext::Error(e) => {
                                     write!(f, "caused by: <span class=\"context-error\">")?;
                                     write_html_escaped(f, &format!("{}", e))?;
                                     writeln!(f, "</span></div>")?;
                                 }
-                                AtContext::Crate(_) => unreachable!(),
+                                AtContext::Leveled(level, msg) => {
+                                    write!(
+                                        f,
+                                        "<span class=\"level-{}\">{}:</span> ",
+                                        level, level
+                                    )?;
+                                    write!(f, "<span class=\"context-text\">")?;
+                                    write_html_escaped(f, msg.as_ref())?;
+                                    writeln!(f, "</span></div>")?;
+                                }
+                                AtContext::Link { label, url } => {
+                                    write!(f, "see: <a class=\"context-link\" href=\"")?;
+                                    write_html_escaped(f, url.as_ref())?;
+                                    write!(f, "\" target=\"_blank\">")?;
+                                    write_html_escaped(f, label.as_ref())?;
+                                    writeln!(f, "</a></div>")?;
+                                }
+                                AtContext::SpanId(id) => {
+                                    writeln!(
+                                        f,
+                                        "span: <span class=\"context-span\">{}</span></div>",
+                                        id
+                                    )?;
+                                }
+                                AtContext::KeyValue { key, value } => {
+                                    write!(f, "<span class=\"context-key\">")?;
+                                    write_html_escaped(f, key.as_ref())?;
+                                    write!(f, "</span>=<span class=\"context-text\">")?;
+                                    write_html_escaped(f, value.as_ref())?;
+                                    writeln!(f, "</span></div>")?;
+                                }
+                                AtContext::Crate(_)
+                                | AtContext::Suggestion(_)
+                                | AtContext::Pinned(_)
+                                | AtContext::Backtrace(_)
+                                | AtContext::Repeated(_) => {
+                                    unreachable!()
+                                }
                             }
                         }
                     }
                     None => {
-                        writeln!(f, "<div class=\"skip-marker\">[...]</div>")?;
+                        let marker = SkipMarker(trace.repeat_count(i));
+                        writeln!(f, "<div class=\"skip-marker\">{}</div>", marker)?;
                     }
                 }
             }
 
+            for hint in trace.suggestions() {
+                write!(f, "<div class=\"help\">help: <span class=\"help-text\">")?;
+                write_html_escaped(f, hint)?;
+                writeln!(f, "</span></div>")?;
+            }
+
+            let mut backtrace = String::new();
+            write_backtrace(&mut backtrace, trace, "", "\n")?;
+            if !backtrace.is_empty() {
+                write!(f, "<pre class=\"backtrace\">")?;
+                write_html_escaped(f, &backtrace)?;
+                writeln!(f, "</pre>")?;
+            }
+
             writeln!(f, "</div>")?;
             Ok(())
         }
@@ -493,7 +774,55 @@ impl<E: fmt::Debug> At<E> {
     #[inline]
     #[cfg(feature = "_termcolor")]
     pub fn display_color_meta(&self) -> TermColorMetaDisplay<'_, E> {
-        TermColorMetaDisplay { traced: self }
+        TermColorMetaDisplay {
+            traced: self,
+            hyperlinks: false,
+        }
+    }
+
+    /// Format the error with terminal colors, making each `file:line` a
+    /// clickable OSC 8 hyperlink to its repository URL.
+    ///
+    /// Frames without a link (no repository or commit in their crate info)
+    /// are printed as in [`display_color_meta()`](Self::display_color_meta).
+    /// Requires the `_termcolor` feature.
+    #[inline]
+    #[cfg(feature = "_termcolor")]
+    pub fn display_color_links(&self) -> TermColorMetaDisplay<'_, E> {
+        TermColorMetaDisplay {
+            traced: self,
+            hyperlinks: true,
+        }
+    }
+
+    /// Format the error for a human at a terminal.
+    ///
+    /// If stderr is a terminal and `NO_COLOR` is not set, this is
+    /// [`display_color_links()`](Self::display_color_links); otherwise
+    /// (redirected output, CI logs, `NO_COLOR=1`, `TERM=dumb`) it is the plain
+    /// [`display_with_meta()`](Self::display_with_meta). The check runs when
+    /// this is called.
+    ///
+    /// Requires the `_termcolor` and `std` features.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err: At<MyError> = at(MyError).at_str("loading config");
+    /// eprintln!("{}", err.display_pretty());
+    /// ```
+    #[inline]
+    #[cfg(all(feature = "_termcolor", feature = "std"))]
+    pub fn display_pretty(&self) -> PrettyDisplay<'_, E> {
+        PrettyDisplay {
+            traced: self,
+            color: term::stderr_supports_color(),
+        }
     }
 
     /// Format the error as HTML.
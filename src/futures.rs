@@ -0,0 +1,235 @@
+//! Tracing combinators for futures and streams (the `futures` feature).
+//!
+//! [`FutureAtExt`] puts `.at()`, `.at_str()` and `.at_string()` on futures of
+//! traced results, so a pipeline of futures can be traced before it is
+//! awaited. The location is captured where the combinator is called, not
+//! where the future is eventually polled. [`StreamAtExt`] is the stream
+//! counterpart of [`IteratorAtExt`](crate::IteratorAtExt).
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::future::Future;
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::At;
+use crate::context::AtContext;
+use crate::iter::ErrorAttach;
+
+/// Extension trait for futures resolving to `Result<T, At<E>>`.
+///
+/// Each method mirrors its [`ResultAtExt`](crate::ResultAtExt) counterpart,
+/// applied to the output once the future completes. Nothing runs on the
+/// `Ok` path.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, At, FutureAtExt};
+///
+/// #[derive(Debug)]
+/// struct FetchError;
+///
+/// async fn fetch(id: u64) -> Result<Vec<u8>, At<FetchError>> {
+///     Err(at(FetchError))
+/// }
+///
+/// async fn load(id: u64) -> Result<Vec<u8>, At<FetchError>> {
+///     let pending = fetch(id).at_str("fetching profile"); // location captured here
+///     pending.at().await
+/// }
+/// # use core::future::Future;
+/// # let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+/// # let mut fut = core::pin::pin!(load(7));
+/// # let core::task::Poll::Ready(Err(err)) = fut.as_mut().poll(&mut cx) else { panic!() };
+/// # assert_eq!(err.frame_count(), 2);
+/// ```
+pub trait FutureAtExt<T, E>: Future<Output = Result<T, At<E>>> + Sized {
+    /// Add the caller's location as a new frame to the error, if any.
+    #[track_caller]
+    fn at(self) -> AtFuture<Self>;
+
+    /// Add a static string context to the error's last frame, if any.
+    #[track_caller]
+    fn at_str(self, msg: &'static str) -> AtFuture<Self>;
+
+    /// Add a lazily-computed string context to the error's last frame, if any.
+    ///
+    /// The closure only runs if the future resolves to an error.
+    #[track_caller]
+    fn at_string<F: FnOnce() -> String>(self, f: F) -> AtFuture<Self, F>;
+}
+
+impl<Fut, T, E> FutureAtExt<T, E> for Fut
+where
+    Fut: Future<Output = Result<T, At<E>>>,
+{
+    #[track_caller]
+    #[inline]
+    fn at(self) -> AtFuture<Self> {
+        AtFuture::new(self, Attach::Frame)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_str(self, msg: &'static str) -> AtFuture<Self> {
+        AtFuture::new(self, Attach::Str(msg))
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_string<F: FnOnce() -> String>(self, f: F) -> AtFuture<Self, F> {
+        AtFuture::new(self, Attach::String(Some(f)))
+    }
+}
+
+/// What an [`AtFuture`] adds to an error.
+enum Attach<F> {
+    Frame,
+    Str(&'static str),
+    String(Option<F>),
+}
+
+pin_project! {
+    /// Future returned by the [`FutureAtExt`] methods.
+    #[must_use = "futures do nothing unless awaited"]
+    pub struct AtFuture<Fut, F = fn() -> String> {
+        #[pin]
+        future: Fut,
+        location: &'static Location<'static>,
+        attach: Attach<F>,
+    }
+}
+
+impl<Fut, F> AtFuture<Fut, F> {
+    #[track_caller]
+    #[inline]
+    fn new(future: Fut, attach: Attach<F>) -> Self {
+        AtFuture {
+            future,
+            location: Location::caller(),
+            attach,
+        }
+    }
+}
+
+impl<Fut, F, T, E> Future for AtFuture<Fut, F>
+where
+    Fut: Future<Output = Result<T, At<E>>>,
+    F: FnOnce() -> String,
+{
+    type Output = Result<T, At<E>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let error = match this.future.poll(cx) {
+            Poll::Ready(Ok(value)) => return Poll::Ready(Ok(value)),
+            Poll::Ready(Err(error)) => error,
+            Poll::Pending => return Poll::Pending,
+        };
+        let location = *this.location;
+        Poll::Ready(Err(match this.attach {
+            Attach::Frame => error.at_location(location),
+            Attach::Str(msg) => {
+                error.at_context_location(location, || Some(AtContext::Text(Cow::Borrowed(*msg))))
+            }
+            Attach::String(f) => error.at_context_location(location, || {
+                f.take().map(|f| AtContext::Text(Cow::Owned(f())))
+            }),
+        }))
+    }
+}
+
+/// Extension trait for streams of `Result<T, At<E>>`.
+///
+/// The stream counterpart of [`IteratorAtExt`](crate::IteratorAtExt): every
+/// `Err` item is traced at the adapter's call site.
+///
+/// ## Example
+///
+/// ```rust
+/// use futures_core::Stream;
+/// use whereat::{At, StreamAtExt};
+///
+/// #[derive(Debug)]
+/// struct RecvError;
+///
+/// fn messages(
+///     input: impl Stream<Item = Result<String, At<RecvError>>>,
+/// ) -> impl Stream<Item = Result<String, At<RecvError>>> {
+///     input.at_str_errors("receiving messages")
+/// }
+/// ```
+pub trait StreamAtExt<T, E>: Stream<Item = Result<T, At<E>>> + Sized {
+    /// Add the caller's location as a new frame to every error item.
+    #[track_caller]
+    fn at_errors(self) -> AtStreamErrors<Self>;
+
+    /// Add a static string context to the last frame of every error item.
+    #[track_caller]
+    fn at_str_errors(self, msg: &'static str) -> AtStreamErrors<Self>;
+}
+
+impl<S, T, E> StreamAtExt<T, E> for S
+where
+    S: Stream<Item = Result<T, At<E>>>,
+{
+    #[track_caller]
+    #[inline]
+    fn at_errors(self) -> AtStreamErrors<Self> {
+        AtStreamErrors::new(self, ErrorAttach::Frame)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_str_errors(self, msg: &'static str) -> AtStreamErrors<Self> {
+        AtStreamErrors::new(self, ErrorAttach::Str(msg))
+    }
+}
+
+pin_project! {
+    /// Stream returned by the [`StreamAtExt`] methods.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct AtStreamErrors<S> {
+        #[pin]
+        stream: S,
+        location: &'static Location<'static>,
+        attach: ErrorAttach,
+    }
+}
+
+impl<S> AtStreamErrors<S> {
+    #[track_caller]
+    #[inline]
+    fn new(stream: S, attach: ErrorAttach) -> Self {
+        AtStreamErrors {
+            stream,
+            location: Location::caller(),
+            attach,
+        }
+    }
+}
+
+impl<S, T, E> Stream for AtStreamErrors<S>
+where
+    S: Stream<Item = Result<T, At<E>>>,
+{
+    type Item = Result<T, At<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let (location, attach) = (*this.location, *this.attach);
+        this.stream
+            .poll_next(cx)
+            .map(|item| item.map(|r| r.map_err(|e| attach.apply(e, location))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
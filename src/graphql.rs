@@ -0,0 +1,73 @@
+//! `async-graphql` integration (the `async-graphql` feature).
+//!
+//! [`At::into_graphql_error()`] turns a traced error into an
+//! [`async_graphql::Error`] whose message is the error's `Display` output and
+//! whose extensions carry the error kind, [fingerprint](At::fingerprint) and
+//! sanitized trace (frame locations only, as in [`AtProblem`](crate::AtProblem)):
+//!
+//! ```json
+//! {
+//!   "message": "user not found",
+//!   "extensions": {
+//!     "kind": "NotFound",
+//!     "fingerprint": "9c1f0e2ab5d7c3e4",
+//!     "trace": ["src/db.rs:10:5", "src/schema.rs:42:9"]
+//!   }
+//! }
+//! ```
+//!
+//! The original `At<E>` is kept in the error's `source` field for server-side
+//! logging. `From` can't be used: async-graphql already converts every
+//! `Display` type, which would drop the extensions.
+
+use std::string::ToString;
+use std::sync::Arc;
+
+use async_graphql::ErrorExtensionValues;
+
+use crate::At;
+use crate::localize::variant_name;
+use crate::problem::sanitized_trace;
+
+impl<E: core::fmt::Display + core::fmt::Debug + Send + Sync + 'static> At<E> {
+    /// Convert into an [`async_graphql::Error`] with trace extensions.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// enum ApiError { NotFound }
+    ///
+    /// impl core::fmt::Display for ApiError {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str("user not found")
+    ///     }
+    /// }
+    ///
+    /// fn user(id: u64) -> async_graphql::Result<String> {
+    ///     let found: Result<String, At<ApiError>> = Err(at(ApiError::NotFound));
+    ///     found.map_err(At::into_graphql_error)
+    /// }
+    ///
+    /// let err = user(42).unwrap_err();
+    /// assert_eq!(err.message, "user not found");
+    /// let ext = err.extensions.as_ref().unwrap();
+    /// assert_eq!(ext.get("kind").unwrap().to_string(), "\"NotFound\"");
+    /// let source = err.source.as_ref().unwrap();
+    /// assert!(source.downcast_ref::<At<ApiError>>().is_some());
+    /// ```
+    pub fn into_graphql_error(self) -> async_graphql::Error {
+        let mut extensions = ErrorExtensionValues::default();
+        let debug = std::format!("{:?}", self.error());
+        extensions.set("kind", variant_name(&debug));
+        extensions.set("fingerprint", std::format!("{:016x}", self.fingerprint()));
+        extensions.set("trace", sanitized_trace(&self));
+        async_graphql::Error {
+            message: self.error().to_string(),
+            source: Some(Arc::new(self)),
+            extensions: Some(extensions),
+        }
+    }
+}
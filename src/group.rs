@@ -0,0 +1,362 @@
+//! Several traced errors collected together.
+//!
+//! [`AtGroup`] keeps every failure from a batch of operations instead of
+//! dropping all but the first, each with its own trace, plus one shared trace
+//! for the place where they were gathered. [`try_join_at!`](crate::try_join_at)
+//! produces one when concurrent branches fail.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::future::Future;
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::trace::write_traceable_body;
+use crate::{At, AtTrace, AtTraceBoxed, AtTraceFormat};
+
+// ============================================================================
+// AtGroup - multiple traced errors
+// ============================================================================
+
+/// A collection of traced errors with a shared trace of where they were gathered.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, AtGroup};
+///
+/// #[derive(Debug)]
+/// struct MyError(u32);
+///
+/// let mut group = AtGroup::new(); // captures this line
+/// for id in [3, 7] {
+///     group.push(at(MyError(id)));
+/// }
+/// assert_eq!(group.len(), 2);
+/// assert_eq!(group.errors()[1].error().0, 7);
+/// assert_eq!(group.trace().unwrap().frame_count(), 1);
+/// ```
+pub struct AtGroup<E> {
+    errors: Vec<At<E>>,
+    trace: AtTraceBoxed,
+}
+
+impl<E> AtGroup<E> {
+    /// Create an empty group, capturing the caller's location as the shared frame.
+    #[track_caller]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            errors: Vec::new(),
+            trace: AtTraceBoxed::capture(),
+        }
+    }
+
+    /// Add an error, keeping its own trace.
+    #[inline]
+    pub fn push(&mut self, error: At<E>) {
+        self.errors.push(error);
+    }
+
+    /// Add the caller's location to the shared trace.
+    ///
+    /// A group built with `collect()` has no shared frame, because the
+    /// location of the `from_iter()` call is inside the standard library;
+    /// `.at()` records the gathering site instead.
+    #[track_caller]
+    #[inline]
+    pub fn at(mut self) -> Self {
+        let _ = self.trace.get_or_insert_mut().try_push(Location::caller());
+        self
+    }
+
+    /// The collected errors, in the order they were added.
+    #[inline]
+    pub fn errors(&self) -> &[At<E>] {
+        &self.errors
+    }
+
+    /// Take the collected errors, discarding the shared trace.
+    #[inline]
+    pub fn into_errors(self) -> Vec<At<E>> {
+        self.errors
+    }
+
+    /// Get the number of errors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Check if the group holds no errors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Get the shared trace, if allocated.
+    #[inline]
+    pub fn trace(&self) -> Option<&AtTrace> {
+        self.trace.as_ref()
+    }
+}
+
+impl<E> Default for AtGroup<E> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> FromIterator<At<E>> for AtGroup<E> {
+    /// Collect errors into a group without a shared frame; see [`at()`](AtGroup::at).
+    fn from_iter<I: IntoIterator<Item = At<E>>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+            trace: AtTraceBoxed::new(),
+        }
+    }
+}
+
+impl<E> Extend<At<E>> for AtGroup<E> {
+    fn extend<I: IntoIterator<Item = At<E>>>(&mut self, iter: I) {
+        self.errors.extend(iter);
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for AtGroup<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtGroup")
+            .field("errors", &self.errors)
+            .field("trace", &self.trace)
+            .finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for AtGroup<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.len() {
+            1 => write!(f, "1 error: ")?,
+            n => write!(f, "{} errors: ", n)?,
+        }
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: core::error::Error> core::error::Error for AtGroup<E> {}
+
+impl<E: fmt::Display> AtGroup<E> {
+    /// Format the shared trace, then every error's
+    /// [`full_trace()`](At::full_trace) indented below it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtGroup};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError(u32);
+    ///
+    /// impl core::fmt::Display for MyError {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         write!(f, "row {} invalid", self.0)
+    ///     }
+    /// }
+    ///
+    /// let group: AtGroup<MyError> = [3, 7].into_iter().map(|id| at(MyError(id))).collect();
+    /// println!("{}", group.at().full_trace());
+    /// // Output:
+    /// // 2 errors
+    /// //     at src/main.rs:14:34
+    /// //
+    /// //     row 3 invalid
+    /// //         at src/main.rs:14:64
+    /// //
+    /// //     row 7 invalid
+    /// //         at src/main.rs:14:64
+    /// ```
+    #[inline]
+    pub fn full_trace(&self) -> impl fmt::Display + '_ {
+        GroupFullTraceDisplay { group: self }
+    }
+}
+
+/// Formatter returned by [`AtGroup::full_trace()`].
+struct GroupFullTraceDisplay<'a, E> {
+    group: &'a AtGroup<E>,
+}
+
+impl<E: fmt::Display> fmt::Display for GroupFullTraceDisplay<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Indents every line after the first by four spaces.
+        struct Indented<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl fmt::Write for Indented<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for (i, line) in s.split('\n').enumerate() {
+                    if i > 0 {
+                        self.0.write_str("\n    ")?;
+                    }
+                    self.0.write_str(line)?;
+                }
+                Ok(())
+            }
+        }
+
+        match self.group.errors.len() {
+            1 => write!(f, "1 error")?,
+            n => write!(f, "{} errors", n)?,
+        }
+        write_traceable_body(
+            f,
+            self.group.trace.as_ref(),
+            &AtTraceFormat::DEFAULT,
+            None,
+            None,
+            true,
+        )?;
+        for error in &self.group.errors {
+            f.write_str("\n\n    ")?;
+            fmt::write(&mut Indented(f), format_args!("{}", error.full_trace()))?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// try_join_at! support
+// ============================================================================
+
+/// One branch of [`try_join_at!`](crate::try_join_at): the future, then its output.
+#[doc(hidden)]
+pub enum __TryJoinSlot<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(Option<F::Output>),
+}
+
+impl<F: Future> __TryJoinSlot<F> {
+    #[inline]
+    pub fn new(future: F) -> Self {
+        Self::Pending(Box::pin(future))
+    }
+
+    /// Poll the branch if still pending. Returns true once it has completed.
+    #[inline]
+    pub fn poll(&mut self, cx: &mut Context<'_>) -> bool {
+        if let Self::Pending(future) = self {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(output) => *self = Self::Done(Some(output)),
+                Poll::Pending => return false,
+            }
+        }
+        true
+    }
+
+    /// Take the output of a completed branch.
+    #[inline]
+    pub fn take(&mut self) -> F::Output {
+        match self {
+            Self::Done(output) => output.take().expect("try_join_at! branch taken twice"),
+            Self::Pending(_) => panic!("try_join_at! branch taken before completion"),
+        }
+    }
+}
+
+/// Await several fallible futures concurrently, keeping every failure.
+///
+/// Each branch must return `Result<T, At<E>>` with the same `E`. Unlike a
+/// short-circuiting `try_join!`, all branches run to completion. The result is
+/// `Ok((t1, t2, ...))` if every branch succeeded, otherwise an [`AtGroup`]
+/// holding each branch's error (with its own trace) whose shared trace points
+/// at the macro call.
+///
+/// Must be used inside an `async` context; the macro awaits internally.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, try_join_at, At, AtGroup};
+///
+/// #[derive(Debug)]
+/// struct MyError(&'static str);
+///
+/// async fn fetch(name: &'static str, ok: bool) -> Result<u32, At<MyError>> {
+///     if ok { Ok(1) } else { Err(at(MyError(name))) }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let (a, b) = try_join_at!(fetch("a", true), fetch("b", true)).unwrap();
+/// assert_eq!(a + b, 2);
+///
+/// let group: AtGroup<MyError> =
+///     try_join_at!(fetch("a", false), fetch("b", true), fetch("c", false)).unwrap_err();
+/// let failed: Vec<_> = group.errors().iter().map(|e| e.error().0).collect();
+/// assert_eq!(failed, ["a", "c"]);
+/// # });
+/// ```
+#[macro_export]
+macro_rules! try_join_at {
+    // Normalize: give each future a list of `_` to skip when picking it out
+    // of the slot tuple by pattern.
+    (@{ ( $($count:tt)* ) $( ( $($skip:tt)* ) $e:expr, )* } $next:expr, $($rest:expr,)*) => {
+        $crate::try_join_at!(
+            @{ ( $($count)* _ ) $( ( $($skip)* ) $e, )* ( $($count)* ) $next, } $($rest,)*
+        )
+    };
+
+    (@{ ( $($count:tt)* ) $( ( $($skip:tt)* ) $e:expr, )* }) => {{
+        let mut slots = ( $( $crate::__TryJoinSlot::new($e), )* );
+        ::core::future::poll_fn(|cx| {
+            let mut done = true;
+            $(
+                let ( $($skip,)* slot, .. ) = &mut slots;
+                done &= slot.poll(cx);
+            )*
+            if done {
+                ::core::task::Poll::Ready(())
+            } else {
+                ::core::task::Poll::Pending
+            }
+        })
+        .await;
+
+        let mut group = $crate::AtGroup::new();
+        let mut outputs = ( $(
+            {
+                let ( $($skip,)* slot, .. ) = &mut slots;
+                match slot.take() {
+                    ::core::result::Result::Ok(value) => ::core::option::Option::Some(value),
+                    ::core::result::Result::Err(error) => {
+                        group.push(error);
+                        ::core::option::Option::None
+                    }
+                }
+            },
+        )* );
+        if group.is_empty() {
+            ::core::result::Result::Ok(( $(
+                {
+                    let ( $($skip,)* output, .. ) = &mut outputs;
+                    match output.take() {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => ::core::unreachable!(),
+                    }
+                },
+            )* ))
+        } else {
+            ::core::result::Result::Err(group)
+        }
+    }};
+
+    ( $($e:expr),+ $(,)? ) => {
+        $crate::try_join_at!(@{ () } $($e,)+)
+    };
+}
@@ -0,0 +1,93 @@
+//! `tonic` integration (the `tonic` feature).
+//!
+//! [`At::into_status()`] turns a traced error into a [`tonic::Status`] whose
+//! message is the error's `Display` output and whose metadata carries the
+//! trace under [`AT_TRACE_METADATA_KEY`], in the [`persist`](AtTrace::encode)
+//! binary format. On the client, [`AtTraceDetached::from_status()`] reads it
+//! back, ready to attach to the client's own error with
+//! [`at_remote_trace()`](At::at_remote_trace):
+//!
+//! ```text
+//! Error: Upstream
+//!     at src/client.rs:31:16
+//!        ╰─ remote trace:
+//!           at src/db.rs:10:5
+//!              ╰─ looking up user
+//!           at src/service.rs:42:9
+//! ```
+//!
+//! Contexts travel as their display text, so secrets stay redacted, but
+//! source paths and context text do reach the client: sending the trace is
+//! opt-in, and the `From<At<E>>` conversion that `?` uses sends the message
+//! only. The metadata is sent as an HTTP/2 header: servers and proxies limit
+//! header size (often to 8-16 KiB), so keep very deep traces out of public APIs.
+
+use std::string::ToString;
+
+use tonic::metadata::MetadataValue;
+use tonic::{Code, Status};
+
+use crate::{At, AtTrace, AtTraceDetached};
+
+/// The binary metadata key that carries the encoded trace.
+pub const AT_TRACE_METADATA_KEY: &str = "whereat-trace-bin";
+
+impl<E: core::fmt::Display> At<E> {
+    /// Convert into a [`tonic::Status`] with `code`, carrying the trace in metadata.
+    ///
+    /// An error without frames gets no metadata entry.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, AtTraceDetached};
+    ///
+    /// #[derive(Debug)]
+    /// struct NotFound;
+    ///
+    /// impl core::fmt::Display for NotFound {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         f.write_str("user not found")
+    ///     }
+    /// }
+    ///
+    /// let status = at(NotFound).at_str("looking up user").into_status(tonic::Code::NotFound);
+    /// assert_eq!(status.code(), tonic::Code::NotFound);
+    /// assert_eq!(status.message(), "user not found");
+    ///
+    /// // On the client
+    /// let remote = AtTraceDetached::from_status(&status).unwrap();
+    /// assert!(remote.to_string().contains("╰─ looking up user"));
+    /// ```
+    pub fn into_status(self, code: Code) -> Status {
+        let mut status = Status::new(code, self.error().to_string());
+        if let Some(trace) = self.trace_ref().filter(|t| !t.is_empty()) {
+            let value = MetadataValue::from_bytes(&trace.encode());
+            status
+                .metadata_mut()
+                .insert_bin(AT_TRACE_METADATA_KEY, value);
+        }
+        status
+    }
+}
+
+/// Converts with [`Code::Internal`] and the error's `Display` output only.
+///
+/// The trace stays on the server; use [`At::into_status()`] to send it.
+impl<E: core::fmt::Display> From<At<E>> for Status {
+    fn from(err: At<E>) -> Self {
+        Status::new(Code::Internal, err.error().to_string())
+    }
+}
+
+impl AtTraceDetached {
+    /// The trace a server attached with [`At::into_status()`].
+    ///
+    /// Returns `None` if the status has no trace entry or it can't be decoded
+    /// (e.g. it was sent by a newer version of the format).
+    pub fn from_status(status: &Status) -> Option<Self> {
+        let value = status.metadata().get_bin(AT_TRACE_METADATA_KEY)?;
+        let bytes = value.to_bytes().ok()?;
+        AtTrace::decode(&bytes).ok()
+    }
+}
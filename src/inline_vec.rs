@@ -39,6 +39,15 @@ mod backend {
             self.0.is_empty()
         }
 
+        /// Bytes allocated on the heap after spilling out of the inline slots.
+        #[inline]
+        pub fn heap_bytes(&self) -> usize {
+            match &self.0 {
+                TinyVec::Heap(v) => v.capacity() * core::mem::size_of::<T>(),
+                TinyVec::Inline(_) => 0,
+            }
+        }
+
         #[inline]
         pub fn try_push(&mut self, value: T) -> bool {
             self.0.push(value);
@@ -61,7 +70,6 @@ mod backend {
             self.0.remove(index)
         }
 
-        #[allow(dead_code)] // Part of complete API
         #[inline]
         pub fn insert(&mut self, index: usize, value: T) {
             self.0.insert(index, value);
@@ -123,6 +131,16 @@ mod backend {
             self.0.is_empty()
         }
 
+        /// Bytes allocated on the heap after spilling out of the inline slots.
+        #[inline]
+        pub fn heap_bytes(&self) -> usize {
+            if self.0.spilled() {
+                self.0.capacity() * core::mem::size_of::<T>()
+            } else {
+                0
+            }
+        }
+
         #[inline]
         pub fn try_push(&mut self, value: T) -> bool {
             self.0.push(value);
@@ -145,7 +163,6 @@ mod backend {
             self.0.remove(index)
         }
 
-        #[allow(dead_code)] // Part of complete API
         #[inline]
         pub fn insert(&mut self, index: usize, value: T) {
             self.0.insert(index, value);
@@ -216,6 +233,12 @@ mod backend {
             self.len == 0
         }
 
+        /// Bytes allocated on the heap for slots beyond the inline ones.
+        #[inline]
+        pub fn heap_bytes(&self) -> usize {
+            self.heap.capacity() * core::mem::size_of::<T>()
+        }
+
         #[inline]
         pub fn try_push(&mut self, value: T) -> bool {
             let idx = self.len as usize;
@@ -281,16 +304,18 @@ mod backend {
             result
         }
 
-        #[allow(dead_code)] // Part of complete API
         #[inline]
         pub fn insert(&mut self, index: usize, value: T) {
             if index > self.len as usize {
                 panic!("index out of bounds");
             }
-            if self.len as usize >= N {
-                self.heap.reserve(1);
+            if index >= N {
+                // Past the inline slots: the inline elements stay put
+                self.heap.insert(index - N, value);
+                self.len += 1;
+                return;
             }
-            if self.len as usize >= N && N > 0 {
+            if self.len as usize >= N {
                 if let Some(last) = self.inline[N - 1].take() {
                     self.heap.insert(0, last);
                 }
@@ -298,11 +323,7 @@ mod backend {
             for i in (index + 1..N).rev() {
                 self.inline[i] = self.inline[i - 1].take();
             }
-            if index < N {
-                self.inline[index] = Some(value);
-            } else {
-                self.heap.insert(index - N, value);
-            }
+            self.inline[index] = Some(value);
             self.len += 1;
         }
 
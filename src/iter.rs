@@ -0,0 +1,141 @@
+//! Tracing adapters for iterators of results.
+//!
+//! [`IteratorAtExt`] traces every `Err` item of an
+//! `Iterator<Item = Result<T, At<E>>>` at the adapter's call site, instead of
+//! a `.map(|r| r.at())` per pipeline step.
+
+use alloc::borrow::Cow;
+use core::iter::FusedIterator;
+use core::panic::Location;
+
+use crate::At;
+use crate::context::AtContext;
+
+/// Extension trait for iterators of `Result<T, At<E>>`.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, At, IteratorAtExt};
+///
+/// #[derive(Debug)]
+/// struct ParseError;
+///
+/// fn parse(line: &str) -> Result<u32, At<ParseError>> {
+///     line.parse().map_err(|_| at(ParseError))
+/// }
+///
+/// let results: Vec<_> = ["1", "x", "3"]
+///     .into_iter()
+///     .map(parse)
+///     .at_str_errors("parsing input") // applied to every Err item
+///     .collect();
+///
+/// let err = results[1].as_ref().unwrap_err();
+/// assert_eq!(err.contexts().next().unwrap().as_text(), Some("parsing input"));
+/// ```
+pub trait IteratorAtExt<T, E>: Iterator<Item = Result<T, At<E>>> + Sized {
+    /// Add the caller's location as a new frame to every error item.
+    #[track_caller]
+    fn at_errors(self) -> AtErrors<Self>;
+
+    /// Add a static string context to the last frame of every error item.
+    #[track_caller]
+    fn at_str_errors(self, msg: &'static str) -> AtErrors<Self>;
+}
+
+impl<I, T, E> IteratorAtExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, At<E>>>,
+{
+    #[track_caller]
+    #[inline]
+    fn at_errors(self) -> AtErrors<Self> {
+        AtErrors::new(self, ErrorAttach::Frame)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn at_str_errors(self, msg: &'static str) -> AtErrors<Self> {
+        AtErrors::new(self, ErrorAttach::Str(msg))
+    }
+}
+
+/// What an error adapter adds to each error item.
+#[derive(Clone, Copy)]
+pub(crate) enum ErrorAttach {
+    Frame,
+    Str(&'static str),
+}
+
+impl ErrorAttach {
+    #[inline]
+    pub(crate) fn apply<E>(self, error: At<E>, location: &'static Location<'static>) -> At<E> {
+        match self {
+            ErrorAttach::Frame => error.at_location(location),
+            ErrorAttach::Str(msg) => {
+                error.at_context_location(location, || Some(AtContext::Text(Cow::Borrowed(msg))))
+            }
+        }
+    }
+}
+
+/// Iterator returned by the [`IteratorAtExt`] methods.
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct AtErrors<I> {
+    iter: I,
+    location: &'static Location<'static>,
+    attach: ErrorAttach,
+}
+
+impl<I> AtErrors<I> {
+    #[track_caller]
+    #[inline]
+    fn new(iter: I, attach: ErrorAttach) -> Self {
+        AtErrors {
+            iter,
+            location: Location::caller(),
+            attach,
+        }
+    }
+}
+
+impl<I, T, E> Iterator for AtErrors<I>
+where
+    I: Iterator<Item = Result<T, At<E>>>,
+{
+    type Item = Result<T, At<E>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(
+            self.iter
+                .next()?
+                .map_err(|e| self.attach.apply(e, self.location)),
+        )
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, T, E> DoubleEndedIterator for AtErrors<I>
+where
+    I: DoubleEndedIterator<Item = Result<T, At<E>>>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some(
+            self.iter
+                .next_back()?
+                .map_err(|e| self.attach.apply(e, self.location)),
+        )
+    }
+}
+
+impl<I, T, E> ExactSizeIterator for AtErrors<I> where I: ExactSizeIterator<Item = Result<T, At<E>>> {}
+
+impl<I, T, E> FusedIterator for AtErrors<I> where I: FusedIterator<Item = Result<T, At<E>>> {}
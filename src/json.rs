@@ -0,0 +1,191 @@
+//! JSON output without serde.
+//!
+//! [`At::to_json()`] writes a trace as one JSON object for log aggregators,
+//! needing only `E: Display`. The schema is documented on
+//! [`At::display_json()`] and is stable: members are only ever added, and
+//! `"version"` is bumped if one changes meaning.
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write as _};
+
+use crate::at::build_link_base;
+use crate::crate_info::fill_location_link;
+use crate::{At, AtContextKind, AtContextRef, AtFrame};
+
+/// Version of the JSON schema written by [`At::to_json()`].
+const SCHEMA_VERSION: u32 = 1;
+
+impl<E: fmt::Display> At<E> {
+    /// Serialize the error and its trace as a compact JSON object.
+    ///
+    /// Needs no serde derive on `E`: the error is written with its `Display`
+    /// output. See [`display_json()`](Self::display_json) for the schema.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct NotFound(u64);
+    ///
+    /// impl core::fmt::Display for NotFound {
+    ///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         write!(f, "user {} not found", self.0)
+    ///     }
+    /// }
+    ///
+    /// let err: At<NotFound> = at(NotFound(42)).at_str("looking up \"user\"");
+    /// let json = err.to_json();
+    /// assert!(json.starts_with(r#"{"version":1,"error":"user 42 not found","#));
+    /// assert!(json.contains(r#""contexts":[{"kind":"text","value":"looking up \"user\""}]"#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        self.display_json().to_string()
+    }
+
+    /// Like [`to_json()`](Self::to_json), but writes into a formatter.
+    ///
+    /// The object is written as follows (`"error_id"` only with the
+    /// `error-id` feature, `"correlation_id"` only after
+    /// [`at_correlation()`](At::at_correlation), `"suppressed"` only on a
+    /// [rate-limited](crate::set_rate_limit) trace, `"crate"` and `"link"`
+    /// only when known):
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "error": "user 42 not found",
+    ///   "error_id": "01HV4B7Q2M3XZ",
+    ///   "correlation_id": "req-7f3a",
+    ///   "suppressed": 3,
+    ///   "frames": [
+    ///     {
+    ///       "file": "src/db.rs", "line": 10, "column": 5,
+    ///       "crate": "myapp", "link": "https://github.com/you/myapp/blob/a1b2c3d/src/db.rs#L10C5",
+    ///       "contexts": [
+    ///         { "kind": "text", "value": "looking up user" },
+    ///         { "kind": "key_value", "key": "user_id", "value": "42" },
+    ///         { "kind": "bytes", "label": "header", "len": 5, "value": "R0VUIC8=" }
+    ///       ]
+    ///     },
+    ///     null
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Skipped-frames markers are `null`. Contexts are in attachment order,
+    /// each with a `"kind"` (see [`AtContextKind::name()`]) and a `"value"`,
+    /// its `Display` text. Key-value contexts add `"key"`, and `"value"` holds
+    /// the value alone; byte contexts add `"label"` and the original `"len"`,
+    /// and `"value"` holds the copied bytes as base64. Crate boundaries appear
+    /// as the frame's `"crate"` rather than as contexts.
+    pub fn display_json(&self) -> impl fmt::Display + '_ {
+        JsonDisplay(self)
+    }
+}
+
+struct JsonDisplay<'a, E>(&'a At<E>);
+
+impl<E: fmt::Display> fmt::Display for JsonDisplay<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"version\":{},\"error\":", SCHEMA_VERSION)?;
+        write_json_str(f, &self.0.error().to_string())?;
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.0.error_id() {
+            write!(f, ",\"error_id\":\"{}\"", id)?;
+        }
+        if let Some(id) = self.0.correlation_id() {
+            f.write_str(",\"correlation_id\":")?;
+            write_json_str(f, id)?;
+        }
+        if let Some(n) = self.0.trace_ref().and_then(|t| t.suppressed_count()) {
+            write!(f, ",\"suppressed\":{}", n)?;
+        }
+        f.write_str(",\"frames\":[")?;
+        for (i, frame) in self.0.frames().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            write_frame(f, &frame)?;
+        }
+        f.write_str("]}")
+    }
+}
+
+fn write_frame(f: &mut fmt::Formatter<'_>, frame: &AtFrame<'_>) -> fmt::Result {
+    let Some(loc) = frame.location() else {
+        return f.write_str("null");
+    };
+    f.write_str("{\"file\":")?;
+    write_json_str(f, loc.file())?;
+    write!(f, ",\"line\":{},\"column\":{}", loc.line(), loc.column())?;
+    if let Some(info) = frame.crate_info() {
+        f.write_str(",\"crate\":")?;
+        write_json_str(f, info.name())?;
+        if let Some(base) = build_link_base(info) {
+            f.write_str(",\"link\":")?;
+            write_json_str(f, &fill_location_link(&base, loc))?;
+        }
+    }
+    f.write_str(",\"contexts\":[")?;
+    let contexts = frame
+        .contexts()
+        .filter(|c| c.kind() != AtContextKind::Crate);
+    for (i, ctx) in contexts.enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        write_context(f, &ctx)?;
+    }
+    f.write_str("]}")
+}
+
+fn write_context(f: &mut fmt::Formatter<'_>, ctx: &AtContextRef<'_>) -> fmt::Result {
+    write!(f, "{{\"kind\":\"{}\",", ctx.kind().name())?;
+    if let Some((key, value)) = ctx.as_kv() {
+        f.write_str("\"key\":")?;
+        write_json_str(f, key)?;
+        f.write_str(",\"value\":")?;
+        write_json_str(f, value)?;
+    } else if let Some(bytes) = ctx.as_bytes() {
+        f.write_str("\"label\":")?;
+        write_json_str(f, bytes.label())?;
+        write!(
+            f,
+            ",\"len\":{},\"value\":\"{}\"",
+            bytes.len(),
+            bytes.to_base64()
+        )?;
+    } else {
+        f.write_str("\"value\":")?;
+        write_json_str(f, &ctx.to_string())?;
+    }
+    f.write_char('}')
+}
+
+/// Write `s` as a quoted JSON string.
+fn write_json_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let escaped = match c {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            c if c < ' ' => "",
+            _ => continue,
+        };
+        f.write_str(&s[start..i])?;
+        if escaped.is_empty() {
+            write!(f, "\\u{:04x}", c as u32)?;
+        } else {
+            f.write_str(escaped)?;
+        }
+        start = i + c.len_utf8();
+    }
+    f.write_str(&s[start..])?;
+    f.write_char('"')
+}
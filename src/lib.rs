@@ -8,7 +8,7 @@
 //!       ╰─ user_id = 42
 //!    at src/api.rs:89:5
 //!       ╰─ in handle_request
-//!    at myapp @ https://github.com/you/myapp/blob/a1b2c3d/src/main.rs#L23
+//!    at myapp @ https://github.com/you/myapp/blob/a1b2c3d/src/main.rs#L23C5
 //! ```
 //!
 //! ## Try It Now
@@ -73,6 +73,9 @@
 //! | [`at(err)`](at()) | ❌ None | Prototyping — no setup needed |
 //! | [`at!(err)`](at!) | ✅ GitHub links | **Production** — requires [`define_at_crate_info!()`](define_at_crate_info) |
 //! | [`err.start_at()`](ErrorAtExt::start_at) | ❌ None | Chaining on `Error` trait types |
+//! | [`opt.ok_or_at(err)`](OptionAtExt::ok_or_at) | ❌ None | Turning `None` into a traced error |
+//! | [`err_at!(err, key = v, "msg")`](err_at!) | ❌ None | Error, message and `key=value` fields in one expression |
+//! | [`at_bail!(err, ...)`](at_bail!) / [`at_ensure!(cond, err, ...)`](at_ensure!) | ❌ None | Returning early, like `anyhow::bail!` / `ensure!` |
 //!
 //! Start with `at()` to try things out. Upgrade to `at!()` before shipping — you'll want
 //! those clickable links when debugging production issues.
@@ -93,9 +96,18 @@
 //! |--------|--------|
 //! | [`.at_str("msg")`](ResultAtExt::at_str) | Static string (zero-cost) |
 //! | [`.at_string(\|\| format!(...))`](ResultAtExt::at_string) | Dynamic string (lazy) |
+//! | [`at_fmt!(result, "...", args)`](at_fmt!) | Same as above, without the closure |
 //! | [`.at_data(\|\| value)`](ResultAtExt::at_data) | Typed via Display (lazy) |
 //! | [`.at_debug(\|\| value)`](ResultAtExt::at_debug) | Typed via Debug (lazy) |
+//! | [`.at_secret(\|\| value)`](ResultAtExt::at_secret) | Typed, printed as `[redacted]` (lazy) |
+//! | [`.at_expected_actual(exp, act)`](ResultAtExt::at_expected_actual) | Aligned expected/actual pair |
+//! | [`.at_bytes(label, &[u8])`](ResultAtExt::at_bytes) | Hex dump of raw bytes (bounded copy) |
 //! | [`.at_error(source_err)`](ResultAtExt::at_error) | Attach a source error |
+//! | [`.at_correlation(id)`](ResultAtExt::at_correlation) | Attach a request/correlation id |
+//! | [`.at_kv("key", value)`](ResultAtExt::at_kv) | Attach a `key=value` pair |
+//! | [`.at_help("msg")`](ResultAtExt::at_help) | Attach a remediation hint (shown under `help:`) |
+//! | [`.at_link("label", url)`](ResultAtExt::at_link) | Attach a "see also" link (runbook, dashboard) |
+//! | [`.at_note("msg")`](ResultAtExt::at_note) / [`.at_warn("msg")`](ResultAtExt::at_warn) | Leveled context, filterable via [`AtTraceFormat`] |
 //!
 //! **Key distinction**: `.at()` creates a NEW frame. `.at_str()` and friends add to the LAST frame.
 //!
@@ -170,27 +182,119 @@
 #![deny(unsafe_code)]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod at;
+mod build_info;
+mod config;
 mod context;
 mod crate_info;
+mod detached;
+mod dyn_error;
+#[cfg(feature = "std")]
+mod env_report;
+#[cfg(feature = "error-id")]
+mod error_id;
+#[cfg(feature = "tracing")]
+mod event;
+#[cfg(feature = "std")]
+mod explain;
 mod ext;
 #[cfg(any(feature = "_termcolor", feature = "_html"))]
 mod format;
+#[cfg(feature = "futures")]
+mod futures;
+#[cfg(feature = "async-graphql")]
+mod graphql;
+mod group;
+#[cfg(feature = "tonic")]
+mod grpc;
 mod inline_vec;
+mod iter;
+mod json;
+mod localize;
+#[cfg(feature = "std")]
+mod panic;
+#[cfg(feature = "persist")]
+mod persist;
 pub mod prelude;
+mod problem;
+#[cfg(feature = "eyre")]
+mod report;
+#[cfg(feature = "retry")]
+mod retry;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "tracing")]
+mod span;
+mod stats;
+#[cfg(feature = "tokio")]
+mod task;
+#[cfg(feature = "std")]
+mod termination;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod trace;
+mod trace_format;
 
-pub use at::At;
-pub use context::AtContextRef;
+pub use at::{At, AtDisplayError, AtResult};
+pub use build_info::AtBuildInfo;
+pub use config::{
+    AtTracePolicy, configure, is_enabled, sample_rate, set_enabled, set_sample_rate, trace_policy,
+};
+#[cfg(feature = "std")]
+pub use config::{clear_rate_limit, clear_skip_files, set_rate_limit, set_skip_files};
+pub use context::{
+    AtBytes, AtContextKind, AtContextOwned, AtContextRef, AtCorrelationId, AtExpectedActual,
+    AtLevel,
+};
 pub use crate_info::{
     AtCrateInfo, AtCrateInfoBuilder, BITBUCKET_LINK_FORMAT, GITEA_LINK_FORMAT, GITHUB_LINK_FORMAT,
-    GITLAB_LINK_FORMAT,
+    GITLAB_LINK_FORMAT, SOURCEHUT_LINK_FORMAT,
+};
+pub use detached::{AtFrameDetached, AtTraceDetached};
+pub use dyn_error::AtErrorDyn;
+#[cfg(feature = "std")]
+pub use env_report::{AtEnvReport, env_report};
+#[cfg(feature = "error-id")]
+pub use error_id::AtErrorId;
+#[cfg(feature = "std")]
+pub use explain::{AtExplanations, explain, register_explanations};
+pub use ext::{ErrorAtExt, OptionAtExt, ResultAtExt, ResultAtTraceableExt};
+#[cfg(feature = "futures")]
+pub use futures::{AtFuture, AtStreamErrors, FutureAtExt, StreamAtExt};
+#[doc(hidden)]
+pub use group::__TryJoinSlot;
+pub use group::AtGroup;
+#[cfg(feature = "tonic")]
+pub use grpc::AT_TRACE_METADATA_KEY;
+pub use iter::{AtErrors, IteratorAtExt};
+pub use localize::AtMessage;
+#[cfg(feature = "std")]
+pub use panic::install_panic_hook;
+#[cfg(feature = "persist")]
+pub use persist::AtDecodeError;
+pub use problem::AtProblem;
+#[cfg(feature = "eyre")]
+pub use report::AtEyreHook;
+#[cfg(feature = "retry")]
+pub use retry::retry_at;
+#[cfg(feature = "tracing")]
+pub use span::{
+    AtSpanFieldsLayer, record_span_fields, record_span_ids, set_record_span_fields,
+    set_record_span_ids,
 };
-pub use ext::{ErrorAtExt, ResultAtExt, ResultAtTraceableExt};
+pub use stats::AtStats;
+#[cfg(feature = "tokio")]
+pub use task::{AtJoinFuture, JoinHandleAtExt};
+#[cfg(feature = "std")]
+pub use termination::{AtMain, report};
 pub use trace::{
-    AT_MAX_CONTEXTS, AT_MAX_FRAMES, AtFrame, AtFrameOwned, AtTrace, AtTraceBoxed, AtTraceable,
+    AT_KEEP_OLDEST_FRAMES, AT_MAX_CONTEXT_BYTES, AT_MAX_CONTEXTS, AT_MAX_FRAMES, AtFrame,
+    AtFrameOwned, AtMergePolicy, AtOrder, AtTrace, AtTraceBoxed, AtTraceStats, AtTraceable,
 };
+pub use trace_format::{AtLocationStyle, AtTraceFormat};
 
 // ============================================================================
 // Crate-level error tracking info (for whereat's own at!() / at_crate!() usage)
@@ -222,6 +326,12 @@ pub fn at_crate_info() -> &'static AtCrateInfo {
     &__AT_CRATE_INFO
 }
 
+/// Used by [`at_fmt!`]: callers may not have `alloc` in scope.
+#[doc(hidden)]
+pub fn __format(args: core::fmt::Arguments<'_>) -> alloc::string::String {
+    alloc::fmt::format(args)
+}
+
 /// Internal macro for commit detection chain.
 #[doc(hidden)]
 #[macro_export]
@@ -424,6 +534,153 @@ macro_rules! at {
     }};
 }
 
+/// Create an `At<E>` with a message and `key=value` contexts in one expression.
+///
+/// Captures the caller's location like [`at()`], then attaches the optional
+/// message followed by each `key = value` pair (values use `Display`). The
+/// message takes `format!` syntax; a plain literal is stored without allocating.
+/// Does not need `define_at_crate_info!()`; chain `.set_crate_info()` if wanted.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{err_at, At};
+///
+/// #[derive(Debug)]
+/// enum MyError { NotFound }
+///
+/// fn find_profile(user_id: u64) -> Result<String, At<MyError>> {
+///     Err(err_at!(MyError::NotFound, user_id = user_id, "looking up profile"))
+/// }
+///
+/// let err = find_profile(42).unwrap_err();
+/// let output = format!("{:?}", err);
+/// assert!(output.contains("╰─ looking up profile\n"));
+/// assert!(output.contains("╰─ user_id=42\n"));
+///
+/// // Message only, with format arguments
+/// let table = "profiles";
+/// let err = err_at!(MyError::NotFound, "missing from {table}");
+/// assert!(format!("{:?}", err).contains("missing from profiles"));
+/// ```
+#[macro_export]
+macro_rules! err_at {
+    ($err:expr $(, $($rest:tt)*)?) => {
+        $crate::__whereat_err_at!([$crate::at($err)] [] $($($rest)*)?)
+    };
+}
+
+/// Internal muncher for [`err_at!`]: collects `key = value` pairs, then the message.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __whereat_err_at {
+    ([$at:expr] [$($kv:tt)*] $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__whereat_err_at!([$at] [$($kv)* ($key, $value)] $($($rest)*)?)
+    };
+    ([$at:expr] [$(($key:ident, $value:expr))*]) => {
+        $at$(.at_kv(stringify!($key), $value))*
+    };
+    ([$at:expr] [$(($key:ident, $value:expr))*] $($msg:tt)+) => {
+        $at.__at_fmt(format_args!($($msg)+))
+            $(.at_kv(stringify!($key), $value))*
+    };
+}
+
+/// Add a formatted message to a `Result`'s error, formatting only on `Err`.
+///
+/// `at_fmt!(result, "fmt", args...)` is
+/// `result.at_string(|| format!("fmt", args...))`: the format string is
+/// checked at compile time, inline arguments like `{id}` work, and nothing
+/// is formatted on the `Ok` path. Works on anything with an `at_string()`
+/// method: `Result<T, At<E>>`, `Result<T, E: AtTraceable>` and `At<E>`.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, at_fmt, At, ResultAtExt};
+///
+/// #[derive(Debug)]
+/// enum DbError { NotFound }
+///
+/// fn query(id: u64) -> Result<String, At<DbError>> {
+///     Err(at(DbError::NotFound))
+/// }
+///
+/// fn load_user(id: u64, table: &str) -> Result<String, At<DbError>> {
+///     at_fmt!(query(id), "loading user {id} from {}", table)
+/// }
+///
+/// let err = load_user(7, "users").unwrap_err();
+/// assert!(format!("{:?}", err).contains("╰─ loading user 7 from users\n"));
+/// ```
+#[macro_export]
+macro_rules! at_fmt {
+    ($result:expr, $($fmt:tt)+) => {
+        $result.at_string(|| $crate::__format(format_args!($($fmt)+)))
+    };
+}
+
+/// Return early with an `Err(At<E>)` built by [`err_at!`].
+///
+/// `at_bail!(err, ...)` is `return Err(err_at!(err, ...))`: the trace starts at
+/// the `at_bail!` line, with the same optional `key = value` fields and message.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at_bail, At};
+///
+/// #[derive(Debug)]
+/// enum ConfigError { Missing }
+///
+/// fn port(raw: Option<&str>) -> Result<u16, At<ConfigError>> {
+///     let Some(raw) = raw else {
+///         at_bail!(ConfigError::Missing, "reading {}", "PORT");
+///     };
+///     Ok(raw.parse().unwrap_or(80))
+/// }
+///
+/// let err = port(None).unwrap_err();
+/// assert!(format!("{:?}", err).contains("╰─ reading PORT\n"));
+/// ```
+#[macro_export]
+macro_rules! at_bail {
+    ($($args:tt)+) => {
+        return ::core::result::Result::Err($crate::err_at!($($args)+))
+    };
+}
+
+/// Return early with an `Err(At<E>)` unless a condition holds.
+///
+/// `at_ensure!(cond, err, ...)` is `if !cond { at_bail!(err, ...) }`. The
+/// error expression is only evaluated when the condition fails.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at_ensure, At};
+///
+/// #[derive(Debug)]
+/// enum UploadError { TooLarge }
+///
+/// fn check(len: usize) -> Result<(), At<UploadError>> {
+///     at_ensure!(len <= 1024, UploadError::TooLarge, len = len, "checking upload");
+///     Ok(())
+/// }
+///
+/// assert!(check(10).is_ok());
+/// let err = check(4096).unwrap_err();
+/// assert!(format!("{:?}", err).contains("╰─ len=4096\n"));
+/// ```
+#[macro_export]
+macro_rules! at_ensure {
+    ($cond:expr, $($args:tt)+) => {
+        if !$cond {
+            $crate::at_bail!($($args)+);
+        }
+    };
+}
+
 /// Add crate boundary marker to a Result with an `At<E>` error.
 ///
 /// Requires `define_at_crate_info!()` or a custom `at_crate_info()` function.
@@ -451,6 +708,83 @@ macro_rules! at_crate {
     ($result:expr) => {{ $crate::ResultAtExt::at_crate($result, crate::at_crate_info()) }};
 }
 
+/// Implement [`AtTraceable`] for a type that has a trace field.
+///
+/// Saves writing out `trace_mut()`, `trace()` and `fmt_message()` by hand.
+/// The field is either an [`AtTrace`] (`trace:`) or an [`AtTraceBoxed`]
+/// (`trace_boxed:`). The message defaults to the type's `Display`; pass
+/// `message: self.<expr>` to format a field or method result instead.
+///
+/// Orphan rules still apply: the type must be defined in your crate, so wrap
+/// foreign errors in a newtype first.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{AtTrace, AtTraceBoxed, AtTraceable, impl_at_traceable};
+///
+/// struct VendorError {
+///     msg: String,
+///     trace: AtTrace,
+/// }
+/// impl_at_traceable!(VendorError, trace: trace, message: self.msg);
+///
+/// struct LazyError {
+///     code: u16,
+///     trace: AtTraceBoxed,
+/// }
+/// impl std::fmt::Display for LazyError {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "code {}", self.code)
+///     }
+/// }
+/// impl_at_traceable!(LazyError, trace_boxed: trace);
+///
+/// let err = VendorError { msg: "quota exceeded".into(), trace: AtTrace::new() }.at();
+/// assert_eq!(err.trace().unwrap().frame_count(), 1);
+/// assert!(err.full_trace().to_string().starts_with("quota exceeded\n"));
+///
+/// let err = LazyError { code: 7, trace: AtTraceBoxed::new() }.at_str("retrying");
+/// assert!(err.full_trace().to_string().starts_with("code 7\n"));
+/// ```
+#[macro_export]
+macro_rules! impl_at_traceable {
+    ($ty:ty, trace: $field:ident $(,)?) => {
+        $crate::impl_at_traceable!(@impl $ty, inline $field, self, *self);
+    };
+    ($ty:ty, trace: $field:ident, message: $self_:ident . $($message:tt)+) => {
+        $crate::impl_at_traceable!(@impl $ty, inline $field, $self_, $self_.$($message)+);
+    };
+    ($ty:ty, trace_boxed: $field:ident $(,)?) => {
+        $crate::impl_at_traceable!(@impl $ty, boxed $field, self, *self);
+    };
+    ($ty:ty, trace_boxed: $field:ident, message: $self_:ident . $($message:tt)+) => {
+        $crate::impl_at_traceable!(@impl $ty, boxed $field, $self_, $self_.$($message)+);
+    };
+
+    // `self` is passed through so the message expression (written at the call
+    // site) and the `&self` receiver share the same hygiene context.
+    (@impl $ty:ty, $storage:ident $field:ident, $self_:ident, $message:expr) => {
+        impl $crate::AtTraceable for $ty {
+            fn trace_mut(&mut self) -> &mut $crate::AtTrace {
+                $crate::impl_at_traceable!(@trace_mut $storage self.$field)
+            }
+
+            fn trace(&self) -> ::core::option::Option<&$crate::AtTrace> {
+                $crate::impl_at_traceable!(@trace $storage self.$field)
+            }
+
+            fn fmt_message(&$self_, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&$message, f)
+            }
+        }
+    };
+    (@trace_mut inline $place:expr) => { &mut $place };
+    (@trace_mut boxed $place:expr) => { $place.get_or_insert_mut() };
+    (@trace inline $place:expr) => { ::core::option::Option::Some(&$place) };
+    (@trace boxed $place:expr) => { $place.as_ref() };
+}
+
 /// Wrap any value in `At<E>` and capture the caller's location.
 ///
 /// This function works with any type, not just `Error` types.
@@ -475,6 +809,36 @@ pub fn at<E>(err: E) -> At<E> {
     At::wrap(err).at()
 }
 
+/// Convert a traced error with `From`, moving its trace and recording the
+/// caller's location as a new frame.
+///
+/// The free-function form of [`At::map_into()`]. Call it directly: passed by
+/// name to `map_err`, `#[track_caller]` does not apply and the frame would
+/// point into `core`. Use [`ResultAtExt::map_err_into()`] for `Result`s.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, convert_at, At};
+///
+/// #[derive(Debug)]
+/// struct ParseError;
+/// #[derive(Debug)]
+/// struct ConfigError(ParseError);
+///
+/// impl From<ParseError> for ConfigError {
+///     fn from(e: ParseError) -> Self { ConfigError(e) }
+/// }
+///
+/// let err: At<ConfigError> = convert_at(at(ParseError));
+/// assert_eq!(err.frame_count(), 2);
+/// ```
+#[track_caller]
+#[inline]
+pub fn convert_at<E, E2: From<E>>(err: At<E>) -> At<E2> {
+    err.map_into()
+}
+
 // Extension traits are in ext.rs
 
 #[cfg(test)]
@@ -0,0 +1,136 @@
+//! Message localization hook for user-facing output.
+//!
+//! whereat never translates anything itself. The `*_localized()` formatters
+//! hand the application an [`AtMessage`] (which variant failed, plus the
+//! default English message) and use whatever string it returns, e.g. from
+//! Fluent or gettext. Trace lines (locations, contexts) stay untranslated so
+//! logs remain greppable.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{At, AtTraceFormat};
+
+/// The message being localized, passed to a localizer closure.
+#[non_exhaustive]
+pub struct AtMessage<'a> {
+    variant: &'a str,
+    default_message: &'a str,
+}
+
+impl<'a> AtMessage<'a> {
+    /// The error's variant (or type) name, taken from its `Debug` output.
+    ///
+    /// `NotFound` for `MyError::NotFound`, `InvalidInput` for
+    /// `MyError::InvalidInput(..)`, `ParseError` for `struct ParseError { .. }`.
+    /// Use it as the translation key.
+    #[inline]
+    pub fn variant(&self) -> &'a str {
+        self.variant
+    }
+
+    /// The untranslated message (the error's `Display` output).
+    #[inline]
+    pub fn default_message(&self) -> &'a str {
+        self.default_message
+    }
+}
+
+/// Leading identifier of a `Debug` string: `Variant`, `Variant(..)`, `Type { .. }`.
+pub(crate) fn variant_name(debug: &str) -> &str {
+    let end = debug
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(debug.len());
+    &debug[..end]
+}
+
+impl<E: fmt::Debug + fmt::Display> At<E> {
+    /// Format the error message through a localization hook.
+    ///
+    /// `localizer` receives the variant name and default message and returns
+    /// the translated text, or `None` to fall back to the default.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtMessage};
+    ///
+    /// #[derive(Debug)]
+    /// enum MyError { NotFound }
+    ///
+    /// impl std::fmt::Display for MyError {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "not found")
+    ///     }
+    /// }
+    ///
+    /// fn german(msg: &AtMessage<'_>) -> Option<String> {
+    ///     match msg.variant() {
+    ///         "NotFound" => Some("nicht gefunden".into()),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// let err = at(MyError::NotFound);
+    /// assert_eq!(err.localized(german).to_string(), "nicht gefunden");
+    /// assert!(err.full_trace_localized(german).to_string().starts_with("nicht gefunden\n"));
+    /// ```
+    #[inline]
+    pub fn localized<'a, F>(&'a self, localizer: F) -> impl fmt::Display + 'a
+    where
+        F: Fn(&AtMessage<'_>) -> Option<String> + 'a,
+    {
+        LocalizedDisplay {
+            at: self,
+            localizer,
+            full_trace: None,
+        }
+    }
+
+    /// Like [`full_trace()`](Self::full_trace), with the message localized.
+    ///
+    /// Only the first line is translated; locations and contexts are unchanged.
+    #[inline]
+    pub fn full_trace_localized<'a, F>(&'a self, localizer: F) -> impl fmt::Display + 'a
+    where
+        F: Fn(&AtMessage<'_>) -> Option<String> + 'a,
+    {
+        LocalizedDisplay {
+            at: self,
+            localizer,
+            full_trace: Some(AtTraceFormat::DEFAULT),
+        }
+    }
+}
+
+/// Formatter behind `localized()` and `full_trace_localized()`.
+struct LocalizedDisplay<'a, E, F> {
+    at: &'a At<E>,
+    localizer: F,
+    /// `Some` to append the full trace after the message.
+    full_trace: Option<AtTraceFormat>,
+}
+
+impl<E, F> fmt::Display for LocalizedDisplay<'_, E, F>
+where
+    E: fmt::Debug + fmt::Display,
+    F: Fn(&AtMessage<'_>) -> Option<String>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let debug = alloc::format!("{:?}", self.at.error());
+        let default_message = alloc::format!("{}", self.at.error());
+        let message = AtMessage {
+            variant: variant_name(&debug),
+            default_message: &default_message,
+        };
+        match (self.localizer)(&message) {
+            Some(localized) => f.write_str(&localized)?,
+            None => f.write_str(&default_message)?,
+        }
+        if let Some(format) = &self.full_trace {
+            let trace = self.at.trace_ref();
+            crate::trace::write_traceable_body(f, trace, format, None, None, false)?;
+        }
+        Ok(())
+    }
+}
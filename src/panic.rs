@@ -0,0 +1,90 @@
+//! A panic hook that prints panics in the trace style (requires `std`).
+//!
+//! [`install_panic_hook()`] replaces the default hook so panics and traced
+//! errors share one layout in logs:
+//!
+//! ```text
+//! thread 'main' panicked: index out of bounds: the len is 3 but the index is 7
+//!     at src/main.rs:42:13
+//!        https://github.com/org/app/blob/a1b2c3d/src/main.rs#L42C13
+//! ```
+
+use core::fmt;
+use core::panic::Location;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::boxed::Box;
+use std::panic::PanicHookInfo;
+use std::string::String;
+
+use crate::AtCrateInfo;
+use crate::at::build_link_base;
+use crate::crate_info::fill_location_link;
+
+/// Install a panic hook that prints the panic location like a trace frame.
+///
+/// With `crate_info`, locations get a repository permalink built like the
+/// links of [`display_with_meta()`](crate::At::display_with_meta); pass
+/// `Some(crate::at_crate_info())` after `define_at_crate_info!()`. As with
+/// the default hook, a backtrace follows when `RUST_BACKTRACE` enables one.
+/// Replaces the current hook.
+///
+/// ## Example
+///
+/// ```rust
+/// whereat::define_at_crate_info!();
+///
+/// fn main() {
+///     whereat::install_panic_hook(Some(crate::at_crate_info()));
+///     # let _ = std::panic::take_hook();
+/// }
+/// ```
+pub fn install_panic_hook(crate_info: Option<&'static AtCrateInfo>) {
+    let link_base = crate_info.and_then(build_link_base);
+    std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        let thread = std::thread::current();
+        let report = PanicReport {
+            thread: thread.name().unwrap_or("<unnamed>"),
+            message: payload_str(info),
+            location: info.location(),
+            link_base: link_base.as_deref(),
+        };
+        std::eprintln!("{}", report);
+        let backtrace = Backtrace::capture();
+        if backtrace.status() == BacktraceStatus::Captured {
+            std::eprintln!("stack backtrace:\n{}", backtrace);
+        }
+    }));
+}
+
+/// The panic message, if the payload is a string.
+fn payload_str<'a>(info: &'a PanicHookInfo<'_>) -> &'a str {
+    let payload = info.payload();
+    match payload.downcast_ref::<&str>() {
+        Some(msg) => msg,
+        None => payload
+            .downcast_ref::<String>()
+            .map_or("Box<dyn Any>", |s| s),
+    }
+}
+
+/// The lines the hook writes for one panic.
+pub(crate) struct PanicReport<'a> {
+    pub(crate) thread: &'a str,
+    pub(crate) message: &'a str,
+    pub(crate) location: Option<&'a Location<'a>>,
+    /// Link template from [`build_link_base`], without the location filled in.
+    pub(crate) link_base: Option<&'a str>,
+}
+
+impl fmt::Display for PanicReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "thread '{}' panicked: {}", self.thread, self.message)?;
+        if let Some(loc) = self.location {
+            write!(f, "\n    at {}:{}:{}", loc.file(), loc.line(), loc.column())?;
+            if let Some(base) = self.link_base {
+                write!(f, "\n       {}", fill_location_link(base, loc))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+//! Binary encoding of traces for crash reports and IPC (the `persist` feature).
+//!
+//! [`AtTrace::encode()`] writes a trace's frames and context text into a
+//! compact byte format; [`AtTrace::decode()`] reads it back as an
+//! [`AtTraceDetached`], since the decoded locations are no longer
+//! `&'static Location` data. The format is versioned by its header:
+//!
+//! ```text
+//! header   b"WAT" + version (1)
+//! count    u32, number of frames
+//! frame    flags: u8 (bit 0: file, bit 1: function)
+//!          [file: str] line: u32, column: u32 [function: str]
+//!          contexts: u32, then one str each
+//! str      u32 byte length, then UTF-8
+//! ```
+//!
+//! Integers are little-endian. Anything after the last frame is an error.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{AtFrameDetached, AtTrace, AtTraceDetached};
+
+const MAGIC: &[u8; 3] = b"WAT";
+const VERSION: u8 = 1;
+
+const HAS_FILE: u8 = 1;
+const HAS_FUNCTION: u8 = 2;
+
+/// Why [`AtTrace::decode()`] rejected its input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AtDecodeError {
+    /// The input doesn't start with the trace header.
+    NotATrace,
+    /// Written by a newer version of the format.
+    UnsupportedVersion(u8),
+    /// The input ends in the middle of a frame.
+    Truncated,
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// Bytes are left over after the last frame.
+    TrailingBytes,
+}
+
+impl fmt::Display for AtDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotATrace => f.write_str("not an encoded trace"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported trace format version {}", v),
+            Self::Truncated => f.write_str("encoded trace is truncated"),
+            Self::InvalidUtf8 => f.write_str("encoded trace contains invalid UTF-8"),
+            Self::TrailingBytes => f.write_str("encoded trace is followed by extra bytes"),
+        }
+    }
+}
+
+impl core::error::Error for AtDecodeError {}
+
+impl AtTrace {
+    /// Encode frames and contexts for storage or another process.
+    ///
+    /// Contexts are kept as text, as in [`to_detached()`](Self::to_detached).
+    /// The format counts in `u32`: strings are cut to `u32::MAX` bytes (at a
+    /// character boundary), and only the first `u32::MAX` frames and contexts
+    /// per frame are written.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At, AtTrace};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err: At<MyError> = at(MyError).at_str("writing report");
+    /// let bytes = err.trace().unwrap().encode();
+    ///
+    /// let restored = AtTrace::decode(&bytes).unwrap();
+    /// assert_eq!(restored.frame_count(), 1);
+    /// assert!(restored.to_string().contains("╰─ writing report"));
+    /// ```
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_detached().encode()
+    }
+
+    /// Decode a trace written by [`encode()`](Self::encode).
+    pub fn decode(bytes: &[u8]) -> Result<AtTraceDetached, AtDecodeError> {
+        AtTraceDetached::decode(bytes)
+    }
+}
+
+impl AtTraceDetached {
+    /// Encode in the same format as [`AtTrace::encode()`].
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        let frames = put_count(&mut out, self.frame_count());
+        for frame in self.frames().take(frames) {
+            let mut flags = 0;
+            if frame.file().is_some() {
+                flags |= HAS_FILE;
+            }
+            if frame.function().is_some() {
+                flags |= HAS_FUNCTION;
+            }
+            out.push(flags);
+            if let Some(file) = frame.file() {
+                put_str(&mut out, file);
+            }
+            out.extend_from_slice(&frame.line().to_le_bytes());
+            out.extend_from_slice(&frame.column().to_le_bytes());
+            if let Some(function) = frame.function() {
+                put_str(&mut out, function);
+            }
+            let contexts = put_count(&mut out, frame.contexts().count());
+            for context in frame.contexts().take(contexts) {
+                put_str(&mut out, context);
+            }
+        }
+        out
+    }
+
+    /// Decode a trace written by [`encode()`](Self::encode) or [`AtTrace::encode()`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, AtDecodeError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+            return Err(AtDecodeError::NotATrace);
+        }
+        match reader.u8()? {
+            VERSION => {}
+            version => return Err(AtDecodeError::UnsupportedVersion(version)),
+        }
+        let count = reader.u32()?;
+        // Frames are pushed one by one: `count` is untrusted and not used to reserve
+        let mut trace = Self::new();
+        for _ in 0..count {
+            let flags = reader.u8()?;
+            let file = match flags & HAS_FILE {
+                0 => None,
+                _ => Some(reader.string()?),
+            };
+            let line = reader.u32()?;
+            let column = reader.u32()?;
+            let mut frame = match file {
+                Some(file) => AtFrameDetached::new(file, line, column),
+                None => AtFrameDetached::skipped(),
+            };
+            if flags & HAS_FUNCTION != 0 {
+                frame = frame.with_function(reader.string()?);
+            }
+            for _ in 0..reader.u32()? {
+                frame = frame.with_context(reader.string()?);
+            }
+            trace.push(frame);
+        }
+        if !reader.0.is_empty() {
+            return Err(AtDecodeError::TrailingBytes);
+        }
+        Ok(trace)
+    }
+}
+
+/// Write `n` capped to `u32::MAX`, returning how many items the caller must write.
+fn put_count(out: &mut Vec<u8>, n: usize) -> usize {
+    let n = u32::try_from(n).unwrap_or(u32::MAX);
+    out.extend_from_slice(&n.to_le_bytes());
+    n as usize
+}
+
+/// Write `s`, cut to `u32::MAX` bytes at a character boundary.
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    let mut len = u32::try_from(s.len()).map_or(u32::MAX as usize, |_| s.len());
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    put_count(out, len);
+    out.extend_from_slice(&s.as_bytes()[..len]);
+}
+
+/// Cursor over the encoded bytes.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], AtDecodeError> {
+        if self.0.len() < len {
+            return Err(AtDecodeError::Truncated);
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, AtDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, AtDecodeError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, AtDecodeError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| AtDecodeError::InvalidUtf8)
+    }
+}
@@ -1,6 +1,7 @@
 //! Convenient re-exports for common usage.
 //!
-//! This prelude includes the most commonly used types and traits for error tracing.
+//! This prelude includes the most commonly used types, traits and macros for
+//! error tracing, so one `use` line replaces the per-file trait imports.
 //!
 //! ## Usage
 //!
@@ -10,16 +11,20 @@
 //! #[derive(Debug)]
 //! struct MyError;
 //!
-//! fn inner() -> Result<(), At<MyError>> {
+//! fn inner() -> AtResult<(), MyError> {
 //!     Err(at(MyError))
 //! }
 //!
-//! fn outer() -> Result<(), At<MyError>> {
+//! fn outer() -> AtResult<(), MyError> {
 //!     inner().at()?;
 //!     Ok(())
 //! }
 //! ```
 
-pub use crate::At;
-pub use crate::ResultAtExt;
-pub use crate::at;
+#[cfg(feature = "tokio")]
+pub use crate::JoinHandleAtExt;
+pub use crate::{At, AtResult, AtTrace, AtTraceable};
+pub use crate::{ErrorAtExt, IteratorAtExt, OptionAtExt, ResultAtExt, ResultAtTraceableExt};
+#[cfg(feature = "futures")]
+pub use crate::{FutureAtExt, StreamAtExt};
+pub use crate::{at, at_crate};
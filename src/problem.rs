@@ -0,0 +1,166 @@
+//! RFC 9457 Problem Details for HTTP APIs.
+//!
+//! [`At::to_problem()`] builds an [`AtProblem`] from a traced error: the
+//! `title` is the error's `Display` output and two extension members carry
+//! the [fingerprint](At::fingerprint) and a sanitized trace (locations only,
+//! no context text, which may hold user data). The `type`, `status`, `detail`
+//! and `instance` members are set by the service. Nothing here depends on a
+//! web framework; with the `serde` feature, `AtProblem` serializes to the
+//! `application/problem+json` body:
+//!
+//! ```json
+//! {
+//!   "type": "https://example.com/probs/not-found",
+//!   "title": "user not found",
+//!   "status": 404,
+//!   "fingerprint": "9c1f0e2ab5d7c3e4",
+//!   "trace": ["src/db.rs:10:5", "src/api.rs:89:5"]
+//! }
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::At;
+use crate::trace::SkipMarker;
+
+/// An RFC 9457 Problem Details object built from a traced error.
+///
+/// Created by [`At::to_problem()`]. Absent members are omitted when
+/// serialized; `type` defaults to `"about:blank"` as the RFC specifies.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, At};
+///
+/// #[derive(Debug)]
+/// enum ApiError { NotFound }
+///
+/// impl core::fmt::Display for ApiError {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("user not found")
+///     }
+/// }
+///
+/// let err: At<ApiError> = at(ApiError::NotFound).at_str("SELECT * FROM users");
+/// let problem = err
+///     .to_problem()
+///     .with_type("https://example.com/probs/not-found")
+///     .with_status(404)
+///     .with_instance("/users/42");
+///
+/// assert_eq!(problem.title(), "user not found");
+/// assert_eq!(problem.status(), Some(404));
+/// assert_eq!(problem.trace().len(), 1);
+/// assert!(!problem.trace()[0].contains("SELECT")); // contexts never leak
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtProblem {
+    type_uri: Cow<'static, str>,
+    title: String,
+    status: Option<u16>,
+    detail: Option<String>,
+    instance: Option<String>,
+    fingerprint: u64,
+    trace: Vec<String>,
+}
+
+impl AtProblem {
+    /// The media type for serialized problem details.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// Set the `type` URI identifying the problem kind.
+    pub fn with_type(mut self, uri: impl Into<Cow<'static, str>>) -> Self {
+        self.type_uri = uri.into();
+        self
+    }
+
+    /// Set the HTTP `status` code.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Set the human-readable `detail` for this occurrence.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Set the `instance` URI identifying this occurrence.
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// The `type` URI, `"about:blank"` unless set.
+    pub fn type_uri(&self) -> &str {
+        &self.type_uri
+    }
+
+    /// The `title`: the error's `Display` output.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The HTTP `status` code, if set.
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// The `detail` member, if set.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// The `instance` member, if set.
+    pub fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    /// The error's [fingerprint](At::fingerprint), serialized as 16 hex digits.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The sanitized trace: `file:line:col` per frame, oldest first, with
+    /// `"[...]"` for skipped-frame markers.
+    pub fn trace(&self) -> &[String] {
+        &self.trace
+    }
+}
+
+impl<E: fmt::Display + fmt::Debug> At<E> {
+    /// Convert into an RFC 9457 Problem Details object.
+    ///
+    /// Fills `title`, the `fingerprint` extension and a `trace` extension of
+    /// frame locations; contexts are left out since they may contain user
+    /// data. Set `type`, `status`, `detail` and `instance` with the
+    /// [`AtProblem`] builder methods.
+    pub fn to_problem(&self) -> AtProblem {
+        AtProblem {
+            type_uri: Cow::Borrowed("about:blank"),
+            title: self.error().to_string(),
+            status: None,
+            detail: None,
+            instance: None,
+            fingerprint: self.fingerprint(),
+            trace: sanitized_trace(self),
+        }
+    }
+}
+
+/// Frame locations as `file:line:col`, oldest first, `"[...]"` for skip markers.
+///
+/// Contexts are left out: they may contain user data and must not reach clients.
+pub(crate) fn sanitized_trace<E>(err: &At<E>) -> Vec<String> {
+    err.frames()
+        .map(|frame| match frame.location() {
+            Some(loc) => alloc::format!("{}:{}:{}", loc.file(), loc.line(), loc.column()),
+            None => SkipMarker(frame.repeat_count()).to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,167 @@
+//! `eyre` integration (the `eyre` feature).
+//!
+//! [`AtEyreHook`] installs an [`eyre::EyreHandler`] that, when a report wraps
+//! a traced error, prints its frames and contexts where eyre's default
+//! handler would print a backtrace:
+//!
+//! ```text
+//! Error: loading profile
+//!
+//! Caused by:
+//!     user not found
+//!
+//! Trace:
+//!     at src/db.rs:10:5
+//!         SELECT * FROM users WHERE id = 7
+//!     at src/profile.rs:42:9
+//! ```
+//!
+//! A `dyn Error` can only be downcast to concrete types, so the hook is told
+//! which traced types to look for. The first one found in the report's
+//! error chain is rendered. Reports without one show where they were
+//! created, like eyre's default handler.
+
+use std::boxed::Box;
+use std::sync::Arc;
+use std::vec::Vec;
+
+use core::error::Error;
+use core::fmt;
+use core::panic::Location;
+
+use crate::trace::write_traceable_body;
+use crate::{AtErrorDyn, AtTraceFormat};
+
+/// Finds a registered traced type behind a `dyn Error`.
+type Downcast = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a dyn AtErrorDyn>;
+
+fn downcast<'a, T: AtErrorDyn + Error + 'static>(
+    error: &'a (dyn Error + 'static),
+) -> Option<&'a dyn AtErrorDyn> {
+    error.downcast_ref::<T>().map(|e| e as &dyn AtErrorDyn)
+}
+
+/// Builder for the `eyre` report hook.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, At, AtEyreHook};
+///
+/// #[derive(Debug)]
+/// struct DbError;
+///
+/// impl core::fmt::Display for DbError {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("user not found")
+///     }
+/// }
+///
+/// impl core::error::Error for DbError {}
+///
+/// AtEyreHook::new().with::<At<DbError>>().install().unwrap();
+///
+/// let report = eyre::Report::new(at(DbError).at_str("SELECT * FROM users"));
+/// let output = format!("{:?}", report);
+/// assert!(output.contains("\n\nTrace:\n"));
+/// assert!(output.contains("\n        SELECT * FROM users"));
+/// ```
+#[derive(Default)]
+pub struct AtEyreHook {
+    casts: Vec<Downcast>,
+}
+
+impl AtEyreHook {
+    /// Create a hook that renders no traced types yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render traces of `T`: an `At<E>` or an [`AtTraceable`](crate::AtTraceable) error.
+    pub fn with<T: AtErrorDyn + Error + 'static>(mut self) -> Self {
+        self.casts.push(downcast::<T>);
+        self
+    }
+
+    /// Install the hook with [`eyre::set_hook()`].
+    ///
+    /// Fails if a hook is already installed, or if a report was created
+    /// before and eyre installed its default hook.
+    pub fn install(self) -> Result<(), eyre::InstallError> {
+        let casts: Arc<[Downcast]> = self.casts.into();
+        eyre::set_hook(Box::new(move |_| {
+            Box::new(AtEyreHandler {
+                casts: casts.clone(),
+                location: None,
+            })
+        }))
+    }
+}
+
+/// The handler installed by [`AtEyreHook::install()`].
+struct AtEyreHandler {
+    casts: Arc<[Downcast]>,
+    location: Option<&'static Location<'static>>,
+}
+
+impl AtEyreHandler {
+    /// The first registered traced error in `error`'s chain.
+    fn find_traced<'a>(&self, error: &'a (dyn Error + 'static)) -> Option<&'a dyn AtErrorDyn> {
+        let mut next = Some(error);
+        while let Some(error) = next {
+            if let Some(traced) = self.casts.iter().find_map(|cast| cast(error)) {
+                return Some(traced);
+            }
+            next = error.source();
+        }
+        None
+    }
+}
+
+impl eyre::EyreHandler for AtEyreHandler {
+    fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return fmt::Debug::fmt(error, f);
+        }
+
+        write!(f, "{}", error)?;
+
+        if let Some(cause) = error.source() {
+            write!(f, "\n\nCaused by:")?;
+            let multiple = cause.source().is_some();
+            let mut next = Some(cause);
+            let mut n = 0;
+            while let Some(cause) = next {
+                if multiple {
+                    write!(f, "\n    {}: {}", n, cause)?;
+                } else {
+                    write!(f, "\n    {}", cause)?;
+                }
+                next = cause.source();
+                n += 1;
+            }
+        }
+
+        match self.find_traced(error) {
+            Some(traced) if traced.trace().is_some() => {
+                write!(f, "\n\nTrace:")?;
+                write_traceable_body(
+                    f,
+                    traced.trace(),
+                    &AtTraceFormat::DEFAULT,
+                    traced.error_code(),
+                    traced.help(),
+                    true,
+                )
+            }
+            _ => match self.location {
+                Some(location) => write!(f, "\n\nLocation:\n    {}", location),
+                None => Ok(()),
+            },
+        }
+    }
+
+    fn track_caller(&mut self, location: &'static Location<'static>) {
+        self.location = Some(location);
+    }
+}
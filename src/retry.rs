@@ -0,0 +1,81 @@
+//! Retrying a fallible operation (the `retry` feature).
+//!
+//! [`retry_at()`] keeps the frames of every failed attempt. Each attempt's
+//! frames end with a frame at the `retry_at()` call labelled with the attempt
+//! number, and a skipped-frames marker separates one attempt from the next:
+//!
+//! ```text
+//! connection refused
+//!     at src/db.rs:10:5
+//!     at src/main.rs:20:9
+//!         attempt 1/3
+//!     [...]
+//!     at src/db.rs:10:5
+//!     at src/main.rs:20:9
+//!         attempt 2/3
+//!     ...
+//! ```
+
+use alloc::format;
+use core::panic::Location;
+
+use crate::{At, AtFrameOwned, AtTrace};
+
+/// Call `f` up to `attempts` times (at least once) until it succeeds.
+///
+/// On success, the frames of earlier failures are dropped. If every attempt
+/// fails, returns the last attempt's error with the frames of all attempts,
+/// oldest first, as described in the [module docs](self). Earlier errors
+/// themselves are dropped. There is no delay between attempts; sleep inside
+/// `f` if the operation needs backoff.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, retry_at, At};
+///
+/// #[derive(Debug)]
+/// struct Busy;
+///
+/// let mut calls = 0;
+/// let err: At<Busy> = retry_at(3, || {
+///     calls += 1;
+///     Err::<(), _>(at(Busy))
+/// })
+/// .unwrap_err();
+///
+/// assert_eq!(calls, 3);
+/// let labels: Vec<_> = err.contexts().filter_map(|c| c.as_text()).collect();
+/// assert_eq!(labels, ["attempt 3/3", "attempt 2/3", "attempt 1/3"]);
+/// ```
+#[track_caller]
+pub fn retry_at<T, E>(attempts: u32, mut f: impl FnMut() -> Result<T, At<E>>) -> Result<T, At<E>> {
+    let loc = Location::caller();
+    let attempts = attempts.max(1);
+    let mut trace: Option<AtTrace> = None;
+    let mut attempt = 1;
+    loop {
+        let mut err = match f() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        let mut attempt_trace = err.take_trace().unwrap_or_default();
+        attempt_trace.push(
+            AtFrameOwned::new(Some(loc)).with_string(format!("attempt {}/{}", attempt, attempts)),
+        );
+        let combined = match trace.take() {
+            Some(mut combined) => {
+                combined.push(AtFrameOwned::new(None));
+                combined.append(attempt_trace);
+                combined
+            }
+            None => attempt_trace,
+        };
+        if attempt == attempts {
+            err.set_trace(combined);
+            return Err(err);
+        }
+        trace = Some(combined);
+        attempt += 1;
+    }
+}
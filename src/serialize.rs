@@ -0,0 +1,435 @@
+//! `serde` support (the `serde` feature).
+//!
+//! `At<E>` serializes as a map with the inner error under `"error"` (using
+//! `E`'s own `Serialize` impl, so downstream consumers can match on its
+//! structure) followed by the trace:
+//!
+//! ```json
+//! {
+//!   "error": { "NotFound": { "id": 42 } },
+//!   "frames": [
+//!     { "file": "src/db.rs", "line": 10, "column": 5, "contexts": ["looking up user"] },
+//!     null,
+//!     {
+//!       "file": "src/api.rs", "line": 89, "column": 5, "contexts": [],
+//!       "crate": {
+//!         "name": "myapp", "repo": "https://github.com/you/myapp", "commit": "a1b2c3d",
+//!         "path": null, "link_format": "{repo}/blob/{commit}/{path}{file}#L{line}C{column}"
+//!       }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! With `error-id`, an `"error_id"` string follows `"error"`, and after
+//! [`at_correlation()`](At::at_correlation) a `"correlation_id"` string does.
+//! A [rate-limited](crate::set_rate_limit) trace adds a `"suppressed"` count.
+//! Skipped-frames markers serialize as `null`. Contexts are rendered with their
+//! `Display` output, in attachment order, the same text the `Debug` trace
+//! shows.
+//! Two kinds are exceptions and serialize as objects. Pairs from `at_kv()`
+//! become `{ "key": "user_id", "value": "42" }`, so log pipelines can index
+//! them. Bytes from `at_bytes()` become
+//! `{ "label": "header", "len": 100, "base64": "R0VUIC8=" }`, where `len` is
+//! the original length and `base64` holds the copied bytes.
+//! Frames with known crate info (from `at!()` or crate boundaries) carry it
+//! under `"crate"`, enough for a viewer to rebuild repository links.
+//! With the `tracing` feature, frames that recorded a span carry a numeric
+//! `"span_id"`.
+//!
+//! The pieces serialize on their own too, for errors that implement
+//! [`AtTraceable`](crate::AtTraceable) or inner errors without a `Serialize`
+//! impl: [`AtTrace`] and [`AtTraceBoxed`] as `{ "frames": [...] }` (plus
+//! `"error_id"`, `"correlation_id"` and `"suppressed"`), [`AtFrame`] as one frame object, and [`AtContextRef`] as one
+//! context.
+//!
+//! The owned [`AtTraceDetached`] and [`AtFrameDetached`] serialize in the same
+//! shape (frames without a file have `"file": null` and a `"function"`) and
+//! also implement `Deserialize`, so a trace can be sent to another process
+//! and attached to a local error with
+//! [`at_remote_trace()`](crate::At::at_remote_trace). Deserializing accepts
+//! any of the JSON above, including a serialized `At<E>`: unknown members
+//! such as `"error"` and `"crate"` are ignored, and object contexts become
+//! text (`key=value`, or the bytes header).
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+use crate::{
+    At, AtBytes, AtContextKind, AtContextRef, AtCrateInfo, AtFrame, AtFrameDetached, AtProblem,
+    AtTrace, AtTraceBoxed, AtTraceDetached,
+};
+
+impl<E: Serialize> Serialize for At<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("error", self.error())?;
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.error_id() {
+            map.serialize_entry("error_id", &DisplayStr(id))?;
+        }
+        if let Some(id) = self.correlation_id() {
+            map.serialize_entry("correlation_id", id)?;
+        }
+        if let Some(n) = self.trace_ref().and_then(AtTrace::suppressed_count) {
+            map.serialize_entry("suppressed", &n)?;
+        }
+        map.serialize_entry("frames", &FramesSer(self.trace_ref()))?;
+        map.end()
+    }
+}
+
+/// A trace without its error: the `"frames"` (and the ids) of [`At<E>`].
+impl Serialize for AtTrace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TraceSer(Some(self)).serialize(serializer)
+    }
+}
+
+/// Same as [`AtTrace`]; an unallocated trace has no frames.
+impl Serialize for AtTraceBoxed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TraceSer(self.as_ref()).serialize(serializer)
+    }
+}
+
+/// RFC 9457 members first, then the `fingerprint` and `trace` extensions.
+impl Serialize for AtProblem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", self.type_uri())?;
+        map.serialize_entry("title", self.title())?;
+        if let Some(status) = self.status() {
+            map.serialize_entry("status", &status)?;
+        }
+        if let Some(detail) = self.detail() {
+            map.serialize_entry("detail", detail)?;
+        }
+        if let Some(instance) = self.instance() {
+            map.serialize_entry("instance", instance)?;
+        }
+        map.serialize_entry("fingerprint", &format!("{:016x}", self.fingerprint()))?;
+        map.serialize_entry("trace", self.trace())?;
+        map.end()
+    }
+}
+
+/// A possibly-unallocated trace as a map.
+struct TraceSer<'a>(Option<&'a AtTrace>);
+
+impl Serialize for TraceSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.0.and_then(AtTrace::error_id) {
+            map.serialize_entry("error_id", &DisplayStr(id))?;
+        }
+        if let Some(id) = self.0.and_then(AtTrace::correlation_id) {
+            map.serialize_entry("correlation_id", id)?;
+        }
+        if let Some(n) = self.0.and_then(AtTrace::suppressed_count) {
+            map.serialize_entry("suppressed", &n)?;
+        }
+        map.serialize_entry("frames", &FramesSer(self.0))?;
+        map.end()
+    }
+}
+
+/// All frames of a trace, as a sequence.
+struct FramesSer<'a>(Option<&'a AtTrace>);
+
+impl Serialize for FramesSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.into_iter().flat_map(|t| t.frames()))
+    }
+}
+
+/// One frame: a location struct, or `null` for a skipped-frames marker.
+impl Serialize for AtFrame<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(loc) = self.location() else {
+            return serializer.serialize_none();
+        };
+        let span_id = span_id(self);
+        let crate_info = self.crate_info();
+        let len = 4 + usize::from(span_id.is_some()) + usize::from(crate_info.is_some());
+        let mut frame = serializer.serialize_struct("AtFrame", len)?;
+        frame.serialize_field("file", loc.file())?;
+        frame.serialize_field("line", &loc.line())?;
+        frame.serialize_field("column", &loc.column())?;
+        if let Some(id) = span_id {
+            frame.serialize_field("span_id", &id)?;
+        }
+        frame.serialize_field("contexts", &ContextsSer(self))?;
+        if let Some(info) = crate_info {
+            frame.serialize_field("crate", &CrateSer(info))?;
+        }
+        frame.end()
+    }
+}
+
+/// The crate metadata needed to rebuild a frame's repository link.
+struct CrateSer(&'static AtCrateInfo);
+
+impl Serialize for CrateSer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let info = self.0;
+        let mut krate = serializer.serialize_struct("AtCrateInfo", 5)?;
+        krate.serialize_field("name", info.name())?;
+        krate.serialize_field("repo", &info.repo())?;
+        krate.serialize_field("commit", &info.commit())?;
+        krate.serialize_field("path", &info.crate_path())?;
+        krate.serialize_field("link_format", info.link_format())?;
+        krate.end()
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn span_id(frame: &AtFrame<'_>) -> Option<u64> {
+    frame.span_id()
+}
+
+#[cfg(not(feature = "tracing"))]
+fn span_id(_: &AtFrame<'_>) -> Option<u64> {
+    None
+}
+
+/// A frame's contexts as display strings.
+struct ContextsSer<'a, 'b>(&'b AtFrame<'a>);
+
+impl Serialize for ContextsSer<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Crate boundaries are serialized as the frame's "crate"
+        let contexts = self
+            .0
+            .contexts()
+            .filter(|c| c.kind() != AtContextKind::Crate);
+        // Span ids have their own field
+        #[cfg(feature = "tracing")]
+        let contexts = contexts.filter(|c| c.as_span_id().is_none());
+        serializer.collect_seq(contexts)
+    }
+}
+
+/// One context: its display string, or an object for pairs and raw bytes.
+impl Serialize for AtContextRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some((key, value)) = self.as_kv() {
+            let mut kv = serializer.serialize_struct("AtKeyValue", 2)?;
+            kv.serialize_field("key", key)?;
+            kv.serialize_field("value", value)?;
+            return kv.end();
+        }
+        match self.as_bytes() {
+            Some(bytes) => BytesSer(bytes).serialize(serializer),
+            None => serializer.collect_str(self),
+        }
+    }
+}
+
+/// Raw bytes as label, original length and base64 of the copied bytes.
+struct BytesSer<'a>(&'a AtBytes);
+
+impl Serialize for BytesSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = serializer.serialize_struct("AtBytes", 3)?;
+        bytes.serialize_field("label", self.0.label())?;
+        bytes.serialize_field("len", &self.0.len())?;
+        bytes.serialize_field("base64", &self.0.to_base64())?;
+        bytes.end()
+    }
+}
+
+// ============================================================================
+// Detached traces
+// ============================================================================
+
+/// `{ "frames": [...] }`, like [`AtTrace`].
+impl Serialize for AtTraceDetached {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("frames", &DetachedFramesSer(self))?;
+        map.end()
+    }
+}
+
+struct DetachedFramesSer<'a>(&'a AtTraceDetached);
+
+impl Serialize for DetachedFramesSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.frames())
+    }
+}
+
+/// One frame like [`AtFrame`], plus `"function"` when known.
+impl Serialize for AtFrameDetached {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_skipped() {
+            return serializer.serialize_none();
+        }
+        let len = 4 + usize::from(self.function().is_some());
+        let mut frame = serializer.serialize_struct("AtFrameDetached", len)?;
+        frame.serialize_field("file", &self.file())?;
+        frame.serialize_field("line", &self.line())?;
+        frame.serialize_field("column", &self.column())?;
+        if let Some(function) = self.function() {
+            frame.serialize_field("function", function)?;
+        }
+        frame.serialize_field("contexts", &DetachedContextsSer(self))?;
+        frame.end()
+    }
+}
+
+struct DetachedContextsSer<'a>(&'a AtFrameDetached);
+
+impl Serialize for DetachedContextsSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.contexts())
+    }
+}
+
+impl<'de> Deserialize<'de> for AtTraceDetached {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(TraceVisitor)
+    }
+}
+
+struct TraceVisitor;
+
+impl<'de> Visitor<'de> for TraceVisitor {
+    type Value = AtTraceDetached;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a trace object with \"frames\"")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut frames = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "frames" => frames = Some(map.next_value::<Vec<Option<AtFrameDetached>>>()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let frames = frames.ok_or_else(|| de::Error::missing_field("frames"))?;
+        Ok(AtTraceDetached::from_frames(
+            frames.into_iter().map(Option::unwrap_or_default),
+        ))
+    }
+}
+
+/// A frame object; `null` (a skipped-frames marker) is handled by `Option`.
+impl<'de> Deserialize<'de> for AtFrameDetached {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(FrameVisitor)
+    }
+}
+
+struct FrameVisitor;
+
+impl<'de> Visitor<'de> for FrameVisitor {
+    type Value = AtFrameDetached;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a frame object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut file: Option<String> = None;
+        let (mut line, mut column) = (0, 0);
+        let mut function: Option<String> = None;
+        let mut contexts = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "file" => file = map.next_value()?,
+                "line" => line = map.next_value()?,
+                "column" => column = map.next_value()?,
+                "function" => function = map.next_value()?,
+                "contexts" => contexts = map.next_value::<Vec<ContextDe>>()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let mut frame = match file {
+            Some(file) => AtFrameDetached::new(file, line, column),
+            None => AtFrameDetached::skipped(),
+        };
+        if let Some(function) = function {
+            frame = frame.with_function(function);
+        }
+        for ContextDe(context) in contexts {
+            frame = frame.with_context(context);
+        }
+        Ok(frame)
+    }
+}
+
+/// A context string, or the text of a key-value or bytes object.
+struct ContextDe(String);
+
+impl<'de> Deserialize<'de> for ContextDe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ContextVisitor)
+    }
+}
+
+struct ContextVisitor;
+
+impl<'de> Visitor<'de> for ContextVisitor {
+    type Value = ContextDe;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a context string or object")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(ContextDe(String::from(v)))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(ContextDe(v))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut key, mut value, mut label) = (None, None, None);
+        let mut len: Option<u64> = None;
+        while let Some(name) = map.next_key::<String>()? {
+            match name.as_str() {
+                "key" => key = Some(map.next_value::<String>()?),
+                "value" => value = Some(map.next_value::<String>()?),
+                "label" => label = Some(map.next_value::<String>()?),
+                "len" => len = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        match (key, value, label, len) {
+            (Some(key), Some(value), _, _) => Ok(ContextDe(format!("{}={}", key, value))),
+            (_, _, Some(label), Some(len)) => Ok(ContextDe(format!("{} ({} bytes)", label, len))),
+            (_, _, Some(label), None) => Ok(ContextDe(label)),
+            _ => Err(de::Error::custom(
+                "context object without key/value or label",
+            )),
+        }
+    }
+}
+
+/// Serialize any `Display` value as a string.
+#[cfg(feature = "error-id")]
+struct DisplayStr<T>(T);
+
+#[cfg(feature = "error-id")]
+impl<T: fmt::Display> Serialize for DisplayStr<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
@@ -0,0 +1,209 @@
+//! Recording `tracing` span data on captured frames (the `tracing` feature).
+//!
+//! Off by default: each capture then costs one relaxed atomic load. Once
+//! enabled, every newly captured frame records the id of the span that was
+//! current at that point, so a frame can be joined against the same span in
+//! a tracing backend. Frames captured outside any span record nothing.
+//!
+//! Separately, [`set_record_span_fields()`] copies the fields of the current
+//! span and its parents (`request_id`, `user_id`, ...) into key-value contexts
+//! when an error is first captured. Span fields are only readable through a
+//! subscriber layer, so this needs [`AtSpanFieldsLayer`] installed on a
+//! `tracing_subscriber::Registry`.
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use tracing::Subscriber;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+static RECORD_SPAN_IDS: AtomicBool = AtomicBool::new(false);
+static RECORD_SPAN_FIELDS: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable recording span ids on newly captured frames.
+///
+/// Process-wide. Frames captured before the call are unaffected.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::at;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// whereat::set_record_span_ids(true);
+/// // Without an installed subscriber spans have no ids, so nothing is recorded
+/// let err = at(MyError);
+/// assert_eq!(err.frames().next().unwrap().span_id(), None);
+/// # whereat::set_record_span_ids(false);
+/// ```
+#[inline]
+pub fn set_record_span_ids(enabled: bool) {
+    RECORD_SPAN_IDS.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether span ids are being recorded.
+#[inline]
+pub fn record_span_ids() -> bool {
+    RECORD_SPAN_IDS.load(Ordering::Relaxed)
+}
+
+/// The current span's id, if recording is enabled and a span is entered.
+#[inline]
+pub(crate) fn current_span_id() -> Option<u64> {
+    if !record_span_ids() {
+        return None;
+    }
+    tracing::Span::current().id().map(|id| id.into_u64())
+}
+
+/// Enable or disable copying span fields into newly captured errors.
+///
+/// Process-wide. When enabled, an error's first capture inside a span adds one
+/// key-value context per field of that span and its parents, with inner spans
+/// winning on duplicate names. Requires [`AtSpanFieldsLayer`].
+///
+/// ## Example
+///
+/// ```rust
+/// use tracing_subscriber::layer::SubscriberExt;
+/// use whereat::{at, AtSpanFieldsLayer};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// let subscriber = tracing_subscriber::registry().with(AtSpanFieldsLayer::new());
+/// let _guard = tracing::subscriber::set_default(subscriber);
+/// whereat::set_record_span_fields(true);
+///
+/// let err = tracing::info_span!("request", request_id = "r-42", user_id = 7)
+///     .in_scope(|| at(MyError));
+/// let kvs: Vec<_> = err.kvs().collect();
+/// assert_eq!(kvs, [("user_id", "7"), ("request_id", "r-42")]);
+/// # whereat::set_record_span_fields(false);
+/// ```
+#[inline]
+pub fn set_record_span_fields(enabled: bool) {
+    RECORD_SPAN_FIELDS.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether span fields are being copied into new errors.
+#[inline]
+pub fn record_span_fields() -> bool {
+    RECORD_SPAN_FIELDS.load(Ordering::Relaxed)
+}
+
+/// Fields of the current span and its parents, outermost span first.
+///
+/// Empty unless recording is enabled and the default subscriber is a
+/// `Registry` with [`AtSpanFieldsLayer`].
+pub(crate) fn current_span_fields() -> Vec<(&'static str, String)> {
+    if !record_span_fields() {
+        return Vec::new();
+    }
+    let current = tracing::Span::current();
+    let mut fields: Vec<(&'static str, String)> = Vec::new();
+    current.with_subscriber(|(id, dispatch)| {
+        let Some(registry) = dispatch.downcast_ref::<Registry>() else {
+            return;
+        };
+        let Some(span) = registry.span(id) else {
+            return;
+        };
+        // A field redeclared by an inner span replaces the outer one
+        for span in span.scope().from_root() {
+            if let Some(recorded) = span.extensions().get::<SpanFields>() {
+                for (name, value) in &recorded.0 {
+                    fields.retain(|(n, _)| n != name);
+                    fields.push((name, value.clone()));
+                }
+            }
+        }
+    });
+    fields
+}
+
+/// A `tracing_subscriber` layer that keeps each span's fields readable by whereat.
+///
+/// Install it on a `Registry` and enable [`set_record_span_fields()`] to copy
+/// span fields into errors:
+///
+/// ```rust
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let subscriber = tracing_subscriber::registry().with(whereat::AtSpanFieldsLayer::new());
+/// # let _ = subscriber;
+/// ```
+///
+/// Values are stored as strings: `&str` fields verbatim, others via `Debug`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AtSpanFieldsLayer {
+    _private: (),
+}
+
+impl AtSpanFieldsLayer {
+    /// Create the layer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<S> Layer<S> for AtSpanFieldsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanFields(Vec::new());
+        attrs.record(&mut fields);
+        if !fields.0.is_empty() {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<SpanFields>() {
+            Some(fields) => values.record(fields),
+            None => {
+                let mut fields = SpanFields(Vec::new());
+                values.record(&mut fields);
+                extensions.insert(fields);
+            }
+        }
+    }
+}
+
+/// Span extension holding the recorded fields, in declaration order.
+struct SpanFields(Vec<(&'static str, String)>);
+
+impl SpanFields {
+    fn set(&mut self, name: &'static str, value: String) {
+        match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some(entry) => entry.1 = value,
+            None => self.0.push((name, value)),
+        }
+    }
+}
+
+impl Visit for SpanFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field.name(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.set(field.name(), std::format!("{:?}", value));
+    }
+}
@@ -0,0 +1,230 @@
+//! Aggregate statistics over batches of traced errors.
+//!
+//! [`AtStats`] counts errors by kind, by origin location, and by fingerprint
+//! (kind plus the full path the error took), for batch job reports and test
+//! summaries. Its `Display` output is a plain-text table.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::panic::Location;
+
+use crate::localize::variant_name;
+use crate::trace::Fnv1a;
+use crate::{At, AtTrace};
+
+impl<E: fmt::Debug> At<E> {
+    /// A stable 64-bit hash of the error kind and the trace locations.
+    ///
+    /// The kind is the variant (or type) name from the error's `Debug` output,
+    /// so `NotFound("a")` and `NotFound("b")` share a fingerprint when they took
+    /// the same path. Contexts are ignored. See [`AtTrace::fingerprint()`](crate::AtTrace::fingerprint).
+    pub fn fingerprint(&self) -> u64 {
+        let debug = alloc::format!("{:?}", self.error());
+        fingerprint(variant_name(&debug), self.trace())
+    }
+}
+
+/// Hash an error kind already taken from `Debug` output with its trace's locations.
+fn fingerprint(kind: &str, trace: Option<&AtTrace>) -> u64 {
+    let mut hash = Fnv1a::new();
+    hash.write(kind.as_bytes());
+    if let Some(trace) = trace {
+        hash.write(&trace.fingerprint().to_le_bytes());
+    }
+    hash.finish()
+}
+
+/// First error seen with a fingerprint, kept for the summary table.
+#[derive(Clone, Debug)]
+struct FingerprintEntry {
+    count: usize,
+    kind: String,
+    origin: Option<&'static Location<'static>>,
+}
+
+/// Counts of traced errors by kind, origin, and fingerprint.
+///
+/// Feed it errors with [`record()`](Self::record), `extend()`, or `collect()`.
+/// The `by_*` accessors return entries sorted by count, most frequent first.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, At, AtStats};
+///
+/// #[derive(Debug)]
+/// enum JobError { Timeout(u32), BadInput }
+///
+/// fn run(job: u32) -> Result<(), At<JobError>> {
+///     match job % 3 {
+///         0 => Err(at(JobError::Timeout(job))),
+///         1 => Err(at(JobError::BadInput)),
+///         _ => Ok(()),
+///     }
+/// }
+///
+/// let failures: Vec<_> = (0..9).filter_map(|job| run(job).err()).collect();
+/// let stats: AtStats = failures.iter().collect();
+///
+/// assert_eq!(stats.total(), 6);
+/// assert_eq!(stats.by_kind(), [("BadInput", 3), ("Timeout", 3)]);
+/// assert_eq!(stats.by_fingerprint().len(), 2);
+/// println!("{}", stats);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AtStats {
+    total: usize,
+    kinds: BTreeMap<String, usize>,
+    origins: BTreeMap<&'static Location<'static>, usize>,
+    fingerprints: BTreeMap<u64, FingerprintEntry>,
+}
+
+impl AtStats {
+    /// Create an empty collector.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one error.
+    ///
+    /// Formats the error with `Debug` once, for both its kind and its fingerprint.
+    pub fn record<E: fmt::Debug>(&mut self, error: &At<E>) {
+        let debug = alloc::format!("{:?}", error.error());
+        let kind = variant_name(&debug);
+        let trace = error.trace();
+        let origin = trace.and_then(|t| t.frame(0)).and_then(|f| f.location());
+
+        self.total += 1;
+        match self.kinds.get_mut(kind) {
+            Some(count) => *count += 1,
+            None => {
+                self.kinds.insert(String::from(kind), 1);
+            }
+        }
+        if let Some(origin) = origin {
+            *self.origins.entry(origin).or_insert(0) += 1;
+        }
+        self.fingerprints
+            .entry(fingerprint(kind, trace))
+            .or_insert_with(|| FingerprintEntry {
+                count: 0,
+                kind: String::from(kind),
+                origin,
+            })
+            .count += 1;
+    }
+
+    /// Number of errors recorded.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Check if no errors have been recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Counts per error kind (variant or type name).
+    pub fn by_kind(&self) -> Vec<(&str, usize)> {
+        sorted(self.kinds.iter().map(|(kind, &n)| (kind.as_str(), n)))
+    }
+
+    /// Counts per origin: the location where each error was first traced.
+    ///
+    /// Errors without a trace have no origin and are not counted here.
+    pub fn by_origin(&self) -> Vec<(&'static Location<'static>, usize)> {
+        sorted(self.origins.iter().map(|(&loc, &n)| (loc, n)))
+    }
+
+    /// Counts per [`At::fingerprint()`].
+    pub fn by_fingerprint(&self) -> Vec<(u64, usize)> {
+        sorted(self.fingerprints.iter().map(|(&fp, e)| (fp, e.count)))
+    }
+}
+
+/// Most frequent first; ties keep key order.
+fn sorted<K>(entries: impl Iterator<Item = (K, usize)>) -> Vec<(K, usize)> {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by_key(|&(_, n)| core::cmp::Reverse(n));
+    entries
+}
+
+impl<E: fmt::Debug> Extend<At<E>> for AtStats {
+    fn extend<I: IntoIterator<Item = At<E>>>(&mut self, iter: I) {
+        for error in iter {
+            self.record(&error);
+        }
+    }
+}
+
+impl<'a, E: fmt::Debug + 'a> Extend<&'a At<E>> for AtStats {
+    fn extend<I: IntoIterator<Item = &'a At<E>>>(&mut self, iter: I) {
+        for error in iter {
+            self.record(error);
+        }
+    }
+}
+
+impl<E: fmt::Debug> FromIterator<At<E>> for AtStats {
+    fn from_iter<I: IntoIterator<Item = At<E>>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl<'a, E: fmt::Debug + 'a> FromIterator<&'a At<E>> for AtStats {
+    fn from_iter<I: IntoIterator<Item = &'a At<E>>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl fmt::Display for AtStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.total {
+            1 => writeln!(f, "1 error")?,
+            n => writeln!(f, "{} errors", n)?,
+        }
+        if self.total == 0 {
+            return Ok(());
+        }
+
+        writeln!(f, "\nby kind:")?;
+        for (kind, n) in self.by_kind() {
+            writeln!(f, "{:>8}  {}", n, kind)?;
+        }
+
+        if !self.origins.is_empty() {
+            writeln!(f, "\nby origin:")?;
+            for (loc, n) in self.by_origin() {
+                writeln!(
+                    f,
+                    "{:>8}  {}:{}:{}",
+                    n,
+                    loc.file(),
+                    loc.line(),
+                    loc.column()
+                )?;
+            }
+        }
+
+        writeln!(f, "\nby fingerprint:")?;
+        let mut entries: Vec<_> = self.fingerprints.iter().collect();
+        entries.sort_by_key(|(_, e)| core::cmp::Reverse(e.count));
+        for (fp, entry) in entries {
+            write!(f, "{:>8}  {:016x}  {}", entry.count, fp, entry.kind)?;
+            if let Some(loc) = entry.origin {
+                write!(f, " at {}:{}", loc.file(), loc.line())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Tokio task helpers (the `tokio` feature).
+//!
+//! [`JoinHandleAtExt::await_at()`] awaits a spawned task's traced result and
+//! records the join site, linking where work was awaited to where it failed.
+
+use core::future::Future;
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use tokio::task::{JoinError, JoinHandle};
+
+use crate::At;
+
+/// Extension trait for tokio `JoinHandle`s of traced results.
+pub trait JoinHandleAtExt<T, E> {
+    /// Await the task, adding the caller's location as a frame to any error.
+    ///
+    /// A task error gets the join site appended to its own trace. A panic or
+    /// cancellation becomes `E::from(join_error)`, traced at the join site,
+    /// instead of a `JoinError` the caller has to handle separately.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use tokio::task::JoinError;
+    /// use whereat::{at, At, JoinHandleAtExt};
+    ///
+    /// #[derive(Debug)]
+    /// enum JobError { Failed, Panicked(String) }
+    ///
+    /// impl From<JoinError> for JobError {
+    ///     fn from(e: JoinError) -> Self { JobError::Panicked(e.to_string()) }
+    /// }
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let failed = tokio::spawn(async { Err::<(), _>(at(JobError::Failed)) });
+    /// let err = failed.await_at().await.unwrap_err();
+    /// assert_eq!(err.frame_count(), 2); // where it failed, then where it was joined
+    ///
+    /// async fn explode() -> Result<(), At<JobError>> {
+    ///     panic!("boom")
+    /// }
+    /// let err = tokio::spawn(explode()).await_at().await.unwrap_err();
+    /// assert!(matches!(err.error(), JobError::Panicked(msg) if msg.contains("boom")));
+    /// assert_eq!(err.frame_count(), 1);
+    /// # });
+    /// ```
+    #[track_caller]
+    fn await_at(self) -> AtJoinFuture<T, E>;
+}
+
+impl<T, E: From<JoinError>> JoinHandleAtExt<T, E> for JoinHandle<Result<T, At<E>>> {
+    #[track_caller]
+    #[inline]
+    fn await_at(self) -> AtJoinFuture<T, E> {
+        AtJoinFuture {
+            handle: self,
+            location: Location::caller(),
+        }
+    }
+}
+
+/// Future returned by [`JoinHandleAtExt::await_at()`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct AtJoinFuture<T, E> {
+    handle: JoinHandle<Result<T, At<E>>>,
+    location: &'static Location<'static>,
+}
+
+impl<T, E: From<JoinError>> Future for AtJoinFuture<T, E> {
+    type Output = Result<T, At<E>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let location = self.location;
+        let joined = match Pin::new(&mut self.handle).poll(cx) {
+            Poll::Ready(joined) => joined,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(match joined {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(error)) => Err(error.at_location(location)),
+            Err(join_error) => Err(At::wrap(E::from(join_error)).at_location(location)),
+        })
+    }
+}
@@ -0,0 +1,113 @@
+//! Reporting a traced error returned from `main` (requires `std`).
+//!
+//! `fn main() -> Result<(), At<E>>` works, but the standard library prints
+//! the error with `Debug` after an `Error: ` prefix and always exits with
+//! code 1. [`AtMain`] prints [`display_with_meta()`](At::display_with_meta)
+//! instead, with repository links, optionally followed by an
+//! [environment snapshot](crate::env_report), and exits with a chosen code.
+
+use core::fmt;
+use std::process::{ExitCode, Termination};
+
+use crate::{At, AtEnvReport};
+
+/// A `main` result that reports a traced error with its full trace.
+///
+/// Convert with `.into()` (or [`AtMain::new()`]) and return it from `main`.
+/// On `Err`, the trace is written to stderr and the process exits with the
+/// configured code (1 by default).
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use whereat::{at, At, AtMain};
+///
+/// #[derive(Debug)]
+/// enum CliError { MissingConfig }
+///
+/// fn run() -> Result<(), At<CliError>> {
+///     Err(at(CliError::MissingConfig))
+/// }
+///
+/// fn main() -> AtMain<CliError> {
+///     AtMain::new(run())
+///         .exit_code(78) // EX_CONFIG
+///         .env_report(whereat::env_report().var("RUST_LOG"))
+/// }
+/// ```
+#[must_use = "return it from `main`, or call `report()`"]
+pub struct AtMain<E> {
+    result: Result<(), At<E>>,
+    code: u8,
+    env: Option<AtEnvReport>,
+}
+
+impl<E> AtMain<E> {
+    /// Wrap the result of the program's work.
+    pub fn new(result: Result<(), At<E>>) -> Self {
+        Self {
+            result,
+            code: 1,
+            env: None,
+        }
+    }
+
+    /// Exit with `code` on failure instead of 1.
+    pub fn exit_code(mut self, code: u8) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Print `env` after the trace on failure, for bug reports.
+    pub fn env_report(mut self, env: AtEnvReport) -> Self {
+        self.env = Some(env);
+        self
+    }
+}
+
+impl<E> From<Result<(), At<E>>> for AtMain<E> {
+    fn from(result: Result<(), At<E>>) -> Self {
+        Self::new(result)
+    }
+}
+
+impl<E: fmt::Debug> Termination for AtMain<E> {
+    fn report(self) -> ExitCode {
+        let Err(err) = self.result else {
+            return ExitCode::SUCCESS;
+        };
+        std::eprint!("{}", err.display_with_meta());
+        if let Some(env) = &self.env {
+            std::eprint!("\n{:#}", env);
+        }
+        ExitCode::from(self.code)
+    }
+}
+
+/// Report `result` like [`AtMain`] and return the exit code.
+///
+/// For a `main` that returns [`ExitCode`] and does more work after the
+/// error is reported.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::process::ExitCode;
+/// use whereat::{at, At};
+///
+/// #[derive(Debug)]
+/// struct Failed;
+///
+/// fn run() -> Result<(), At<Failed>> {
+///     Ok(())
+/// }
+///
+/// fn main() -> ExitCode {
+///     let code = whereat::report(run());
+///     // flush logs, ...
+///     code
+/// }
+/// ```
+pub fn report<E: fmt::Debug>(result: Result<(), At<E>>) -> ExitCode {
+    AtMain::new(result).report()
+}
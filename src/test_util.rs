@@ -0,0 +1,109 @@
+//! Deterministic locations for testing error-handling code.
+//!
+//! Traces record `file:line:col` of each capture, so assertions on formatted
+//! output break whenever an unrelated line shifts. The guards here replace
+//! every location captured on the current thread while they are alive: frame
+//! counts, contexts and crate info are unaffected, only the recorded location
+//! changes. Requires the `test-util` feature; not meant for production builds.
+//!
+//! `Location::caller()` is a `const fn`, so a fixed location can be taken
+//! once in a `static` and reused by every test:
+//!
+//! ```rust
+//! use core::panic::Location;
+//! use whereat::{at, ResultAtExt};
+//!
+//! #[derive(Debug)]
+//! struct MyError;
+//!
+//! static TEST_SITE: &Location<'static> = Location::caller();
+//!
+//! let _frozen = whereat::test_util::freeze_locations(TEST_SITE);
+//! let err = at(MyError).at_str("loading").at();
+//!
+//! assert_eq!(err.frame_count(), 2);
+//! assert!(err.frames().all(|f| f.location() == Some(TEST_SITE)));
+//! ```
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::panic::Location;
+
+#[derive(Clone, Copy)]
+enum Override {
+    Freeze(&'static Location<'static>),
+    Map(fn(&'static Location<'static>) -> &'static Location<'static>),
+}
+
+std::thread_local! {
+    static OVERRIDE: Cell<Option<Override>> = const { Cell::new(None) };
+}
+
+/// Restores the previous location override when dropped.
+///
+/// Returned by [`freeze_locations()`] and [`map_locations()`]. Guards nest:
+/// dropping an inner guard reinstates the outer one. Not `Send`, since the
+/// override is per thread.
+#[must_use = "the override is removed when the guard is dropped"]
+pub struct LocationOverride {
+    previous: Option<Override>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for LocationOverride {
+    fn drop(&mut self) {
+        OVERRIDE.with(|o| o.set(self.previous));
+    }
+}
+
+fn install(new: Override) -> LocationOverride {
+    LocationOverride {
+        previous: OVERRIDE.with(|o| o.replace(Some(new))),
+        _not_send: PhantomData,
+    }
+}
+
+/// Record `location` for every capture on this thread until the guard drops.
+pub fn freeze_locations(location: &'static Location<'static>) -> LocationOverride {
+    install(Override::Freeze(location))
+}
+
+/// Pass every location captured on this thread through `map` until the guard drops.
+///
+/// Useful for collapsing only some locations, e.g. everything from one file.
+///
+/// ## Example
+///
+/// ```rust
+/// use core::panic::Location;
+/// use whereat::{at, ResultAtExt};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// static HELPERS: &Location<'static> = Location::caller();
+///
+/// fn collapse_this_file(loc: &'static Location<'static>) -> &'static Location<'static> {
+///     if loc.file() == HELPERS.file() { HELPERS } else { loc }
+/// }
+///
+/// let _guard = whereat::test_util::map_locations(collapse_this_file);
+/// let a = at(MyError);
+/// let b = at(MyError);
+/// assert_eq!(a.frames().next().unwrap().location(), b.frames().next().unwrap().location());
+/// ```
+pub fn map_locations(
+    map: fn(&'static Location<'static>) -> &'static Location<'static>,
+) -> LocationOverride {
+    install(Override::Map(map))
+}
+
+/// Apply the current thread's override, if any, to a captured location.
+#[inline]
+pub(crate) fn apply(location: &'static Location<'static>) -> &'static Location<'static> {
+    match OVERRIDE.with(Cell::get) {
+        None => location,
+        Some(Override::Freeze(frozen)) => frozen,
+        Some(Override::Map(map)) => map(location),
+    }
+}
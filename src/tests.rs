@@ -3,14 +3,21 @@
 //! These tests are in a separate file for organization but remain in the `src/`
 //! directory to retain access to `pub(crate)` items like `AtContext`.
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
+use crate::ResultAtExt;
 use crate::context::AtContext;
 use crate::trace::AtTrace;
-use crate::{At, ErrorAtExt, ResultAtExt, at};
+use crate::{At, ErrorAtExt, at};
 use alloc::boxed::Box;
 use alloc::string::String;
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 use alloc::vec::Vec;
 use core::fmt;
 
+// Tests that assert on recorded frames are compiled out under `off`, and
+// those that assert on recorded contexts also under `trace-locations-only`.
+// `trace-full` wins over both, so `--all-features` runs every test.
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 enum TestError {
     NotFound,
@@ -54,12 +61,20 @@ fn test_sizeof_trace() {
 
     let trace_size = size_of::<AtTrace>();
 
+    // The optional error id field adds one word on top of the sizes below
+    #[cfg(feature = "error-id")]
+    let trace_size = trace_size - size_of::<Option<crate::AtErrorId>>();
+
+    // The tokio task id adds a word; its boundary flag shares the state's word
+    #[cfg(feature = "tokio")]
+    let trace_size = trace_size - 8;
+
     // AtTrace size depends on feature flags:
-    // - Without tinyvec/smallvec: 40 bytes (locations Vec 24 + crate_info 8 + contexts Option<Box> 8)
-    // - tinyvec-64-bytes: 64 bytes (TinyVec<4 slots> 48 + crate_info 8 + contexts 8)
-    // - tinyvec-128-bytes / smallvec-128-bytes: 128 bytes (12 slots)
-    // - tinyvec-256-bytes / smallvec-256-bytes: 256 bytes (28 slots)
-    // - tinyvec-512-bytes: 512 bytes (60 slots)
+    // - Without tinyvec/smallvec: 48 bytes (locations Vec 24 + crate_info 8 + contexts Option<Box> 8 + state 8)
+    // - tinyvec-64-bytes: 64 bytes (TinyVec<3 slots> 40 + crate_info 8 + contexts 8 + state 8)
+    // - tinyvec-128-bytes / smallvec-128-bytes: 128 bytes (11 slots)
+    // - tinyvec-256-bytes / smallvec-256-bytes: 256 bytes (27 slots)
+    // - tinyvec-512-bytes: 512 bytes (59 slots)
 
     #[cfg(not(any(
         feature = "_tinyvec-64-bytes",
@@ -73,10 +88,10 @@ fn test_sizeof_trace() {
     // - len: u8 (1 byte, padded to 8)
     // - inline: [Option<Option<&Location>>; 4] = 64 bytes
     // - heap: Vec<T> = 24 bytes (ptr + len + capacity)
-    // Plus crate_info (8) + contexts (8) = 112 bytes total
+    // Plus crate_info (8) + contexts (8) + state (1, padded to 8) = 120 bytes total
     assert_eq!(
-        trace_size, 112,
-        "AtTrace should be 112 bytes with 4 inline slots"
+        trace_size, 120,
+        "AtTrace should be 120 bytes with 4 inline slots"
     );
 
     #[cfg(all(
@@ -134,6 +149,7 @@ fn test_sizeof_trace() {
     );
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_basic_trace() {
     let err = TestError::NotFound.start_at();
@@ -142,6 +158,7 @@ fn test_basic_trace() {
     assert!(!err.is_empty());
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_propagation() {
     fn inner() -> Result<(), At<TestError>> {
@@ -169,6 +186,7 @@ fn test_propagation() {
     }
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_result_map_err_at() {
     fn fallible() -> Result<(), &'static str> {
@@ -192,6 +210,7 @@ fn test_into_inner() {
     assert_eq!(inner, TestError::InvalidInput);
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_first_last_location() {
     fn level1() -> Result<(), At<TestError>> {
@@ -215,6 +234,7 @@ fn test_first_last_location() {
     assert!(first.line() < last.line());
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_display_debug() {
     let err = TestError::NotFound.start_at();
@@ -258,6 +278,7 @@ fn test_error_mut() {
     assert_eq!(err.error().count, 42);
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_larger_error_type() {
     #[derive(Debug)]
@@ -278,6 +299,10 @@ fn test_larger_error_type() {
     assert_eq!(err.error().code, 42);
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_at_str() {
     let err = TestError::NotFound.start_at().at_str("while fetching user");
@@ -287,6 +312,10 @@ fn test_at_str() {
     assert_eq!(text, Some("while fetching user"));
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_at_fn_captures_function_name() {
     fn my_function_name() -> At<TestError> {
@@ -306,6 +335,10 @@ fn test_at_fn_captures_function_name() {
     );
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_at_fn_adds_frame() {
     fn inner() -> Result<(), At<TestError>> {
@@ -326,6 +359,10 @@ fn test_at_fn_adds_frame() {
     );
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_at_named_adds_frame_with_label() {
     fn inner() -> Result<(), At<TestError>> {
@@ -348,6 +385,132 @@ fn test_at_named_adds_frame_with_label() {
     );
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
+#[test]
+fn test_at_if_only_captures_when_true() {
+    let err = at(TestError::NotFound).at_if(false);
+    assert_eq!(err.frame_count(), 1);
+
+    let err = err.at_if(true);
+    assert_eq!(err.frame_count(), 2);
+
+    let result: Result<(), At<TestError>> = Err(at(TestError::NotFound));
+    let err = result.at_if(false).at_if(true).unwrap_err();
+    assert_eq!(err.frame_count(), 2);
+}
+
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
+#[test]
+fn test_at_origin_str_targets_first_frame() {
+    // Empty trace: creates a frame
+    let err = At::wrap(TestError::NotFound).at_origin_str("origin");
+    assert_eq!(err.frame_count(), 1);
+
+    // Skipped markers are passed over; context goes after existing origin contexts
+    let result: Result<(), At<TestError>> = Err(At::wrap(TestError::NotFound)
+        .at_skipped_frames()
+        .at()
+        .at_str("first"));
+    let err = result
+        .at()
+        .at_str("last")
+        .at_origin_str("while parsing config")
+        .unwrap_err();
+
+    let per_frame: Vec<Vec<&str>> = err
+        .frames()
+        .map(|f| f.contexts().filter_map(|c| c.as_text()).collect())
+        .collect();
+    assert_eq!(
+        per_frame,
+        alloc::vec![
+            alloc::vec![],
+            alloc::vec!["first", "while parsing config"],
+            alloc::vec!["last"],
+        ]
+    );
+}
+
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
+#[test]
+fn test_at_str_if_only_adds_context_when_true() {
+    let err = at(TestError::NotFound)
+        .at_str_if(false, "skipped")
+        .at_str_if(true, "kept");
+
+    let texts: Vec<_> = err.contexts().filter_map(|c| c.as_text()).collect();
+    assert_eq!(texts, alloc::vec!["kept"]);
+    assert_eq!(err.frame_count(), 1);
+}
+
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
+#[test]
+fn test_debug_at_follows_debug_assertions() {
+    let expected_frames = if cfg!(debug_assertions) { 2 } else { 1 };
+
+    let err = at(TestError::NotFound).debug_at();
+    assert_eq!(err.frame_count(), expected_frames);
+
+    let result: Result<(), At<TestError>> = Err(at(TestError::NotFound));
+    let err = result
+        .debug_at()
+        .debug_at_str("hot loop")
+        .debug_at_string(|| String::from("iteration 7"))
+        .unwrap_err();
+    assert_eq!(err.frame_count(), expected_frames);
+
+    let texts: Vec<_> = err.contexts().filter_map(|c| c.as_text()).collect();
+    if cfg!(debug_assertions) {
+        assert_eq!(texts, alloc::vec!["iteration 7", "hot loop"]); // newest first
+    } else {
+        assert!(texts.is_empty());
+    }
+}
+
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
+#[test]
+fn test_capture_level_controls_contexts() {
+    use crate::trace::CAPTURE_CONTEXTS;
+    use core::cell::Cell;
+
+    let calls = Cell::new(0);
+    let err = At::wrap(TestError::NotFound)
+        .at_str("static")
+        .at_string(|| {
+            calls.set(calls.get() + 1);
+            String::from("lazy")
+        })
+        .at_named("phase");
+
+    // Frames are recorded at every capture level
+    assert_eq!(err.frame_count(), 2);
+
+    if CAPTURE_CONTEXTS {
+        assert_eq!(calls.get(), 1);
+        assert_eq!(err.contexts().count(), 3);
+    } else {
+        assert_eq!(
+            calls.get(),
+            0,
+            "closures must not run when contexts are off"
+        );
+        assert_eq!(err.contexts().count(), 0);
+    }
+}
+
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_str_propagation() {
     fn inner() -> Result<(), At<TestError>> {
@@ -366,6 +529,10 @@ fn test_str_propagation() {
     assert_eq!(text, Some("during initialization"));
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_map_err_at_with_context() {
     fn fallible() -> Result<(), &'static str> {
@@ -383,6 +550,10 @@ fn test_map_err_at_with_context() {
     assert_eq!(text, Some("while doing something"));
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_debug_with_message() {
     let err = TestError::NotFound.start_at().at_str("context info");
@@ -392,6 +563,10 @@ fn test_debug_with_message() {
     assert!(debug.contains("tests.rs"));
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_dbg_ctx_typed() {
     #[derive(Debug)]
@@ -416,6 +591,10 @@ fn test_dbg_ctx_typed() {
     assert!(found, "should find RequestInfo context");
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_multiple_contexts() {
     fn level1() -> Result<(), At<TestError>> {
@@ -476,6 +655,10 @@ fn test_context_enum() {
     assert!(display_ctx.is_display());
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_typed_context_debug_output() {
     #[derive(Debug)]
@@ -497,6 +680,10 @@ fn test_typed_context_debug_output() {
     assert!(debug.contains("test"));
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_ctx_data() {
     // Use a type that has both Display and Debug but we want Display formatting
@@ -521,6 +708,10 @@ fn test_ctx_data() {
     assert!(found, "should find string context");
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_mixed_context_types() {
     #[derive(Debug)]
@@ -548,6 +739,10 @@ fn test_mixed_context_types() {
     assert!(contexts[2].is_display()); // text message
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_trace_format_structure() {
     // Test that trace format shows locations oldest-first with contexts
@@ -592,6 +787,7 @@ fn test_trace_format_structure() {
     );
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_trace_origin_comes_first() {
     fn origin() -> Result<(), At<TestError>> {
@@ -624,6 +820,7 @@ fn test_trace_origin_comes_first() {
     );
 }
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_partial_eq_compares_error_only() {
     // Same error, different traces
@@ -660,6 +857,10 @@ fn test_as_ref() {
     assert!(core::ptr::eq(err.as_ref(), err.error()));
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_map_err_at() {
     #[derive(Debug, PartialEq)]
@@ -684,6 +885,54 @@ fn test_map_err_at() {
     assert_eq!(text, Some("inner context")); // Context preserved
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
+#[test]
+fn test_map_into_records_conversion_site() {
+    #[derive(Debug, PartialEq)]
+    struct Error1;
+    #[derive(Debug, PartialEq)]
+    struct Error2(Error1);
+
+    impl From<Error1> for Error2 {
+        fn from(e: Error1) -> Self {
+            Error2(e)
+        }
+    }
+
+    fn inner() -> Result<(), At<Error1>> {
+        Err(at(Error1).at_str("inner context"))
+    }
+
+    let (err, line) = (inner().map_err_into::<Error2>().unwrap_err(), line!());
+    assert_eq!(*err.error(), Error2(Error1));
+    assert_eq!(err.frame_count(), 2);
+    assert_eq!(
+        err.frames().last().unwrap().location().unwrap().line(),
+        line
+    );
+    assert_eq!(
+        err.contexts().find_map(|c| c.as_text()),
+        Some("inner context")
+    );
+
+    let (err, line) = (crate::convert_at::<_, Error2>(at(Error1)), line!());
+    assert_eq!(err.frame_count(), 2);
+    assert_eq!(
+        err.frames().last().unwrap().location().unwrap().line(),
+        line
+    );
+
+    let (err, line) = (at(Error1).map_into::<Error2>(), line!());
+    assert_eq!(
+        err.frames().last().unwrap().location().unwrap().line(),
+        line
+    );
+}
+
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_hash_ignores_trace() {
     use core::hash::{Hash, Hasher};
@@ -729,6 +978,87 @@ fn test_hash_ignores_trace() {
     assert_ne!(hash_one(&err1), hash_one(&err3));
 }
 
+// ============================================================================
+// Correlation Id Tests
+// ============================================================================
+
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
+#[test]
+fn test_correlation_id_roundtrip() {
+    let err = at(TestError::NotFound);
+    assert_eq!(err.correlation_id(), None);
+
+    let result: Result<(), At<TestError>> = Err(err);
+    let err = result
+        .at_correlation("req-1")
+        .at()
+        .at_correlation(String::from("req-2"))
+        .unwrap_err();
+
+    // Most recent wins
+    assert_eq!(err.correlation_id(), Some("req-2"));
+    assert_eq!(err.frame_count(), 2);
+
+    let debug = alloc::format!("{:?}", err);
+    assert!(debug.contains("correlation id: req-1"), "{}", debug);
+    assert!(debug.contains("correlation id: req-2"), "{}", debug);
+
+    // Log output carries the newest id at the top level
+    let json = err.to_json();
+    assert!(
+        json.contains(r#""correlation_id":"req-2","frames":"#),
+        "{}",
+        json
+    );
+}
+
+// ============================================================================
+// Error Id Tests
+// ============================================================================
+
+#[cfg(feature = "error-id")]
+#[test]
+fn test_error_id_assigned_at_first_capture() {
+    let err = At::wrap(TestError::NotFound);
+    assert!(err.error_id().is_none());
+
+    let err = err.at();
+    let id = err.error_id().expect("id assigned on first capture");
+
+    // Stable as the error propagates
+    let err = err.at().at_str("more context");
+    assert_eq!(err.error_id(), Some(id));
+
+    // Distinct per error
+    assert_ne!(at(TestError::NotFound).error_id(), Some(id));
+}
+
+#[cfg(feature = "error-id")]
+#[test]
+fn test_error_id_display_and_formatters() {
+    use alloc::string::ToString;
+
+    let err = at(TestError::NotFound);
+    let id = err.error_id().unwrap().to_string();
+
+    assert_eq!(id.len(), 13);
+    assert!(
+        id.chars()
+            .all(|c| c.is_ascii_digit() || (c.is_ascii_uppercase() && !"ILOU".contains(c)))
+    );
+    assert_eq!(
+        crate::AtErrorId::from_u64(err.error_id().unwrap().as_u64()),
+        err.error_id()
+    );
+
+    let expected = alloc::format!("error id: {}", id);
+    assert!(alloc::format!("{:?}", err).contains(&expected));
+    assert!(alloc::format!("{}", err.display_with_meta()).contains(&expected));
+    assert!(alloc::format!("{}", err.full_trace()).contains(&expected));
+    assert!(alloc::format!("{}", err.last_error_trace()).contains(&expected));
+    assert!(!alloc::format!("{}", err.last_error()).contains(&expected));
+}
+
 // ============================================================================
 // Pretty Formatter Tests
 // ============================================================================
@@ -757,6 +1087,41 @@ fn test_termcolor_meta_display_contains_crate_info() {
     assert!(output.contains("whereat"), "Output: {}", output);
 }
 
+#[cfg(feature = "_termcolor")]
+#[test]
+fn test_termcolor_links_wrap_locations_in_osc8() {
+    static INFO: crate::AtCrateInfo = crate::AtCrateInfo::builder()
+        .name("linked")
+        .repo(Some("https://github.com/org/repo"))
+        .commit(Some("abc123"))
+        .build();
+
+    let err = At::wrap(TestError::NotFound).at().at_crate(&INFO);
+    let loc = err.frames().next().unwrap().location().unwrap();
+    let url = alloc::format!(
+        "https://github.com/org/repo/blob/abc123/src/tests.rs#L{}C{}",
+        loc.line(),
+        loc.column()
+    );
+
+    let output = alloc::format!("{}", err.display_color_links());
+    assert!(
+        output.contains(&alloc::format!("\x1b]8;;{}\x1b\\", url)),
+        "Output: {:?}",
+        output
+    );
+    assert_eq!(output.matches("\x1b]8;;\x1b\\").count(), 1);
+    // The URL is the link target, not printed as text
+    assert_eq!(output.matches(url.as_str()).count(), 1);
+
+    #[cfg(feature = "std")]
+    {
+        let pretty = alloc::format!("{}", err.display_pretty());
+        let plain = alloc::format!("{}", err.display_with_meta());
+        assert!(pretty == output || pretty == plain);
+    }
+}
+
 #[cfg(feature = "_html")]
 #[test]
 fn test_html_display_contains_markup() {
@@ -810,6 +1175,7 @@ fn test_html_escapes_special_chars() {
 // Depth Limit Tests
 // ============================================================================
 
+#[cfg(any(feature = "trace-full", not(feature = "off")))]
 #[test]
 fn test_max_trace_frames_limit() {
     use crate::trace::AT_MAX_FRAMES;
@@ -835,6 +1201,56 @@ fn test_max_trace_frames_limit() {
     );
 }
 
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
+#[test]
+fn test_max_trace_frames_keeps_origin_and_newest() {
+    use crate::trace::{AT_KEEP_OLDEST_FRAMES, AT_MAX_FRAMES};
+
+    let err = at(TestError::NotFound).at_str("origin");
+    let origin = err.frames().next().unwrap().location().unwrap().line();
+    let mut err = err;
+    for _ in 0..AT_MAX_FRAMES + 50 {
+        err = err.at();
+    }
+    let (err, newest) = (err.at(), line!());
+
+    assert_eq!(err.frame_count(), AT_MAX_FRAMES);
+    let frames: Vec<_> = err.frames().collect();
+    assert_eq!(frames[0].location().unwrap().line(), origin);
+    assert_eq!(
+        frames[0].contexts().next().unwrap().as_text(),
+        Some("origin")
+    );
+    let marker = &frames[AT_KEEP_OLDEST_FRAMES];
+    assert!(marker.is_skipped());
+    // 180 frames in 128 slots, one of which is the marker
+    assert_eq!(marker.repeat_count(), 53);
+    assert_eq!(frames.last().unwrap().location().unwrap().line(), newest);
+    assert!(alloc::format!("{:?}", err).contains("[... 53 frames omitted]"));
+}
+
+#[test]
+fn test_push_onto_full_trace_keeps_contexts() {
+    use crate::trace::AT_MAX_FRAMES;
+
+    let mut trace = AtTrace::new();
+    for _ in 0..AT_MAX_FRAMES {
+        trace.push(crate::AtFrameOwned::capture());
+    }
+    trace.push(crate::AtFrameOwned::capture().with_str("NEWEST"));
+
+    assert_eq!(trace.frame_count(), AT_MAX_FRAMES);
+    let newest = trace.frames().last().unwrap();
+    assert_eq!(newest.contexts().next().unwrap().as_text(), Some("NEWEST"));
+}
+
+#[cfg(any(
+    feature = "trace-full",
+    not(any(feature = "off", feature = "trace-locations-only"))
+))]
 #[test]
 fn test_max_trace_contexts_limit() {
     use crate::trace::AT_MAX_CONTEXTS;
@@ -864,3 +1280,64 @@ fn test_limits_constants_are_128() {
     assert_eq!(AT_MAX_FRAMES, 128);
     assert_eq!(AT_MAX_CONTEXTS, 128);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn panic_report_uses_trace_layout() {
+    use crate::at::build_link_base;
+    use crate::panic::PanicReport;
+
+    static INFO: crate::AtCrateInfo = crate::AtCrateInfo::builder()
+        .name("app")
+        .repo(Some("https://github.com/org/app"))
+        .commit(Some("abc123"))
+        .build();
+
+    let loc = core::panic::Location::caller();
+    let base = build_link_base(&INFO);
+    let report = PanicReport {
+        thread: "main",
+        message: "index out of bounds",
+        location: Some(loc),
+        link_base: base.as_deref(),
+    };
+    assert_eq!(
+        alloc::format!("{}", report),
+        alloc::format!(
+            "thread 'main' panicked: index out of bounds\n    at {file}:{line}:{col}\n       \
+             https://github.com/org/app/blob/abc123/{file}#L{line}C{col}",
+            file = loc.file(),
+            line = loc.line(),
+            col = loc.column()
+        )
+    );
+
+    let report = PanicReport {
+        link_base: None,
+        location: None,
+        ..report
+    };
+    assert_eq!(
+        alloc::format!("{}", report),
+        "thread 'main' panicked: index out of bounds"
+    );
+}
+
+#[test]
+fn strip_registry_keeps_crate_and_path() {
+    use crate::trace_format::strip_registry;
+
+    assert_eq!(
+        strip_registry(
+            "/home/me/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.200/src/de.rs"
+        ),
+        "serde-1.0.200/src/de.rs"
+    );
+    assert_eq!(
+        strip_registry(
+            r"C:\Users\me\.cargo\registry\src\index.crates.io-1949cf8c6b5b557f\log-0.4.22\src\lib.rs"
+        ),
+        r"log-0.4.22\src\lib.rs"
+    );
+    assert_eq!(strip_registry("src/lib.rs"), "src/lib.rs");
+}
@@ -10,8 +10,13 @@ use alloc::vec::Vec;
 use core::fmt;
 use core::panic::Location;
 
-use crate::AtCrateInfo;
-use crate::context::{AtContext, AtContextRef};
+#[cfg(feature = "error-id")]
+use crate::AtErrorId;
+use crate::context::{
+    AtBytes, AtContext, AtContextKind, AtContextOwned, AtContextRef, AtCorrelationId,
+    AtExpectedActual, AtLevel,
+};
+use crate::{AtCrateInfo, AtTraceFormat};
 
 /// Context entry: (location_index, context).
 type ContextEntry = (u16, AtContext);
@@ -29,9 +34,18 @@ type ContextEntry = (u16, AtContext);
 ///
 /// This is an arbitrary limit (128) to prevent unbounded memory growth from
 /// consumer bugs like infinite recursion. Real-world traces rarely exceed 20 frames.
-/// Attempts to push beyond this limit are silently ignored.
+/// Once a trace is full, each new frame is still recorded: the oldest
+/// [`AT_KEEP_OLDEST_FRAMES`] stay, and the frames right after them are folded
+/// into a `[... k frames omitted]` marker, so both the origin and the most
+/// recent path survive.
 pub const AT_MAX_FRAMES: usize = 128;
 
+/// Number of oldest frames kept when a full trace starts omitting frames.
+///
+/// See [`AT_MAX_FRAMES`]. The omitted-frames marker sits right after them.
+/// A lower [`max_depth()`](crate::AtTracePolicy::max_depth) keeps fewer.
+pub const AT_KEEP_OLDEST_FRAMES: usize = 32;
+
 /// Maximum number of context entries in a trace.
 ///
 /// This is an arbitrary limit (128) to prevent unbounded memory growth from
@@ -39,6 +53,43 @@ pub const AT_MAX_FRAMES: usize = 128;
 /// Attempts to add contexts beyond this limit are silently ignored.
 pub const AT_MAX_CONTEXTS: usize = 128;
 
+/// Maximum number of bytes copied by `at_bytes()`.
+///
+/// Longer inputs keep their first 64 bytes and record the original length;
+/// the offending bytes of a malformed frame are almost always near its start.
+pub const AT_MAX_CONTEXT_BYTES: usize = 64;
+
+// ============================================================================
+// Capture Level
+// ============================================================================
+//
+// Selected with cargo features. `trace-full` (the behavior when no level feature
+// is enabled) records everything. `trace-locations-only` keeps the frame list but
+// turns every context method (`at_str`, `at_string`, `at_data`, `at_fn`, ...) into
+// a no-op: closures are never called and no context storage is allocated.
+// Crate boundary markers are still recorded because they determine which
+// repository each location links to. `off` records nothing: `At<E>` methods
+// never allocate a trace or call context closures, and captures into
+// `AtTraceable` traces are dropped.
+//
+// If `trace-full` is enabled too (e.g. `--all-features`), `trace-full` wins.
+
+/// Whether context entries are recorded under the selected capture level.
+pub(crate) const CAPTURE_CONTEXTS: bool = cfg!(any(
+    feature = "trace-full",
+    not(feature = "trace-locations-only")
+));
+
+/// Whether all capture is compiled out (the `off` feature).
+pub(crate) const CAPTURE_OFF: bool = cfg!(all(feature = "off", not(feature = "trace-full")));
+
+/// Whether context entries are recorded right now: the capture level allows
+/// them and the runtime [`AtTracePolicy`](crate::AtTracePolicy) does too.
+#[inline]
+pub(crate) fn captures_contexts() -> bool {
+    CAPTURE_CONTEXTS && crate::config::contexts_enabled()
+}
+
 // ============================================================================
 // LocationVec - configurable storage for trace locations
 // ============================================================================
@@ -63,32 +114,32 @@ type LocationElem = Option<&'static Location<'static>>;
 // InlineVec provides a consistent API regardless of backend (tinyvec, smallvec,
 // or custom inline+heap). The N constant determines inline capacity.
 
-/// Stack-first location storage with 4 inline slots (tinyvec-64-bytes).
+/// Stack-first location storage with 3 inline slots (tinyvec-64-bytes).
 #[cfg(all(
     feature = "_tinyvec-64-bytes",
     not(any(feature = "_tinyvec-128-bytes", feature = "_tinyvec-256-bytes"))
 ))]
-type LocationVec = crate::inline_vec::InlineVec<LocationElem, 4>;
+type LocationVec = crate::inline_vec::InlineVec<LocationElem, 3>;
 
-/// Stack-first location storage with 12 inline slots (tinyvec-128-bytes).
+/// Stack-first location storage with 11 inline slots (tinyvec-128-bytes).
 #[cfg(all(feature = "_tinyvec-128-bytes", not(feature = "_tinyvec-256-bytes")))]
-type LocationVec = crate::inline_vec::InlineVec<LocationElem, 12>;
+type LocationVec = crate::inline_vec::InlineVec<LocationElem, 11>;
 
-/// Stack-first location storage with 28 inline slots (tinyvec-256-bytes).
+/// Stack-first location storage with 27 inline slots (tinyvec-256-bytes).
 #[cfg(all(feature = "_tinyvec-256-bytes", not(feature = "_tinyvec-512-bytes")))]
-type LocationVec = crate::inline_vec::InlineVec<LocationElem, 28>;
+type LocationVec = crate::inline_vec::InlineVec<LocationElem, 27>;
 
-/// Stack-first location storage with 60 inline slots (tinyvec-512-bytes).
+/// Stack-first location storage with 59 inline slots (tinyvec-512-bytes).
 #[cfg(all(feature = "_tinyvec-512-bytes", not(feature = "_smallvec-128-bytes")))]
-type LocationVec = crate::inline_vec::InlineVec<LocationElem, 60>;
+type LocationVec = crate::inline_vec::InlineVec<LocationElem, 59>;
 
-/// Stack-first location storage with 12 inline slots (smallvec-128-bytes).
+/// Stack-first location storage with 11 inline slots (smallvec-128-bytes).
 #[cfg(all(feature = "_smallvec-128-bytes", not(feature = "_smallvec-256-bytes")))]
-type LocationVec = crate::inline_vec::InlineVec<LocationElem, 12>;
+type LocationVec = crate::inline_vec::InlineVec<LocationElem, 11>;
 
-/// Stack-first location storage with 28 inline slots (smallvec-256-bytes).
+/// Stack-first location storage with 27 inline slots (smallvec-256-bytes).
 #[cfg(feature = "_smallvec-256-bytes")]
-type LocationVec = crate::inline_vec::InlineVec<LocationElem, 28>;
+type LocationVec = crate::inline_vec::InlineVec<LocationElem, 27>;
 
 /// Inline-first location storage with 4 inline slots (default).
 #[cfg(not(any(
@@ -136,7 +187,7 @@ pub(crate) fn try_box<T>(value: T) -> Option<Box<T>> {
 }
 
 /// Try to push a location onto a LocationVec, returning false on allocation failure
-/// or if [`AT_MAX_FRAMES`] limit is reached.
+/// or if [`AT_MAX_FRAMES`] limit is reached (see [`AtTrace::omit_middle_frame`]).
 #[inline]
 fn try_push_location(vec: &mut LocationVec, elem: LocationElem) -> bool {
     if vec.len() >= AT_MAX_FRAMES {
@@ -176,6 +227,29 @@ fn context_iter(vec: &ContextVec) -> impl DoubleEndedIterator<Item = &ContextEnt
     vec.iter().flat_map(|v| v.iter())
 }
 
+/// 64-bit FNV-1a, for fingerprints that must not depend on `std`'s random hasher.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    #[inline]
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 // ============================================================================
 // AtTrace - Trace storage for location and context tracking
 // ============================================================================
@@ -223,6 +297,35 @@ pub struct AtTrace {
     /// AtContext associations: (location_index, context).
     /// Index saturates at u16::MAX; out-of-bounds associations are silently ignored.
     contexts: ContextVec,
+    /// Unique id, assigned when the first location is captured.
+    #[cfg(feature = "error-id")]
+    error_id: Option<AtErrorId>,
+    /// Tokio task that captured the first location.
+    #[cfg(feature = "tokio")]
+    task_id: Option<tokio::task::Id>,
+    /// Set when a later location was captured on a different task.
+    #[cfg(feature = "tokio")]
+    crossed_tasks: bool,
+    /// Whether sampling or rate limiting stopped this trace from recording.
+    state: TraceState,
+}
+
+/// What capture policy decided for a trace when its first frame was captured.
+///
+/// The decision sticks, so a sampled-out error stays untraced and a
+/// rate-limited one records nothing after its origin frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TraceState {
+    /// Recording normally (or not yet started).
+    Active,
+    /// Sampled out by [`set_sample_rate()`](crate::set_sample_rate): records nothing.
+    SampledOut,
+    /// Cut short by [`set_rate_limit()`](crate::set_rate_limit): keeps its
+    /// origin frame only. Holds how many errors from that origin were
+    /// suppressed, shown as a note when the trace is displayed. A `u16`
+    /// keeps the state in the same word as the tokio boundary flag.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    RateLimited(u16),
 }
 
 impl AtTrace {
@@ -235,6 +338,13 @@ impl AtTrace {
             locations: location_vec_new(),
             crate_info: None,
             contexts: context_vec_new(),
+            #[cfg(feature = "error-id")]
+            error_id: None,
+            #[cfg(feature = "tokio")]
+            task_id: None,
+            #[cfg(feature = "tokio")]
+            crossed_tasks: false,
+            state: TraceState::Active,
         }
     }
 
@@ -266,6 +376,146 @@ impl AtTrace {
         trace
     }
 
+    /// Build a trace from locations captured by some other mechanism, oldest first.
+    ///
+    /// Lets alternative capture sources (code generators, interpreters with their
+    /// own source maps) feed frames into whereat's formatting pipeline. Beyond
+    /// [`AT_MAX_FRAMES`], middle locations are folded into an omitted-frames
+    /// marker. Use [`from_frames()`](Self::from_frames) to include contexts.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use core::panic::Location;
+    /// use whereat::AtTrace;
+    ///
+    /// #[track_caller]
+    /// fn here() -> &'static Location<'static> {
+    ///     Location::caller()
+    /// }
+    ///
+    /// let trace = AtTrace::from_locations([here(), here()]);
+    /// assert_eq!(trace.frame_count(), 2);
+    /// ```
+    pub fn from_locations(locations: impl IntoIterator<Item = &'static Location<'static>>) -> Self {
+        let mut trace = Self::new();
+        for loc in locations {
+            if !trace.try_store(Some(loc)) {
+                break;
+            }
+        }
+        trace
+    }
+
+    /// Build a trace from owned frames (location + contexts), oldest first.
+    ///
+    /// A frame with no location becomes a skipped-frames marker (`[...]`).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{AtContextOwned, AtFrameOwned, AtTrace};
+    ///
+    /// let trace = AtTrace::from_frames([
+    ///     AtFrameOwned::capture().with_str("parsing template"),
+    ///     AtFrameOwned::new(None),
+    ///     AtFrameOwned::capture().with_context(AtContextOwned::debug(("line", 7))),
+    /// ]);
+    /// assert_eq!(trace.frame_count(), 3);
+    /// ```
+    pub fn from_frames(frames: impl IntoIterator<Item = AtFrameOwned>) -> Self {
+        let mut trace = Self::new();
+        for frame in frames {
+            if trace.frame_count() >= AT_MAX_FRAMES {
+                break;
+            }
+            trace.push(frame);
+        }
+        trace
+    }
+
+    /// Copy this trace into an [`AtTraceDetached`](crate::AtTraceDetached) with owned strings.
+    ///
+    /// Function names from `at_fn()` and `at_named()` become each frame's
+    /// function; other contexts are kept as their `Display` text, so secrets
+    /// stay redacted. Crate boundaries, repeat counts and backtraces are dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err: At<MyError> = at(MyError).at_str("loading").at_named("startup");
+    /// let detached = err.trace().unwrap().to_detached();
+    /// let frame = detached.frames().last().unwrap();
+    /// assert_eq!(frame.function(), Some("startup"));
+    /// assert_eq!(detached.frames().next().unwrap().contexts().next(), Some("loading"));
+    /// ```
+    pub fn to_detached(&self) -> crate::AtTraceDetached {
+        use alloc::string::ToString;
+
+        crate::AtTraceDetached::from_frames(self.frames().enumerate().map(|(i, frame)| {
+            let mut detached = match frame.location() {
+                Some(loc) => crate::AtFrameDetached::new(loc.file(), loc.line(), loc.column()),
+                None => crate::AtFrameDetached::skipped(),
+            };
+            if let Some(n) = self.suppressed_count().filter(|_| i == 0) {
+                detached = detached.with_context(alloc::format!("warn: {}", SuppressedNote(n)));
+            }
+            for context in frame.contexts() {
+                if let Some(name) = context.as_function_name() {
+                    detached = detached.with_function(name);
+                    continue;
+                }
+                if let Some(remote) = context.as_remote_trace() {
+                    // Indented to line up under the context marker
+                    let mut block = alloc::string::String::new();
+                    let _ = remote.write_block(&mut block, "          ");
+                    detached = detached.with_context(block);
+                    continue;
+                }
+                match context.kind() {
+                    AtContextKind::Crate | AtContextKind::Repeated | AtContextKind::Backtrace => {}
+                    _ => detached = detached.with_context(context.to_string()),
+                }
+            }
+            detached
+        }))
+    }
+
+    /// Convert a captured `std::backtrace::Backtrace` into an owned trace.
+    ///
+    /// Backtrace frames are only known at runtime, so they can't become
+    /// `&'static Location` entries; the result is an [`AtTraceDetached`](crate::AtTraceDetached)
+    /// with owned file names and function names, oldest first. Frames that
+    /// could not be symbolized are dropped. Returns an empty trace if the
+    /// backtrace was not captured (e.g. `RUST_BACKTRACE` unset).
+    ///
+    /// Useful for displaying errors captured before adopting whereat, or from
+    /// panic hooks, with the same layout as whereat traces.
+    ///
+    /// This is best-effort: stable std exposes backtrace frames only through
+    /// their `Display` text, whose layout is unspecified and may change
+    /// between Rust versions. Lines that don't parse are skipped, so a layout
+    /// change can lose file or function names but never panics.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::backtrace::Backtrace;
+    /// use whereat::AtTrace;
+    ///
+    /// let trace = AtTrace::from_backtrace(&Backtrace::force_capture());
+    /// println!("{}", trace);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_backtrace(backtrace: &std::backtrace::Backtrace) -> crate::AtTraceDetached {
+        crate::detached::from_backtrace(backtrace)
+    }
+
     /// Set the crate info for this trace.
     ///
     /// This is used by `at!()` to provide repository metadata for GitHub links.
@@ -281,6 +531,26 @@ impl AtTrace {
         self.crate_info
     }
 
+    /// Check if this trace was cut short by [`set_rate_limit()`](crate::set_rate_limit).
+    ///
+    /// A rate-limited trace holds only its origin frame; formatters show a
+    /// `warn: N similar errors suppressed` note under it.
+    #[inline]
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.state, TraceState::RateLimited(_))
+    }
+
+    /// How many errors from this trace's origin were suppressed in the current
+    /// window, including this one, if it was cut short by
+    /// [`set_rate_limit()`](crate::set_rate_limit). Saturates at `u16::MAX`.
+    #[inline]
+    pub fn suppressed_count(&self) -> Option<u32> {
+        match self.state {
+            TraceState::RateLimited(n) => Some(u32::from(n)),
+            _ => None,
+        }
+    }
+
     /// Add crate boundary marker, using inline storage when possible.
     ///
     /// - If no crate_info is set, stores it inline (no allocation)
@@ -307,16 +577,225 @@ impl AtTrace {
         }
     }
 
+    /// Get the unique id assigned when this trace captured its first location.
+    ///
+    /// Returns `None` if nothing has been captured yet.
+    #[cfg(feature = "error-id")]
+    #[inline]
+    pub fn error_id(&self) -> Option<AtErrorId> {
+        self.error_id
+    }
+
+    /// Get the id of the tokio task that captured the first location.
+    ///
+    /// Returns `None` if nothing has been captured yet, or if the first
+    /// capture happened outside a tokio task.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn task_id(&self) -> Option<tokio::task::Id> {
+        self.task_id
+    }
+
+    /// Check whether locations were captured on more than one tokio task.
+    ///
+    /// True once the error has been propagated across a task boundary, e.g.
+    /// returned from a spawned task and traced again by the awaiting task.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn crossed_task_boundary(&self) -> bool {
+        self.crossed_tasks
+    }
+
+    /// Get the most recently attached correlation id, if any.
+    ///
+    /// See [`At::at_correlation()`](crate::At::at_correlation).
+    #[inline]
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.contexts()
+            .find_map(|ctx| ctx.downcast_ref::<AtCorrelationId>())
+            .map(AtCorrelationId::as_str)
+    }
+
+    /// Iterate over remediation hints added with `at_help()`, oldest first.
+    pub fn suggestions(&self) -> impl Iterator<Item = &str> {
+        context_iter(&self.contexts).filter_map(|(_, ctx)| ctx.as_suggestion())
+    }
+
+    /// Iterate over pinned messages added with `at_pinned()`, oldest first.
+    pub fn pinned(&self) -> impl Iterator<Item = &str> {
+        context_iter(&self.contexts).filter_map(|(_, ctx)| ctx.as_pinned())
+    }
+
+    /// The backtrace captured by `at_backtrace()`, if any.
+    #[cfg(feature = "std-backtrace")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        context_iter(&self.contexts)
+            .find(|(_, ctx)| ctx.is_backtrace())
+            .and_then(|(_, ctx)| ctx.downcast_ref())
+    }
+
+    /// Whether `at_backtrace()` already captured a backtrace.
+    #[cfg(feature = "std-backtrace")]
+    pub(crate) fn has_backtrace(&self) -> bool {
+        context_iter(&self.contexts).any(|(_, ctx)| ctx.is_backtrace())
+    }
+
     /// Try to push a location. Returns false if allocation fails.
     #[inline]
     pub(crate) fn try_push(&mut self, loc: &'static Location<'static>) -> bool {
-        try_push_location(&mut self.locations, Some(loc))
+        !CAPTURE_OFF && self.try_capture(Some(loc))
     }
 
     /// Try to push a skipped frame marker. Returns false if allocation fails.
     #[inline]
     pub(crate) fn try_push_skipped(&mut self) -> bool {
-        try_push_location(&mut self.locations, None)
+        !CAPTURE_OFF && self.try_capture(None)
+    }
+
+    /// Push a newly captured location, assigning the error id on first capture.
+    #[inline]
+    fn try_capture(&mut self, elem: LocationElem) -> bool {
+        #[cfg(feature = "test-util")]
+        let elem = elem.map(crate::test_util::apply);
+        #[cfg(feature = "std")]
+        if elem.is_some_and(crate::config::is_skipped) {
+            return false;
+        }
+        if self.locations.is_empty() {
+            if self.state == TraceState::SampledOut {
+                return false;
+            }
+            if !crate::config::should_start_trace() {
+                if crate::config::is_enabled() {
+                    self.state = TraceState::SampledOut;
+                }
+                return false;
+            }
+            #[cfg(feature = "std")]
+            if let Some(loc) = elem {
+                let suppressed = crate::config::rate_limit_check(loc);
+                if suppressed > 0 {
+                    return self.start_rate_limited(loc, suppressed);
+                }
+            }
+        } else if !crate::config::is_enabled() || self.is_rate_limited() {
+            return false;
+        }
+        let pushed = self.try_store(elem);
+        #[cfg(feature = "error-id")]
+        if pushed && self.error_id.is_none() {
+            self.error_id = Some(AtErrorId::generate());
+        }
+        #[cfg(feature = "tokio")]
+        if pushed && elem.is_some() {
+            self.note_task(tokio::task::try_id());
+        }
+        #[cfg(feature = "tracing")]
+        if pushed && elem.is_some() && captures_contexts() {
+            if let Some(id) = crate::span::current_span_id() {
+                let idx = (self.locations.len() - 1).min(u16::MAX as usize) as u16;
+                let _ = try_push_context(&mut self.contexts, (idx, AtContext::SpanId(id)));
+            }
+            if self.locations.len() == 1 {
+                for (key, value) in crate::span::current_span_fields() {
+                    let context = AtContext::KeyValue {
+                        key: Cow::Borrowed(key),
+                        value: Cow::Owned(value),
+                    };
+                    let _ = try_push_context(&mut self.contexts, (0, context));
+                }
+            }
+        }
+        pushed
+    }
+
+    /// Store a frame without applying capture policy (sampling, rate limits,
+    /// skipped files, span recording): only the depth limit. For frames that
+    /// were captured elsewhere and are moved into this trace.
+    fn try_store(&mut self, elem: LocationElem) -> bool {
+        let limit = crate::config::max_depth();
+        if self.locations.len() >= limit {
+            let keep = AT_KEEP_OLDEST_FRAMES.min((limit - 1) / 2).max(1);
+            while self.locations.len() >= limit && self.omit_middle_frame(keep) {}
+            if self.locations.len() >= limit {
+                return false;
+            }
+        }
+        try_push_location(&mut self.locations, elem)
+    }
+
+    /// Free one slot in a full trace by folding the frame after the omitted-frames
+    /// marker into it, creating the marker after the oldest `at` frames first.
+    /// Returns false if there is no frame to fold.
+    ///
+    /// Contexts of omitted frames are dropped, except crate boundaries, which
+    /// move to the marker so later frames still link to the right repository.
+    #[cold]
+    fn omit_middle_frame(&mut self, at: usize) -> bool {
+        if self.locations.len() <= at + 1 {
+            return false;
+        }
+        let mut omitted = self.repeat_count(at);
+        if self.locations.get(at).flatten().is_some() {
+            self.locations.remove(at);
+            self.locations.insert(at, None);
+            self.drop_contexts_into(at, at);
+        }
+        omitted += self.repeat_count(at + 1);
+        self.locations.remove(at + 1);
+        self.drop_contexts_into(at + 1, at);
+
+        let (at_idx, omitted) = (at as u16, omitted.min(u32::MAX as usize) as u32);
+        if let Some(entries) = &mut self.contexts {
+            entries.retain(|(i, ctx)| *i != at_idx || ctx.repeat_count().is_none());
+            for (i, _) in entries.iter_mut() {
+                if *i > at_idx {
+                    *i -= 1;
+                }
+            }
+        }
+        let _ = self.try_insert_context(at, AtContext::Repeated(omitted));
+        true
+    }
+
+    /// Drop the contexts of frame `from`, moving its crate boundaries to frame `to`.
+    fn drop_contexts_into(&mut self, from: usize, to: usize) {
+        let Some(entries) = &mut self.contexts else {
+            return;
+        };
+        let (from, to) = (from as u16, to as u16);
+        entries.retain(|(i, ctx)| *i != from || ctx.is_crate_boundary());
+        for (i, _) in entries.iter_mut() {
+            if *i == from {
+                *i = to;
+            }
+        }
+    }
+
+    /// Start a trace that was rate limited: the origin frame only, marked so
+    /// that nothing more is recorded. The suppression note is rendered from
+    /// the count, so no context storage is allocated.
+    #[cfg(feature = "std")]
+    #[cold]
+    fn start_rate_limited(&mut self, origin: &'static Location<'static>, suppressed: u32) -> bool {
+        if !try_push_location(&mut self.locations, Some(origin)) {
+            return false;
+        }
+        self.state = TraceState::RateLimited(u16::try_from(suppressed).unwrap_or(u16::MAX));
+        true
+    }
+
+    /// Record the task of a new capture: the first one sets the task id, any
+    /// later one on a different task marks the boundary crossing.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    fn note_task(&mut self, current: Option<tokio::task::Id>) {
+        let first = self.locations.iter().flatten().nth(1).is_none();
+        if first {
+            self.task_id = current;
+        } else if current != self.task_id {
+            self.crossed_tasks = true;
+        }
     }
 
     /// Add context to the last location, or push a new location if trace is empty.
@@ -325,21 +804,61 @@ impl AtTrace {
     /// Use `try_push()` first if you need a new location, then call this for context.
     ///
     /// On allocation failure, the context may be lost but existing data is preserved.
+    ///
+    /// Under `trace-locations-only`, only crate boundary markers and correlation
+    /// ids are stored; other contexts are dropped (the frame is still created if
+    /// the trace is empty).
     #[inline]
     pub(crate) fn try_add_context(&mut self, loc: &'static Location<'static>, context: AtContext) {
-        // If empty, push a location first
-        let idx = if self.locations.is_empty() {
-            if !try_push_location(&mut self.locations, Some(loc)) {
-                return;
-            }
-            0u16
-        } else {
-            (self.locations.len() - 1).min(u16::MAX as usize) as u16
+        let Some(idx) = self.last_index_or_push(loc) else {
+            return;
         };
+        if !captures_contexts() && !context.is_crate_boundary() && !context.is_correlation() {
+            return;
+        }
         // Try to push context; silently fail on OOM
         let _ = try_push_context(&mut self.contexts, (idx, context));
     }
 
+    /// Like [`try_add_context`](Self::try_add_context), but builds the context lazily.
+    ///
+    /// The closure is not called when contexts are compiled out
+    /// (`trace-locations-only`), so user closures passed to `at_string()` etc.
+    /// never run. A `None` result (e.g. failed boxing) adds nothing.
+    #[inline]
+    pub(crate) fn try_add_context_with(
+        &mut self,
+        loc: &'static Location<'static>,
+        f: impl FnOnce() -> Option<AtContext>,
+    ) {
+        let Some(idx) = self.last_index_or_push(loc) else {
+            return;
+        };
+        if !captures_contexts() {
+            return;
+        }
+        if let Some(context) = f() {
+            let _ = try_push_context(&mut self.contexts, (idx, context));
+        }
+    }
+
+    /// Index of the last location, pushing `loc` first if the trace is empty.
+    /// Returns None if the push fails.
+    #[inline]
+    fn last_index_or_push(&mut self, loc: &'static Location<'static>) -> Option<u16> {
+        if CAPTURE_OFF || !crate::config::is_enabled() || self.is_rate_limited() {
+            return None;
+        }
+        if self.locations.is_empty() {
+            if !self.try_capture(Some(loc)) {
+                return None;
+            }
+            Some(0)
+        } else {
+            Some((self.locations.len() - 1).min(u16::MAX as usize) as u16)
+        }
+    }
+
     /// Iterate over all location entries, oldest first.
     /// Returns Option where None = skipped frame marker.
     #[inline]
@@ -403,44 +922,393 @@ impl AtTrace {
         })
     }
 
-    /// Get the number of frames in the trace.
+    /// Get the frame at `index` (0 is the origin), or None if out of range.
     #[inline]
-    pub fn frame_count(&self) -> usize {
-        self.locations.len()
+    pub fn frame(&self, index: usize) -> Option<AtFrame<'_>> {
+        self.locations.get(index).map(|loc| AtFrame {
+            location: loc,
+            trace: self,
+            index,
+        })
     }
 
-    /// Check if the trace is empty.
+    /// Iterate over every context with the index of the frame it is attached to.
+    ///
+    /// Entries come in attachment order (oldest first), which is also their
+    /// order within each frame. Frame indices match [`AtFrame::index()`], so
+    /// this gives the raw frame/context association in a single pass.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let mut err: At<MyError> = at(MyError).at_str("reading").at().at_str("parsing");
+    /// let trace = err.take_trace().unwrap();
+    /// let entries: Vec<_> = trace
+    ///     .context_entries()
+    ///     .map(|(frame, ctx)| (frame, ctx.as_text().unwrap()))
+    ///     .collect();
+    /// assert_eq!(entries, [(0, "reading"), (1, "parsing")]);
+    /// assert_eq!(trace.frame(1).unwrap().contexts().count(), 1);
+    /// ```
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.locations.is_empty()
+    pub fn context_entries(&self) -> impl DoubleEndedIterator<Item = (usize, AtContextRef<'_>)> {
+        context_iter(&self.contexts).map(|(i, ctx)| (*i as usize, AtContextRef { inner: ctx }))
     }
 
-    /// Take the entire trace, leaving self empty.
+    /// Iterate over every context with its frame index and location, in the given order.
     ///
-    /// Preserves crate_info in self (not transferred).
+    /// Each item is `(frame_index, location, context)`. The location is `None`
+    /// for contexts on a `[...]` skipped-frame marker. Unlike mixing
+    /// [`contexts()`](crate::At::contexts) (newest first) with
+    /// [`frames()`](Self::frames) (oldest first), both the order and the
+    /// association are explicit, which is what exporters need.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtOrder};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError).at_str("reading").at().at_str("parsing");
+    /// let trace = err.trace().unwrap();
+    /// let newest: Vec<_> = trace
+    ///     .contexts_with_frames(AtOrder::NewestFirst)
+    ///     .map(|(frame, loc, ctx)| (frame, loc.unwrap().file(), ctx.as_text().unwrap()))
+    ///     .collect();
+    /// assert_eq!(newest, [(1, file!(), "parsing"), (0, file!(), "reading")]);
+    /// ```
+    pub fn contexts_with_frames(
+        &self,
+        order: AtOrder,
+    ) -> impl ExactSizeIterator<Item = (usize, Option<&'static Location<'static>>, AtContextRef<'_>)>
+    {
+        let entries: &[ContextEntry] = self.contexts.as_deref().map_or(&[], |v| v.as_slice());
+        let len = entries.len();
+        (0..len).map(move |k| {
+            let (index, ctx) = match order {
+                AtOrder::OldestFirst => &entries[k],
+                AtOrder::NewestFirst => &entries[len - 1 - k],
+            };
+            let index = *index as usize;
+            let location = self.locations.get(index).flatten();
+            (index, location, AtContextRef { inner: ctx })
+        })
+    }
+
+    /// Get the number of frames in the trace.
     #[inline]
-    pub fn take(&mut self) -> AtTrace {
-        AtTrace {
-            locations: core::mem::take(&mut self.locations),
-            crate_info: self.crate_info, // Copy, don't move
-            contexts: core::mem::take(&mut self.contexts),
-        }
+    pub fn frame_count(&self) -> usize {
+        self.locations.len()
     }
 
-    /// Pop the most recent location and its contexts from the end.
+    /// A stable 64-bit hash of the frame locations (file, line, column).
     ///
-    /// Returns `None` if the trace is empty.
-    #[inline]
-    pub fn pop(&mut self) -> Option<AtFrameOwned> {
-        if self.locations.is_empty() {
-            return None;
+    /// Contexts are ignored, so errors that took the same path hash equal even
+    /// when their messages differ. The value is stable across runs and
+    /// platforms, but changes when the code at any frame moves.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, At};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError(u32);
+    ///
+    /// fn fail(id: u32) -> At<MyError> {
+    ///     at(MyError(id)).at_string(|| format!("id {}", id))
+    /// }
+    ///
+    /// let (a, b) = (fail(1), fail(2));
+    /// assert_eq!(a.trace().unwrap().fingerprint(), b.trace().unwrap().fingerprint());
+    /// assert_ne!(a.trace().unwrap().fingerprint(), fail(1).at().trace().unwrap().fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = Fnv1a::new();
+        for loc in self.locations.iter() {
+            match loc {
+                Some(loc) => {
+                    hash.write(&(loc.file().len() as u64).to_le_bytes());
+                    hash.write(loc.file().as_bytes());
+                    hash.write(&loc.line().to_le_bytes());
+                    hash.write(&loc.column().to_le_bytes());
+                }
+                None => hash.write(&u64::MAX.to_le_bytes()),
+            }
         }
+        hash.finish()
+    }
 
-        let last_idx = (self.locations.len() - 1) as u16;
-        let location = self.locations.pop()?;
-
-        // Collect contexts for this location (they're stored newest-first in usage,
-        // but we need to extract those with matching index)
+    /// Format the trace on its own, with [`AtTraceFormat`] options applied.
+    ///
+    /// `{}` on an `AtTrace` uses the default options. Each frame is written as
+    /// `    at file:line:col` followed by its contexts, exactly as
+    /// [`full_trace()`](AtTraceable::full_trace) shows them under an error
+    /// message. Useful for traces stored without an error, e.g. captured for
+    /// an operation that succeeded but ran slowly.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{AtContextOwned, AtLevel, AtTrace, AtTraceFormat};
+    ///
+    /// let mut trace = AtTrace::capture();
+    /// trace.annotate(0, AtContextOwned::text("query took 2.3s"));
+    /// trace.annotate(0, AtContextOwned::leveled(AtLevel::Note, "retried once"));
+    ///
+    /// let text = trace.to_string();
+    /// assert!(text.contains("    at "));
+    /// assert!(text.contains("\n        query took 2.3s"));
+    ///
+    /// let quiet = AtTraceFormat::new().min_level(AtLevel::Warn);
+    /// assert!(!trace.display_with(quiet).to_string().contains("retried once"));
+    /// ```
+    pub fn display_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+        AtTraceDisplay {
+            trace: Some(self),
+            format,
+        }
+    }
+
+    /// Counts and approximate memory use of this trace, in one value.
+    ///
+    /// See [`At::trace_stats()`](crate::At::trace_stats).
+    pub fn stats(&self) -> AtTraceStats {
+        let mut stats = AtTraceStats {
+            frames: self.locations.len(),
+            skipped_frames: self.locations.iter().filter(Option::is_none).count(),
+            contexts: [0; AtContextKind::ALL.len()],
+            heap_bytes: core::mem::size_of::<Self>() + self.locations.heap_bytes(),
+        };
+        if let Some(contexts) = &self.contexts {
+            stats.heap_bytes += core::mem::size_of::<Vec<ContextEntry>>()
+                + contexts.capacity() * core::mem::size_of::<ContextEntry>();
+            for (_, ctx) in contexts.iter() {
+                stats.contexts[AtContextRef { inner: ctx }.kind() as usize] += 1;
+                stats.heap_bytes += ctx.heap_bytes();
+            }
+        }
+        stats
+    }
+
+    /// Check if the trace is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Attach context to an existing frame, oldest first (index 0 is the origin).
+    ///
+    /// Unlike `at_str()` and friends, which always target the last frame, this lets
+    /// post-processing put data on the frame where it logically belongs.
+    /// The context is placed after any contexts already on that frame.
+    ///
+    /// Returns `false` (dropping the context) if `frame_index` is out of range,
+    /// the frame is a skipped-frames marker, [`AT_MAX_CONTEXTS`] is reached, or
+    /// contexts are compiled out (`trace-locations-only`).
+    pub fn annotate(&mut self, frame_index: usize, context: AtContextOwned) -> bool {
+        if !captures_contexts() || !matches!(self.locations.get(frame_index), Some(Some(_))) {
+            return false;
+        }
+        self.try_insert_context(frame_index, context.inner)
+    }
+
+    /// Add context to the oldest real frame, or push `loc` first if the trace is empty.
+    ///
+    /// Used by `at_origin_str()`. Skipped-frame markers are passed over.
+    #[inline]
+    pub(crate) fn try_add_origin_context(
+        &mut self,
+        loc: &'static Location<'static>,
+        f: impl FnOnce() -> AtContext,
+    ) {
+        if CAPTURE_OFF || !crate::config::is_enabled() || self.is_rate_limited() {
+            return;
+        }
+        let origin = self.locations.iter().position(|l| l.is_some());
+        match origin {
+            Some(idx) if captures_contexts() => {
+                let _ = self.try_insert_context(idx, f());
+            }
+            Some(_) => {}
+            None => self.try_add_context_with(loc, || Some(f())),
+        }
+    }
+
+    /// Collapse runs of identical consecutive locations into one frame.
+    ///
+    /// Recursion and loops that call `.at()` at the same site push the same
+    /// location over and over. Each run keeps its first frame, which takes the
+    /// contexts of the frames it absorbs and records how many it stands for;
+    /// formatters show the count on the location line as `(x17)`. Counts add
+    /// up when a trace is compressed again. Skipped-frame markers are never
+    /// merged.
+    ///
+    /// Returns the number of frames removed. On allocation failure the trace
+    /// is left unchanged and 0 is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use core::panic::Location;
+    /// use whereat::AtTrace;
+    ///
+    /// #[track_caller]
+    /// fn here() -> &'static Location<'static> {
+    ///     Location::caller()
+    /// }
+    ///
+    /// let (origin, retry) = (here(), here());
+    /// let mut trace = AtTrace::from_locations([origin, retry, retry, retry]);
+    /// assert_eq!(trace.compress(), 2);
+    ///
+    /// let counts: Vec<_> = trace.frames().map(|f| f.repeat_count()).collect();
+    /// assert_eq!(counts, [1, 3]);
+    /// assert!(trace.to_string().contains(&format!(":{} (x3)", retry.column())));
+    /// ```
+    pub fn compress(&mut self) -> usize {
+        let len = self.locations.len();
+        let mut remap: Vec<u16> = Vec::new();
+        let mut counts: Vec<u32> = Vec::new();
+        if remap.try_reserve_exact(len).is_err() || counts.try_reserve_exact(len).is_err() {
+            return 0;
+        }
+        let mut previous = None;
+        for (i, loc) in self.locations.iter().enumerate() {
+            let count = self
+                .contexts_at(i)
+                .find_map(AtContext::repeat_count)
+                .unwrap_or(1);
+            if loc.is_some() && loc == previous {
+                if let Some(total) = counts.last_mut() {
+                    *total = total.saturating_add(count);
+                }
+                remap.push((counts.len() - 1) as u16);
+            } else {
+                remap.push(counts.len().min(u16::MAX as usize) as u16);
+                counts.push(count);
+            }
+            previous = loc;
+        }
+        let removed = len - counts.len();
+        if removed == 0 {
+            return 0;
+        }
+
+        for i in (1..len).rev() {
+            if remap[i] == remap[i - 1] {
+                self.locations.remove(i);
+            }
+        }
+        if let Some(entries) = &mut self.contexts {
+            entries.retain(|(_, ctx)| ctx.repeat_count().is_none());
+            for (idx, _) in entries.iter_mut() {
+                if let Some(&new) = remap.get(*idx as usize) {
+                    *idx = new;
+                }
+            }
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            if count > 1 {
+                let _ = self.try_insert_context(i, AtContext::Repeated(count));
+            }
+        }
+        removed
+    }
+
+    /// How many frames the frame at `idx` stands for after [`compress()`](Self::compress).
+    #[inline]
+    pub(crate) fn repeat_count(&self, idx: usize) -> usize {
+        self.contexts_at(idx)
+            .find_map(AtContext::repeat_count)
+            .map_or(1, |n| n as usize)
+    }
+
+    /// Insert context for `frame_index`, after any contexts already on that frame.
+    fn try_insert_context(&mut self, frame_index: usize, context: AtContext) -> bool {
+        if frame_index > u16::MAX as usize {
+            return false;
+        }
+        let idx = frame_index as u16;
+        let entries = self.contexts.get_or_insert_with(|| Box::new(Vec::new()));
+        if entries.len() >= AT_MAX_CONTEXTS || entries.try_reserve(1).is_err() {
+            return false;
+        }
+        // Keep entries grouped by frame index so pop() finds the last frame's contexts at the end
+        let pos = entries.partition_point(|(i, _)| *i <= idx);
+        entries.insert(pos, (idx, context));
+        true
+    }
+
+    /// Remove all contexts, keeping the locations.
+    ///
+    /// Returns `(frame_index, context)` pairs, oldest first. Crate boundary
+    /// markers stay in the trace because links for later frames depend on them,
+    /// and so do the repeat counts left by [`compress()`](Self::compress).
+    ///
+    /// Use this at a boundary that moves rich context into a response or report
+    /// and passes a slimmer error onward.
+    pub fn drain_contexts(&mut self) -> Vec<(usize, AtContextOwned)> {
+        let Some(entries) = self.contexts.as_mut() else {
+            return Vec::new();
+        };
+        let mut drained = Vec::new();
+        let mut kept = Vec::new();
+        for (idx, ctx) in entries.drain(..) {
+            if ctx.is_crate_boundary() || ctx.repeat_count().is_some() {
+                kept.push((idx, ctx));
+            } else {
+                drained.push((idx as usize, AtContextOwned { inner: ctx }));
+            }
+        }
+        if kept.is_empty() {
+            self.contexts = None;
+        } else {
+            **entries = kept;
+        }
+        drained
+    }
+
+    /// Take the entire trace, leaving self empty.
+    ///
+    /// Preserves crate_info in self (not transferred).
+    #[inline]
+    pub fn take(&mut self) -> AtTrace {
+        AtTrace {
+            locations: core::mem::take(&mut self.locations),
+            crate_info: self.crate_info, // Copy, don't move
+            contexts: core::mem::take(&mut self.contexts),
+            #[cfg(feature = "error-id")]
+            error_id: self.error_id.take(),
+            #[cfg(feature = "tokio")]
+            task_id: self.task_id.take(),
+            #[cfg(feature = "tokio")]
+            crossed_tasks: core::mem::take(&mut self.crossed_tasks),
+            state: core::mem::replace(&mut self.state, TraceState::Active),
+        }
+    }
+
+    /// Pop the most recent location and its contexts from the end.
+    ///
+    /// Returns `None` if the trace is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<AtFrameOwned> {
+        if self.locations.is_empty() {
+            return None;
+        }
+
+        let last_idx = (self.locations.len() - 1) as u16;
+        let location = self.locations.pop()?;
+
+        // Collect contexts for this location (they're stored newest-first in usage,
+        // but we need to extract those with matching index)
         let mut contexts = Vec::new();
         if let Some(ref mut ctx_vec) = self.contexts {
             // Count matching contexts first for reservation
@@ -468,12 +1336,12 @@ impl AtTrace {
     /// Push a segment (location + contexts) to the end of the trace.
     #[inline]
     pub fn push(&mut self, segment: AtFrameOwned) {
-        let idx = self.locations.len() as u16;
-
         // Try to push location
-        if !try_push_location(&mut self.locations, segment.location) {
+        if !self.try_store(segment.location) {
             return;
         }
+        // Index after the push: a full trace folds middle frames to make room
+        let idx = (self.locations.len() - 1).min(u16::MAX as usize) as u16;
 
         // Push contexts
         for ctx in segment.contexts {
@@ -575,6 +1443,56 @@ impl AtTrace {
             self.push_first(seg);
         }
     }
+
+    /// Combine another trace into this one according to `policy`.
+    ///
+    /// For joining errors from concurrent branches (e.g. `join_all`), where a
+    /// flat [`append()`](Self::append) makes it unclear which frames came from
+    /// which branch. [`Grouped`](AtMergePolicy::Grouped) puts a separator
+    /// frame (a skipped-frames marker with a `trace 1` or `trace 2` context)
+    /// before each trace's frames.
+    ///
+    /// The source trace is consumed; its crate info is not transferred.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{AtFrameOwned, AtMergePolicy, AtTrace};
+    ///
+    /// let mut left = AtTrace::from_frames([AtFrameOwned::capture(), AtFrameOwned::capture()]);
+    /// let right = AtTrace::from_frames([AtFrameOwned::capture()]);
+    /// left.merge(right, AtMergePolicy::Grouped);
+    ///
+    /// let labels: Vec<_> = left
+    ///     .frames()
+    ///     .filter(|f| f.is_skipped())
+    ///     .map(|f| f.contexts().next().unwrap().as_text().unwrap())
+    ///     .collect();
+    /// assert_eq!(labels, ["trace 1", "trace 2"]);
+    /// assert_eq!(left.frame_count(), 5);
+    /// ```
+    pub fn merge(&mut self, other: AtTrace, policy: AtMergePolicy) {
+        match policy {
+            AtMergePolicy::Append => self.append(other),
+            AtMergePolicy::Grouped => {
+                if !self.is_empty() {
+                    self.push_first(merge_separator(1));
+                }
+                if !other.is_empty() {
+                    self.push(merge_separator(2));
+                    self.append(other);
+                }
+            }
+        }
+    }
+}
+
+/// The separator frame [`AtTrace::merge()`] puts before frames from `source`.
+fn merge_separator(source: u8) -> AtFrameOwned {
+    AtFrameOwned::new(None).with_str(match source {
+        1 => "trace 1",
+        _ => "trace 2",
+    })
 }
 
 impl Default for AtTrace {
@@ -672,6 +1590,13 @@ impl AtFrameOwned {
         }
         self
     }
+
+    /// Add a prebuilt context.
+    #[inline]
+    pub fn with_context(mut self, context: AtContextOwned) -> Self {
+        self.contexts.push(context.inner);
+        self
+    }
 }
 
 // ============================================================================
@@ -718,13 +1643,19 @@ impl<'a> AtFrame<'a> {
         self.location
     }
 
+    /// Position of this frame in the trace: 0 is the origin, increasing toward the caller.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// Check if this frame is a skipped-frames marker (`[...]`).
     #[inline]
     pub fn is_skipped(&self) -> bool {
         self.location.is_none()
     }
 
-    /// Iterate over contexts attached to this frame.
+    /// Iterate over contexts attached to this frame, in attachment order.
     #[inline]
     pub fn contexts(&self) -> impl Iterator<Item = AtContextRef<'a>> {
         let idx = self.index;
@@ -733,12 +1664,61 @@ impl<'a> AtFrame<'a> {
             .map(|(_, ctx)| AtContextRef { inner: ctx })
     }
 
+    /// The crate this frame belongs to: the latest crate boundary at or before
+    /// this frame, else the trace's own crate info.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{At, AtCrateInfo};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// static DEP: AtCrateInfo = AtCrateInfo::builder().name("dep").build();
+    /// static APP: AtCrateInfo = AtCrateInfo::builder().name("app").build();
+    ///
+    /// let err = At::wrap(MyError).set_crate_info(&DEP).at().at().at_crate(&APP).at();
+    /// let names: Vec<_> = err.frames().map(|f| f.crate_info().unwrap().name()).collect();
+    /// assert_eq!(names, ["dep", "app", "app"]);
+    /// ```
+    pub fn crate_info(&self) -> Option<&'static AtCrateInfo> {
+        let idx = self.index;
+        context_iter(&self.trace.contexts)
+            .rev()
+            .filter(|(i, _)| *i as usize <= idx)
+            .find_map(|(_, ctx)| match ctx {
+                AtContext::Crate(info) => Some(*info),
+                _ => None,
+            })
+            .or_else(|| self.trace.crate_info())
+    }
+
+    /// How many frames this one stands for: 1 unless the trace was compressed
+    /// with [`At::dedup_trace()`](crate::At::dedup_trace). For a skipped-frames
+    /// marker left by the [`AT_MAX_FRAMES`] limit, the number of frames omitted.
+    #[inline]
+    pub fn repeat_count(&self) -> usize {
+        self.trace.repeat_count(self.index)
+    }
+
     /// Check if this frame has any contexts.
     #[inline]
     pub fn has_contexts(&self) -> bool {
         let idx = self.index;
         context_iter(&self.trace.contexts).any(|(i, _)| *i as usize == idx)
     }
+
+    /// The id of the `tracing` span that was current when this frame was captured.
+    ///
+    /// Only recorded after [`set_record_span_ids(true)`](crate::set_record_span_ids).
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn span_id(&self) -> Option<u64> {
+        self.trace
+            .contexts_at(self.index)
+            .find_map(AtContext::as_span_id)
+    }
 }
 
 impl fmt::Debug for AtFrame<'_> {
@@ -751,11 +1731,118 @@ impl fmt::Debug for AtFrame<'_> {
                 }
                 Ok(())
             }
-            None => write!(f, "[...]"),
+            None => write!(f, "{}", SkipMarker(self.repeat_count())),
         }
     }
 }
 
+// ============================================================================
+// AtOrder - Iteration order for context listings
+// ============================================================================
+
+/// Iteration order for [`AtTrace::contexts_with_frames()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AtOrder {
+    /// Attachment order: origin frame first, as in [`frames()`](AtTrace::frames).
+    #[default]
+    OldestFirst,
+    /// Reverse attachment order, as in [`At::contexts()`](crate::At::contexts).
+    NewestFirst,
+}
+
+// ============================================================================
+// AtMergePolicy - How two traces are combined
+// ============================================================================
+
+/// How [`AtTrace::merge()`] combines two traces.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AtMergePolicy {
+    /// The other trace's frames after this trace's, as in [`AtTrace::append()`].
+    #[default]
+    Append,
+    /// Like `Append`, with a separator frame before each trace's frames.
+    Grouped,
+}
+
+// ============================================================================
+// AtTraceStats - Summary counts for a trace
+// ============================================================================
+
+/// Frame and context counts plus approximate heap use of a trace.
+///
+/// Returned by [`At::trace_stats()`](crate::At::trace_stats) and
+/// [`AtTrace::stats()`]. Meant for tests that bound what escapes a layer,
+/// and for spotting traces that grew unexpectedly large.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, AtContextKind};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// let err = at(MyError).at_str("loading").at().at_debug(|| 42);
+/// let stats = err.trace_stats();
+/// assert_eq!(stats.frames(), 2);
+/// assert_eq!(stats.contexts(), 2);
+/// assert_eq!(stats.contexts_of(AtContextKind::Debug), 1);
+/// assert!(stats.heap_bytes() > 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AtTraceStats {
+    frames: usize,
+    skipped_frames: usize,
+    /// Indexed by `AtContextKind as usize`.
+    contexts: [usize; AtContextKind::ALL.len()],
+    heap_bytes: usize,
+}
+
+impl AtTraceStats {
+    /// Number of frames, including `[...]` skipped-frame markers.
+    #[inline]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Number of `[...]` markers left by [`at_skipped_frames()`](crate::At::at_skipped_frames).
+    #[inline]
+    pub fn skipped_frames(&self) -> usize {
+        self.skipped_frames
+    }
+
+    /// Total number of contexts, of any kind.
+    #[inline]
+    pub fn contexts(&self) -> usize {
+        self.contexts.iter().sum()
+    }
+
+    /// Number of contexts of one kind.
+    #[inline]
+    pub fn contexts_of(&self, kind: AtContextKind) -> usize {
+        self.contexts[kind as usize]
+    }
+
+    /// Context counts by kind, omitting kinds with no contexts.
+    pub fn contexts_by_kind(&self) -> impl Iterator<Item = (AtContextKind, usize)> + '_ {
+        AtContextKind::ALL
+            .iter()
+            .map(|&kind| (kind, self.contexts_of(kind)))
+            .filter(|&(_, count)| count > 0)
+    }
+
+    /// Approximate heap bytes held by the trace.
+    ///
+    /// Counts the trace allocation, spilled frame storage, the context list
+    /// and each context's boxed or owned payload. Heap memory owned *inside*
+    /// typed payloads (a `Vec` passed to `at_debug()`, say) is not included,
+    /// nor is allocator overhead.
+    #[inline]
+    pub fn heap_bytes(&self) -> usize {
+        self.heap_bytes
+    }
+}
+
 // ============================================================================
 // AtTraceBoxed - Boxed optional trace for small error footprint
 // ============================================================================
@@ -782,6 +1869,9 @@ impl AtTraceBoxed {
     #[track_caller]
     #[inline]
     pub fn capture() -> Self {
+        if CAPTURE_OFF {
+            return Self::new();
+        }
         Self(Some(Box::new(AtTrace::capture())))
     }
 
@@ -845,6 +1935,25 @@ impl AtTraceBoxed {
     pub fn crate_info(&self) -> Option<&'static AtCrateInfo> {
         self.0.as_ref().and_then(|t| t.crate_info())
     }
+
+    /// Get the error id, if a location has been captured.
+    #[cfg(feature = "error-id")]
+    #[inline]
+    pub fn error_id(&self) -> Option<AtErrorId> {
+        self.0.as_ref().and_then(|t| t.error_id())
+    }
+}
+
+impl AtTraceBoxed {
+    /// Format the trace on its own; see [`AtTrace::display_with()`].
+    ///
+    /// Writes nothing if no trace is allocated.
+    pub fn display_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+        AtTraceDisplay {
+            trace: self.as_ref(),
+            format,
+        }
+    }
 }
 
 impl fmt::Debug for AtTraceBoxed {
@@ -1029,6 +2138,78 @@ pub trait AtTraceable: Sized {
     /// ```
     fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 
+    /// Remediation guidance for this error, printed by [`full_trace()`](Self::full_trace)
+    /// as a `help:` line after any hints added with [`at_help()`](Self::at_help).
+    ///
+    /// Defaults to `None`. Override it to keep guidance next to the error definition:
+    ///
+    /// ```rust
+    /// use whereat::{AtTrace, AtTraceable};
+    /// use core::fmt;
+    ///
+    /// enum Kind { MissingConfig, Timeout }
+    ///
+    /// struct MyError { kind: Kind, trace: AtTrace }
+    ///
+    /// impl AtTraceable for MyError {
+    ///     fn trace_mut(&mut self) -> &mut AtTrace { &mut self.trace }
+    ///     fn trace(&self) -> Option<&AtTrace> { Some(&self.trace) }
+    ///     fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("config error")
+    ///     }
+    ///     fn help(&self) -> Option<&str> {
+    ///         match self.kind {
+    ///             Kind::MissingConfig => Some("create one with `app init`"),
+    ///             Kind::Timeout => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let err = MyError { kind: Kind::MissingConfig, trace: AtTrace::capture() };
+    /// assert!(err.full_trace().to_string().ends_with("\n    help: create one with `app init`"));
+    /// ```
+    #[inline]
+    fn help(&self) -> Option<&str> {
+        None
+    }
+
+    /// A stable diagnostic code for this error, printed by `full_trace()` as a `code:` line.
+    ///
+    /// Codes can key a public error registry and `--explain` lookups (with
+    /// `std`, see `explain()`).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{AtTrace, AtTraceable};
+    /// use core::fmt;
+    ///
+    /// enum Kind { MissingConfig, Timeout }
+    ///
+    /// struct MyError { kind: Kind, trace: AtTrace }
+    ///
+    /// impl AtTraceable for MyError {
+    ///     fn trace_mut(&mut self) -> &mut AtTrace { &mut self.trace }
+    ///     fn trace(&self) -> Option<&AtTrace> { Some(&self.trace) }
+    ///     fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("config error")
+    ///     }
+    ///     fn error_code(&self) -> Option<&'static str> {
+    ///         match self.kind {
+    ///             Kind::MissingConfig => Some("E1003"),
+    ///             Kind::Timeout => Some("E2001"),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let err = MyError { kind: Kind::MissingConfig, trace: AtTrace::capture() };
+    /// assert!(err.full_trace().to_string().starts_with("config error\n    code: E1003\n"));
+    /// ```
+    #[inline]
+    fn error_code(&self) -> Option<&'static str> {
+        None
+    }
+
     /// Add the caller's location to the trace.
     #[track_caller]
     #[inline]
@@ -1041,19 +2222,30 @@ pub trait AtTraceable: Sized {
     #[track_caller]
     #[inline]
     fn at_str(mut self, msg: &'static str) -> Self {
-        let context = AtContext::Text(Cow::Borrowed(msg));
         self.trace_mut()
-            .try_add_context(Location::caller(), context);
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::Text(Cow::Borrowed(msg)))
+            });
         self
     }
 
-    /// Add a lazily-computed string context to the last location (or create one if empty).
+    /// Add a static string context to the oldest (origin) frame (or create one if empty).
     #[track_caller]
     #[inline]
-    fn at_string(mut self, f: impl FnOnce() -> String) -> Self {
-        let context = AtContext::Text(Cow::Owned(f()));
+    fn at_origin_str(mut self, msg: &'static str) -> Self {
         self.trace_mut()
-            .try_add_context(Location::caller(), context);
+            .try_add_origin_context(Location::caller(), || AtContext::Text(Cow::Borrowed(msg)));
+        self
+    }
+
+    /// Add a lazily-computed string context to the last location (or create one if empty).
+    #[track_caller]
+    #[inline]
+    fn at_string(mut self, f: impl FnOnce() -> String) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::Text(Cow::Owned(f())))
+            });
         self
     }
 
@@ -1061,13 +2253,10 @@ pub trait AtTraceable: Sized {
     #[track_caller]
     #[inline]
     fn at_data<T: fmt::Display + Send + Sync + 'static>(mut self, f: impl FnOnce() -> T) -> Self {
-        let ctx = f();
-        let Some(boxed_ctx) = try_box(ctx) else {
-            return self;
-        };
-        let context = AtContext::Display(boxed_ctx);
         self.trace_mut()
-            .try_add_context(Location::caller(), context);
+            .try_add_context_with(Location::caller(), || {
+                try_box(f()).map(|ctx| AtContext::Display(ctx))
+            });
         self
     }
 
@@ -1075,13 +2264,62 @@ pub trait AtTraceable: Sized {
     #[track_caller]
     #[inline]
     fn at_debug<T: fmt::Debug + Send + Sync + 'static>(mut self, f: impl FnOnce() -> T) -> Self {
-        let ctx = f();
-        let Some(boxed_ctx) = try_box(ctx) else {
-            return self;
-        };
-        let context = AtContext::Debug(boxed_ctx);
         self.trace_mut()
-            .try_add_context(Location::caller(), context);
+            .try_add_context_with(Location::caller(), || {
+                try_box(f()).map(|ctx| AtContext::Debug(ctx))
+            });
+        self
+    }
+
+    /// Add lazily-computed sensitive context, printed as `[redacted]` (see [`At::at_secret()`](crate::At::at_secret)).
+    #[track_caller]
+    #[inline]
+    fn at_secret<T: fmt::Debug + Send + Sync + 'static>(mut self, f: impl FnOnce() -> T) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                try_box(f()).map(|ctx| AtContext::Secret(ctx))
+            });
+        self
+    }
+
+    /// Attach an expected and an actual value (or create a location if empty).
+    ///
+    /// See [`At::at_expected_actual()`](crate::At::at_expected_actual).
+    #[track_caller]
+    #[inline]
+    fn at_expected_actual<T, U>(mut self, expected: T, actual: U) -> Self
+    where
+        T: fmt::Debug + Send + Sync + 'static,
+        U: fmt::Debug + Send + Sync + 'static,
+    {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                try_box(AtExpectedActual::new(expected, actual)).map(AtContext::ExpectedActual)
+            });
+        self
+    }
+
+    /// Attach a trace received from another process (or create a location if empty).
+    ///
+    /// See [`At::at_remote_trace()`](crate::At::at_remote_trace).
+    #[track_caller]
+    #[inline]
+    fn at_remote_trace(mut self, trace: crate::AtTraceDetached) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || try_box(trace).map(AtContext::Remote));
+        self
+    }
+
+    /// Attach a bounded copy of raw bytes (or create a location if empty).
+    ///
+    /// See [`At::at_bytes()`](crate::At::at_bytes).
+    #[track_caller]
+    #[inline]
+    fn at_bytes(mut self, label: &'static str, bytes: &[u8]) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                try_box(AtBytes::try_copy_from(label, bytes)?).map(AtContext::Bytes)
+            });
         self
     }
 
@@ -1091,12 +2329,10 @@ pub trait AtTraceable: Sized {
     #[track_caller]
     #[inline]
     fn at_error<E: core::error::Error + Send + Sync + 'static>(mut self, err: E) -> Self {
-        let Some(boxed_err) = try_box(err) else {
-            return self;
-        };
-        let context = AtContext::Error(boxed_err);
         self.trace_mut()
-            .try_add_context(Location::caller(), context);
+            .try_add_context_with(Location::caller(), || {
+                try_box(err).map(|err| AtContext::Error(err))
+            });
         self
     }
 
@@ -1112,6 +2348,177 @@ pub trait AtTraceable: Sized {
         self
     }
 
+    /// Attach an externally supplied correlation/request id to the last location.
+    ///
+    /// Retrieve it with [`correlation_id()`](Self::correlation_id).
+    #[track_caller]
+    #[inline]
+    fn at_correlation(mut self, id: impl Into<Cow<'static, str>>) -> Self {
+        let context = AtContext::Display(Box::new(AtCorrelationId::new(id)));
+        self.trace_mut()
+            .try_add_context(Location::caller(), context);
+        self
+    }
+
+    /// Attach a `key=value` pair to the last location (or create one if empty).
+    ///
+    /// See [`At::at_kv()`](crate::At::at_kv).
+    #[track_caller]
+    #[inline]
+    fn at_kv(mut self, key: &'static str, value: impl fmt::Display) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::KeyValue {
+                    key: Cow::Borrowed(key),
+                    value: Cow::Owned(alloc::format!("{}", value)),
+                })
+            });
+        self
+    }
+
+    /// Add a [`Note`](AtLevel::Note)-level breadcrumb to the last location.
+    ///
+    /// Hide notes in output with [`AtTraceFormat::min_level()`].
+    #[track_caller]
+    #[inline]
+    fn at_note(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::Leveled(AtLevel::Note, msg.into()))
+            });
+        self
+    }
+
+    /// Add a [`Warn`](AtLevel::Warn)-level context to the last location.
+    #[track_caller]
+    #[inline]
+    fn at_warn(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::Leveled(AtLevel::Warn, msg.into()))
+            });
+        self
+    }
+
+    /// Attach a "see also" link (runbook, dashboard, docs) to the last location.
+    ///
+    /// Renders as `see: label (url)`.
+    #[track_caller]
+    #[inline]
+    fn at_link(
+        mut self,
+        label: impl Into<Cow<'static, str>>,
+        url: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::Link {
+                    label: label.into(),
+                    url: url.into(),
+                })
+            });
+        self
+    }
+
+    /// Attach an actionable remediation hint to the last location.
+    ///
+    /// Formatters show hints in a `help:` section after the trace.
+    #[track_caller]
+    #[inline]
+    fn at_help(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || {
+                Some(AtContext::Suggestion(msg.into()))
+            });
+        self
+    }
+
+    /// Capture a full backtrace, shown after the frames (or create a location if empty).
+    ///
+    /// See [`At::at_backtrace()`](crate::At::at_backtrace).
+    #[cfg(feature = "std-backtrace")]
+    #[track_caller]
+    #[inline]
+    fn at_backtrace(mut self) -> Self {
+        let trace = self.trace_mut();
+        if !trace.has_backtrace() {
+            trace.try_add_context_with(Location::caller(), || {
+                let backtrace = try_box(std::backtrace::Backtrace::force_capture())?;
+                Some(AtContext::Backtrace(backtrace))
+            });
+        }
+        self
+    }
+
+    /// Attach a high-priority message, shown directly under the error header.
+    #[track_caller]
+    #[inline]
+    fn at_pinned(mut self, msg: impl Into<Cow<'static, str>>) -> Self {
+        self.trace_mut()
+            .try_add_context_with(Location::caller(), || Some(AtContext::Pinned(msg.into())));
+        self
+    }
+
+    /// Add the caller's location to the trace only if `condition` is true.
+    #[track_caller]
+    #[inline]
+    fn at_if(self, condition: bool) -> Self {
+        if condition { self.at() } else { self }
+    }
+
+    /// Add a static string context to the last location only if `condition` is true.
+    #[track_caller]
+    #[inline]
+    fn at_str_if(self, condition: bool, msg: &'static str) -> Self {
+        if condition { self.at_str(msg) } else { self }
+    }
+
+    /// Add the caller's location to the trace in debug builds only.
+    /// A no-op when `debug_assertions` are off.
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at(self) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.at()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            self
+        }
+    }
+
+    /// Add a static string context to the last location in debug builds only.
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at_str(self, msg: &'static str) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.at_str(msg)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = msg;
+            self
+        }
+    }
+
+    /// Add a lazily-computed string context to the last location in debug builds only.
+    /// The closure is never called in release builds.
+    #[track_caller]
+    #[inline(always)]
+    fn debug_at_string(self, f: impl FnOnce() -> String) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            self.at_string(f)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = f;
+            self
+        }
+    }
+
     /// Add a skip marker to indicate skipped frames.
     /// Displayed as `[...]` in trace output.
     #[doc(hidden)]
@@ -1191,6 +2598,22 @@ pub trait AtTraceable: Sized {
     // Trace manipulation methods
     // ========================================================================
 
+    /// Attach context to an existing frame (index 0 is the origin).
+    ///
+    /// See [`AtTrace::annotate()`].
+    #[inline]
+    fn annotate(&mut self, frame_index: usize, context: AtContextOwned) -> bool {
+        self.trace_mut().annotate(frame_index, context)
+    }
+
+    /// Remove all contexts (except crate boundaries), keeping the locations.
+    ///
+    /// See [`AtTrace::drain_contexts()`].
+    #[inline]
+    fn take_contexts(&mut self) -> Vec<(usize, AtContextOwned)> {
+        self.trace_mut().drain_contexts()
+    }
+
     /// Pop the most recent location and its contexts from the trace.
     #[inline]
     fn at_pop(&mut self) -> Option<AtFrameOwned> {
@@ -1243,6 +2666,110 @@ pub trait AtTraceable: Sized {
         crate::At::from_parts(error, trace)
     }
 
+    /// Iterate over text contexts (`at_str`, `at_string`), newest first.
+    #[inline]
+    fn texts(&self) -> impl Iterator<Item = &str> {
+        self.trace()
+            .into_iter()
+            .flat_map(|t| t.contexts())
+            .filter_map(|ctx| ctx.as_text())
+    }
+
+    /// Iterate over attached source errors (`at_error`), newest first.
+    #[inline]
+    fn errors(&self) -> impl Iterator<Item = &(dyn core::error::Error + Send + Sync)> {
+        self.trace()
+            .into_iter()
+            .flat_map(|t| t.contexts())
+            .filter_map(|ctx| ctx.as_error())
+    }
+
+    /// Iterate over `(label, url)` links (`at_link`), newest first.
+    #[inline]
+    fn links(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.trace()
+            .into_iter()
+            .flat_map(|t| t.contexts())
+            .filter_map(|ctx| ctx.as_link())
+    }
+
+    /// Iterate over `(key, value)` pairs (`at_kv`), newest first.
+    #[inline]
+    fn kvs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.trace()
+            .into_iter()
+            .flat_map(|t| t.contexts())
+            .filter_map(|ctx| ctx.as_kv())
+    }
+
+    /// The value of the newest `key` pair (`at_kv`), if any.
+    #[inline]
+    fn get_context_value(&self, key: &str) -> Option<&str> {
+        self.kvs().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// The newest typed context of type `T`, from any frame; see [`At::find_context()`](crate::At::find_context).
+    #[inline]
+    fn find_context<T: 'static>(&self) -> Option<&T> {
+        self.find_all_contexts().next()
+    }
+
+    /// Every typed context of type `T`, from all frames, newest first.
+    #[inline]
+    fn find_all_contexts<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.trace()
+            .into_iter()
+            .flat_map(|t| t.contexts())
+            .filter_map(|ctx| ctx.downcast_ref())
+    }
+
+    /// Iterate over crate boundary markers (`at_crate`), newest first.
+    #[inline]
+    fn crates(&self) -> impl Iterator<Item = &'static AtCrateInfo> + '_ {
+        self.trace()
+            .into_iter()
+            .flat_map(|t| t.contexts())
+            .filter_map(|ctx| ctx.as_crate_info())
+    }
+
+    /// Iterate over `(frame_index, location, context)`; see [`AtTrace::contexts_with_frames()`].
+    #[inline]
+    fn contexts_with_frames(
+        &self,
+        order: AtOrder,
+    ) -> impl Iterator<Item = (usize, Option<&'static Location<'static>>, AtContextRef<'_>)> {
+        self.trace()
+            .into_iter()
+            .flat_map(move |t| t.contexts_with_frames(order))
+    }
+
+    /// Get the most recently attached correlation id, if any.
+    #[inline]
+    fn correlation_id(&self) -> Option<&str> {
+        self.trace().and_then(|t| t.correlation_id())
+    }
+
+    /// Get the unique id assigned when the trace captured its first location.
+    #[cfg(feature = "error-id")]
+    #[inline]
+    fn error_id(&self) -> Option<AtErrorId> {
+        self.trace().and_then(|t| t.error_id())
+    }
+
+    /// Get the id of the tokio task the trace captured its first location on.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    fn task_id(&self) -> Option<tokio::task::Id> {
+        self.trace().and_then(|t| t.task_id())
+    }
+
+    /// Check whether the trace captured locations on more than one tokio task.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    fn crossed_task_boundary(&self) -> bool {
+        self.trace().is_some_and(|t| t.crossed_task_boundary())
+    }
+
     // ========================================================================
     // Formatting methods
     // ========================================================================
@@ -1289,7 +2816,15 @@ pub trait AtTraceable: Sized {
     /// //         while loading
     /// ```
     fn full_trace(&self) -> impl fmt::Display + '_ {
-        FullTraceDisplay { error: self }
+        self.full_trace_with(AtTraceFormat::DEFAULT)
+    }
+
+    /// Like [`full_trace()`](Self::full_trace), with [`AtTraceFormat`] options applied.
+    fn full_trace_with(&self, format: AtTraceFormat) -> impl fmt::Display + '_ {
+        FullTraceDisplay {
+            error: self,
+            format,
+        }
     }
 
     /// Format with trace locations only (message + locations, no context strings).
@@ -1336,6 +2871,20 @@ pub trait AtTraceable: Sized {
         LastErrorTraceDisplay { error: self }
     }
 
+    /// Format with consecutive frames in the same file grouped onto one line.
+    ///
+    /// Like [`last_error_trace()`](Self::last_error_trace), contexts are omitted.
+    /// Errors that bounce around inside one module read much shorter:
+    ///
+    /// ```text
+    /// something failed
+    ///     at src/db.rs: lines 42 → 88 → 130
+    ///     at src/api.rs: line 17
+    /// ```
+    fn grouped_trace(&self) -> impl fmt::Display + '_ {
+        GroupedTraceDisplay { error: self }
+    }
+
     /// Format just the error message (no trace).
     ///
     /// Returns a formatter that only displays the error message via `fmt_message`.
@@ -1349,65 +2898,304 @@ pub trait AtTraceable: Sized {
 // Trace formatters for AtTraceable
 // ============================================================================
 
+/// Write the `task:` line of `full_trace()`, if a tokio task was recorded.
+#[cfg(feature = "tokio")]
+pub(crate) fn write_task_line(f: &mut fmt::Formatter<'_>, trace: &AtTrace) -> fmt::Result {
+    match (trace.task_id(), trace.crossed_task_boundary()) {
+        (Some(id), false) => write!(f, "\n    task: {}", id),
+        (Some(id), true) => write!(f, "\n    task: {} (crossed task boundary)", id),
+        (None, true) => write!(f, "\n    task: crossed task boundary"),
+        (None, false) => Ok(()),
+    }
+}
+
+/// Write the `backtrace:` section, if `at_backtrace()` captured one.
+///
+/// Each line is written between `line_start` and `line_end`, so the section
+/// fits both `\n`-prefixed and `\n`-terminated formatters.
+pub(crate) fn write_backtrace<W: fmt::Write>(
+    f: &mut W,
+    trace: &AtTrace,
+    line_start: &str,
+    line_end: &str,
+) -> fmt::Result {
+    let Some((_, backtrace)) = context_iter(&trace.contexts).find(|(_, ctx)| ctx.is_backtrace())
+    else {
+        return Ok(());
+    };
+    write!(f, "{}backtrace:{}", line_start, line_end)?;
+    let text = alloc::format!("{}", backtrace);
+    // Skip the context's own "backtrace:" header
+    for line in text.lines().skip(1) {
+        write!(f, "{}{}{}", line_start, line, line_end)?;
+    }
+    Ok(())
+}
+
+/// A skipped-frames marker: `[...]`, or `[... 17 frames omitted]` when the
+/// number of omitted frames is known.
+pub(crate) struct SkipMarker(pub(crate) usize);
+
+impl fmt::Display for SkipMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 | 1 => f.write_str("[...]"),
+            n => write!(f, "[... {} frames omitted]", n),
+        }
+    }
+}
+
+/// The note under a rate-limited trace's origin frame, e.g. `3 similar errors suppressed`.
+pub(crate) struct SuppressedNote(pub(crate) u32);
+
+impl fmt::Display for SuppressedNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => f.write_str("1 similar error suppressed"),
+            n => write!(f, "{} similar errors suppressed", n),
+        }
+    }
+}
+
+/// Repeat count of a compressed frame, written after its location as ` (x17)`.
+pub(crate) struct RepeatSuffix(pub(crate) usize);
+
+impl fmt::Display for RepeatSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 > 1 {
+            write!(f, " (x{})", self.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write frames with consecutive same-file locations merged into one line.
+///
+/// Shared by `At::grouped_trace()` and `AtTraceable::grouped_trace()`.
+pub(crate) fn write_grouped_frames(f: &mut fmt::Formatter<'_>, trace: &AtTrace) -> fmt::Result {
+    let mut frames = trace.frames().peekable();
+    while let Some(frame) = frames.next() {
+        let Some(loc) = frame.location() else {
+            write!(f, "\n    {}", SkipMarker(frame.repeat_count()))?;
+            continue;
+        };
+        let mut lines: Vec<u32> = Vec::new();
+        lines.push(loc.line());
+        while let Some(next) = frames
+            .peek()
+            .and_then(|frame| frame.location())
+            .filter(|next| next.file() == loc.file())
+        {
+            lines.push(next.line());
+            frames.next();
+        }
+        if lines.len() == 1 {
+            write!(f, "\n    at {}: line {}", loc.file(), loc.line())?;
+        } else {
+            write!(f, "\n    at {}: lines ", loc.file())?;
+            for (i, line) in lines.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " → ")?;
+                }
+                write!(f, "{}", line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Formatter that shows error message + full trace with all contexts.
 struct FullTraceDisplay<'a, E: AtTraceable> {
     error: &'a E,
+    format: AtTraceFormat,
 }
 
 impl<E: AtTraceable> fmt::Display for FullTraceDisplay<'_, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Show the error message
         self.error.fmt_message(f)?;
+        write_traceable_body(
+            f,
+            self.error.trace(),
+            &self.format,
+            self.error.error_code(),
+            self.error.help(),
+            true,
+        )
+    }
+}
 
-        // Show trace frames
-        if let Some(trace) = self.error.trace() {
-            // Track current crate for boundary display
-            let mut current_crate: Option<&str> = trace.crate_info().map(|i| i.name());
-
-            for frame in trace.frames() {
-                // Check for crate boundary before showing location
-                for ctx in frame.contexts() {
-                    if let Some(info) = ctx.as_crate_info() {
-                        let from = current_crate.unwrap_or("?");
-                        let to = info.name();
-                        write!(f, "\n    ─── {} (above) → {} (below) ───", from, to)?;
-                        current_crate = Some(to);
-                    }
+/// Write everything `full_trace()` shows after the error message.
+///
+/// Shared by `At<E>`, `AtTraceable` and the `dyn AtErrorDyn` formatter. With
+/// `crate_boundaries`, a `─── a (above) → b (below) ───` line is written where
+/// the trace crosses into another crate; otherwise boundaries are shown like
+/// any other context.
+pub(crate) fn write_traceable_body(
+    f: &mut fmt::Formatter<'_>,
+    trace: Option<&AtTrace>,
+    format: &AtTraceFormat,
+    code: Option<&str>,
+    help: Option<&str>,
+    crate_boundaries: bool,
+) -> fmt::Result {
+    if let Some(code) = code {
+        write!(f, "\n    code: {}", code)?;
+    }
+
+    #[cfg(feature = "error-id")]
+    if let Some(id) = trace.and_then(|t| t.error_id()) {
+        write!(f, "\n    error id: {}", id)?;
+    }
+
+    for msg in trace.into_iter().flat_map(|t| t.pinned()) {
+        write!(f, "\n    {}", msg)?;
+    }
+
+    // Show trace frames
+    if let Some(trace) = trace {
+        #[cfg(feature = "tokio")]
+        write_task_line(f, trace)?;
+
+        // Track current crate for boundary display
+        let mut current_crate: Option<&str> = trace.crate_info().map(|i| i.name());
+
+        for (i, frame) in trace.frames().enumerate() {
+            // Check for crate boundary before showing location
+            for ctx in frame.contexts().filter(|_| crate_boundaries) {
+                if let Some(info) = ctx.as_crate_info() {
+                    let from = current_crate.unwrap_or("?");
+                    let to = info.name();
+                    write!(f, "\n    ─── {} (above) → {} (below) ───", from, to)?;
+                    current_crate = Some(to);
                 }
+            }
 
-                if let Some(loc) = frame.location() {
-                    write!(f, "\n    at {}:{}:{}", loc.file(), loc.line(), loc.column())?;
-                } else {
-                    write!(f, "\n    [...]")?;
-                }
+            if let Some(loc) = frame.location() {
+                write!(f, "\n    at ")?;
+                format.write_location(f, &frame, loc)?;
+                write!(f, "{}", RepeatSuffix(frame.repeat_count()))?;
+            } else {
+                write!(f, "\n    {}", SkipMarker(frame.repeat_count()))?;
+            }
+            if let Some(n) = trace.suppressed_count().filter(|_| i == 0) {
+                write!(f, "\n        warn: {}", SuppressedNote(n))?;
+            }
 
-                // Show contexts for this frame (skip crate boundaries, already shown)
-                for ctx in frame.contexts() {
-                    if ctx.as_crate_info().is_some() {
-                        continue;
-                    }
-                    if let Some(text) = ctx.as_text() {
-                        write!(f, "\n        {}", text)?;
-                    } else if let Some(fn_name) = ctx.as_function_name() {
-                        write!(f, "\n        in {}", fn_name)?;
-                    } else if let Some(err) = ctx.as_error() {
-                        write!(f, "\n        caused by: {}", err)?;
-                        // Write nested error chain
-                        let mut source = err.source();
-                        let mut depth = 2;
-                        while let Some(src) = source {
-                            let indent = "    ".repeat(depth);
-                            write!(f, "\n{}caused by: {}", indent, src)?;
-                            source = src.source();
-                            depth += 1;
-                        }
-                    } else {
-                        write!(f, "\n        {}", ctx)?;
+            // Show contexts for this frame (skip crate boundaries, already shown)
+            for ctx in frame.contexts() {
+                if crate_boundaries && ctx.as_crate_info().is_some() {
+                    continue;
+                }
+                if ctx.is_suggestion()
+                    || ctx.is_pinned()
+                    || ctx.inner.is_backtrace()
+                    || ctx.inner.repeat_count().is_some()
+                    || !format.shows(ctx.inner)
+                {
+                    continue; // Hints, pinned messages and repeat counts are shown separately
+                }
+                if let Some(text) = ctx.as_text() {
+                    write!(f, "\n        {}", text)?;
+                } else if let Some(fn_name) = ctx.as_function_name() {
+                    write!(f, "\n        in {}", fn_name)?;
+                } else if let Some(err) = ctx.as_error() {
+                    write!(f, "\n        caused by: {}", err)?;
+                    // Write nested error chain
+                    let mut source = err.source();
+                    let mut depth = 2;
+                    while let Some(src) = source {
+                        let indent = "    ".repeat(depth);
+                        write!(f, "\n{}caused by: {}", indent, src)?;
+                        source = src.source();
+                        depth += 1;
                     }
+                } else if let AtContext::Debug(t) = ctx.inner {
+                    write!(f, "\n        ")?;
+                    format.write_debug(f, &**t, "        ")?;
+                } else if let AtContext::Secret(t) = ctx.inner {
+                    write!(f, "\n        ")?;
+                    format.write_secret(f, &**t, "        ")?;
+                } else if let Some(pair) = ctx.as_expected_actual() {
+                    write!(f, "\n        ")?;
+                    pair.write_block(f, "        ")?;
+                } else if let Some(bytes) = ctx.as_bytes() {
+                    write!(f, "\n        ")?;
+                    bytes.write_dump(f, "        ")?;
+                } else if let Some(trace) = ctx.as_remote_trace() {
+                    write!(f, "\n        ")?;
+                    trace.write_block(f, "        ")?;
+                } else {
+                    write!(f, "\n        {}", ctx)?;
                 }
             }
         }
-        Ok(())
+
+        for hint in trace.suggestions() {
+            write!(f, "\n    help: {}", hint)?;
+        }
+    }
+
+    if let Some(help) = help {
+        write!(f, "\n    help: {}", help)?;
+    }
+    if let Some(trace) = trace {
+        write_backtrace(f, trace, "\n    ", "")?;
+    }
+    Ok(())
+}
+
+/// Formatter for a bare trace, returned by [`AtTrace::display_with()`].
+struct AtTraceDisplay<'a> {
+    trace: Option<&'a AtTrace>,
+    format: AtTraceFormat,
+}
+
+impl fmt::Display for AtTraceDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// The body as it follows an error message, one `\n`-prefixed line each.
+        struct Body<'a>(&'a AtTraceDisplay<'a>);
+
+        impl fmt::Display for Body<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_traceable_body(f, self.0.trace, &self.0.format, None, None, true)
+            }
+        }
+
+        /// With no message to follow, the first line break is dropped.
+        struct SkipFirstNewline<'a, 'b> {
+            f: &'a mut fmt::Formatter<'b>,
+            skipped: bool,
+        }
+
+        impl fmt::Write for SkipFirstNewline<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let s = match s.strip_prefix('\n') {
+                    Some(rest) if !self.skipped => rest,
+                    _ => s,
+                };
+                self.skipped = true;
+                self.f.write_str(s)
+            }
+        }
+
+        let mut out = SkipFirstNewline { f, skipped: false };
+        fmt::write(&mut out, format_args!("{}", Body(self)))
+    }
+}
+
+impl fmt::Display for AtTrace {
+    /// Frames and their contexts, as [`full_trace()`](AtTraceable::full_trace)
+    /// shows them below the error message.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display_with(AtTraceFormat::DEFAULT), f)
+    }
+}
+
+impl fmt::Display for AtTraceBoxed {
+    /// Same as [`AtTrace`]'s `Display`; writes nothing if no trace is allocated.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display_with(AtTraceFormat::DEFAULT), f)
     }
 }
 
@@ -1416,18 +3204,45 @@ struct LastErrorTraceDisplay<'a, E: AtTraceable> {
     error: &'a E,
 }
 
+/// Formatter that shows error message + locations grouped by file.
+struct GroupedTraceDisplay<'a, E: AtTraceable> {
+    error: &'a E,
+}
+
+impl<E: AtTraceable> fmt::Display for GroupedTraceDisplay<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt_message(f)?;
+
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.error.error_id() {
+            write!(f, "\n    error id: {}", id)?;
+        }
+
+        if let Some(trace) = self.error.trace() {
+            write_grouped_frames(f, trace)?;
+        }
+        Ok(())
+    }
+}
+
 impl<E: AtTraceable> fmt::Display for LastErrorTraceDisplay<'_, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Show the error message
         self.error.fmt_message(f)?;
 
+        #[cfg(feature = "error-id")]
+        if let Some(id) = self.error.error_id() {
+            write!(f, "\n    error id: {}", id)?;
+        }
+
         // Show trace frames (locations only, no contexts)
         if let Some(trace) = self.error.trace() {
             for frame in trace.frames() {
                 if let Some(loc) = frame.location() {
                     write!(f, "\n    at {}:{}:{}", loc.file(), loc.line(), loc.column())?;
+                    write!(f, "{}", RepeatSuffix(frame.repeat_count()))?;
                 } else {
-                    write!(f, "\n    [...]")?;
+                    write!(f, "\n    {}", SkipMarker(frame.repeat_count()))?;
                 }
             }
         }
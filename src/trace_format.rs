@@ -0,0 +1,413 @@
+//! Options for trace formatters.
+//!
+//! [`AtTraceFormat`] is passed to the `*_with()` formatting methods, e.g.
+//! [`At::debug_with()`](crate::At::debug_with) and
+//! [`At::full_trace_with()`](crate::At::full_trace_with). The plain methods
+//! (`{:?}`, `full_trace()`) use [`AtTraceFormat::DEFAULT`].
+
+use alloc::format;
+use core::fmt;
+use core::panic::Location;
+
+use crate::context::{AtContext, AtLevel, REDACTED};
+use crate::trace::AtFrame;
+
+/// How the `*_with()` formatters write frame locations.
+///
+/// The editor styles turn each frame into an absolute URI that terminals can
+/// open, e.g. to jump to the code during local development. A relative
+/// location is resolved against the source root (see
+/// [`AtTraceFormat::source_root()`]) plus its crate's
+/// [`crate_path()`](crate::AtCrateInfo::crate_path); locations that are
+/// already absolute (e.g. in registry dependencies) are used as they are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AtLocationStyle {
+    /// `src/lib.rs:42:7`, as recorded (the default).
+    Relative,
+    /// `file:///home/me/repo/src/lib.rs:42:7`
+    FileUri,
+    /// `vscode://file/home/me/repo/src/lib.rs:42:7`, which opens VS Code at the line.
+    Vscode,
+}
+
+/// Formatting options shared by the trace formatters.
+///
+/// Built with `const` chained setters, so a format can live in a `static`.
+///
+/// ## Example
+///
+/// ```rust
+/// use whereat::{at, AtLevel, AtTraceFormat};
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// static QUIET: AtTraceFormat = AtTraceFormat::new().min_level(AtLevel::Warn);
+///
+/// let err = at(MyError).at_note("cache miss").at_warn("retrying with backup");
+///
+/// let output = err.debug_with(QUIET).to_string();
+/// assert!(!output.contains("cache miss"));
+/// assert!(output.contains("warn: retrying with backup"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AtTraceFormat {
+    pub(crate) min_level: AtLevel,
+    /// Debug contexts longer than this (as `{:?}`) are printed with `{:#?}`.
+    pub(crate) pretty_debug_over: usize,
+    pub(crate) location_style: AtLocationStyle,
+    pub(crate) source_root: Option<&'static str>,
+    pub(crate) redact: bool,
+    pub(crate) columns: bool,
+    pub(crate) strip_prefixes: &'static [&'static str],
+    pub(crate) short_registry_paths: bool,
+    pub(crate) crate_relative_paths: bool,
+    pub(crate) docs_link: bool,
+}
+
+impl AtTraceFormat {
+    /// The format used by `Debug`, `full_trace()` and the other plain methods.
+    pub const DEFAULT: Self = Self {
+        min_level: AtLevel::Note,
+        pretty_debug_over: usize::MAX,
+        location_style: AtLocationStyle::Relative,
+        source_root: None,
+        redact: true,
+        columns: true,
+        strip_prefixes: &[],
+        short_registry_paths: false,
+        crate_relative_paths: false,
+        docs_link: false,
+    };
+
+    /// Create the default format (all contexts shown).
+    #[inline]
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Hide contexts below `level`.
+    ///
+    /// Hidden contexts stay attached to the trace; they are only left out of
+    /// the output. Contexts without an explicit level are never hidden.
+    #[inline]
+    pub const fn min_level(mut self, level: AtLevel) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Pretty-print `Debug` contexts whose one-line form is longer than `len` bytes.
+    ///
+    /// Long contexts are printed with `{:#?}`, one field per line, indented
+    /// under their frame. Use `0` to pretty-print every `Debug` context.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtTraceFormat};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    /// #[derive(Debug)]
+    /// struct Request { method: &'static str, path: &'static str }
+    ///
+    /// let err = at(MyError).at_debug(|| Request { method: "GET", path: "/users" });
+    /// let output = err.debug_with(AtTraceFormat::new().pretty_debug(20)).to_string();
+    /// assert!(output.contains("╰─ Request {\n              method: \"GET\",\n"));
+    /// ```
+    #[inline]
+    pub const fn pretty_debug(mut self, len: usize) -> Self {
+        self.pretty_debug_over = len;
+        self
+    }
+
+    /// Write frame locations in `style`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtLocationStyle, AtTraceFormat};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let format = AtTraceFormat::new()
+    ///     .location_style(AtLocationStyle::Vscode)
+    ///     .source_root("/home/me/project");
+    /// let output = at(MyError).debug_with(format).to_string();
+    /// assert!(output.contains("    at vscode://file/"));
+    /// ```
+    #[inline]
+    pub const fn location_style(mut self, style: AtLocationStyle) -> Self {
+        self.location_style = style;
+        self
+    }
+
+    /// Resolve relative locations against `root` in the editor location styles.
+    ///
+    /// `root` is the directory [`crate_path()`](crate::AtCrateInfo::crate_path)
+    /// is relative to, usually the repository root. Without it, the
+    /// `WHEREAT_SOURCE_ROOT` environment variable is used, then the current
+    /// directory (both need `std`).
+    #[inline]
+    pub const fn source_root(mut self, root: &'static str) -> Self {
+        self.source_root = Some(root);
+        self
+    }
+
+    /// Print sensitive contexts (`at_secret`) as `[redacted]` (the default).
+    ///
+    /// `redact(false)` prints them with `Debug` instead, for local debugging.
+    /// [`At::display_unredacted()`](crate::At::display_unredacted) is a shorthand.
+    #[inline]
+    pub const fn redact(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Write frame locations with column numbers (the default).
+    ///
+    /// `columns(false)` writes `src/lib.rs:42` instead of `src/lib.rs:42:7`,
+    /// in every [location style](AtLocationStyle). Applies to `Debug`,
+    /// `full_trace()` and `display_with_meta()` alike.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{at, AtTraceFormat};
+    ///
+    /// #[derive(Debug)]
+    /// struct MyError;
+    ///
+    /// let err = at(MyError);
+    /// let line = line!() - 1;
+    /// let output = err.debug_with(AtTraceFormat::new().columns(false)).to_string();
+    /// assert!(output.contains(&format!("    at {}:{}\n", file!(), line)));
+    /// ```
+    #[inline]
+    pub const fn columns(mut self, columns: bool) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Remove the first matching prefix from locations in the relative style.
+    ///
+    /// For build paths baked into locations, e.g. `/build/src/` on CI or a
+    /// vendor directory. Prefixes are compared as plain strings.
+    #[inline]
+    pub const fn strip_prefixes(mut self, prefixes: &'static [&'static str]) -> Self {
+        self.strip_prefixes = prefixes;
+        self
+    }
+
+    /// Shorten locations in the cargo registry to `crate-version/path`.
+    ///
+    /// `/home/me/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.200/src/de.rs`
+    /// becomes `serde-1.0.200/src/de.rs`. Applies to the relative style; the
+    /// editor styles need the full path.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use whereat::{AtTrace, AtTraceFormat};
+    ///
+    /// let trace = AtTrace::capture();
+    /// let format = AtTraceFormat::new()
+    ///     .short_registry_paths(true)
+    ///     .strip_prefixes(&["/build/"]);
+    /// // Locations in this file are already short and stay unchanged
+    /// assert!(trace.display_with(format).to_string().contains(file!()));
+    /// ```
+    #[inline]
+    pub const fn short_registry_paths(mut self, short: bool) -> Self {
+        self.short_registry_paths = short;
+        self
+    }
+
+    /// Write locations relative to their crate's directory.
+    ///
+    /// In a workspace, `crates/db/src/pool.rs` becomes `src/pool.rs` when the
+    /// frame's crate was registered with `define_at_crate_info!(path =
+    /// "crates/db/")`. Frames without a [`crate_path()`](crate::AtCrateInfo::crate_path)
+    /// are unchanged. Applies to the relative style.
+    #[inline]
+    pub const fn crate_relative_paths(mut self, relative: bool) -> Self {
+        self.crate_relative_paths = relative;
+        self
+    }
+
+    /// Add a `see:` line with [`At::docs_url()`](crate::At::docs_url) to `display_with_meta_with()`.
+    ///
+    /// Off by default: the link's variant is guessed from the error's `Debug`
+    /// output, so it only points at a real page for derived `Debug` on types
+    /// documented under their `type_name`. Turn it on for error types where
+    /// that holds.
+    #[inline]
+    pub const fn docs_link(mut self, show: bool) -> Self {
+        self.docs_link = show;
+        self
+    }
+
+    /// `file` shortened by the path options.
+    fn short_path<'a>(&self, frame: &AtFrame<'_>, file: &'a str) -> &'a str {
+        let mut file = file;
+        if self.short_registry_paths {
+            file = strip_registry(file);
+        }
+        if let Some(rest) = self
+            .strip_prefixes
+            .iter()
+            .find_map(|p| file.strip_prefix(p))
+        {
+            file = rest;
+        }
+        if self.crate_relative_paths {
+            let crate_path = frame.crate_info().and_then(|info| info.crate_path());
+            if let Some(rest) = crate_path.and_then(|p| file.strip_prefix(p)) {
+                file = rest.trim_start_matches(['/', '\\']);
+            }
+        }
+        file
+    }
+
+    /// Write a frame's location in the selected style.
+    pub(crate) fn write_location(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        frame: &AtFrame<'_>,
+        loc: &Location<'_>,
+    ) -> fmt::Result {
+        let scheme = match self.location_style {
+            AtLocationStyle::Relative => {
+                write!(f, "{}:{}", self.short_path(frame, loc.file()), loc.line())?;
+                return self.write_column(f, loc);
+            }
+            AtLocationStyle::FileUri => "file:///",
+            AtLocationStyle::Vscode => "vscode://file/",
+        };
+        f.write_str(scheme)?;
+        let file = loc.file();
+        if !is_absolute(file) {
+            #[cfg(feature = "std")]
+            let env_root = match self.source_root {
+                Some(_) => None,
+                None => std::env::var("WHEREAT_SOURCE_ROOT").ok().or_else(|| {
+                    std::env::current_dir()
+                        .ok()?
+                        .into_os_string()
+                        .into_string()
+                        .ok()
+                }),
+            };
+            #[cfg(feature = "std")]
+            let root = self.source_root.or(env_root.as_deref());
+            #[cfg(not(feature = "std"))]
+            let root = self.source_root;
+            if let Some(root) = root {
+                write_uri_path(f, root.trim_end_matches(['/', '\\']))?;
+                f.write_str("/")?;
+            }
+            if let Some(path) = frame.crate_info().and_then(|info| info.crate_path()) {
+                write_uri_path(f, path)?;
+            }
+        }
+        write_uri_path(f, file)?;
+        write!(f, ":{}", loc.line())?;
+        self.write_column(f, loc)
+    }
+
+    /// Write `:column` if columns are enabled.
+    #[inline]
+    fn write_column(&self, f: &mut fmt::Formatter<'_>, loc: &Location<'_>) -> fmt::Result {
+        if self.columns {
+            write!(f, ":{}", loc.column())?;
+        }
+        Ok(())
+    }
+
+    /// Whether `context` passes this format's filters.
+    #[inline]
+    pub(crate) fn shows(&self, context: &AtContext) -> bool {
+        context.level() >= self.min_level
+    }
+
+    /// Write a sensitive context: `[redacted]`, or like a `Debug` context if revealed.
+    pub(crate) fn write_secret<T: fmt::Debug + ?Sized>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        value: &T,
+        indent: &str,
+    ) -> fmt::Result {
+        if self.redact {
+            return f.write_str(REDACTED);
+        }
+        self.write_debug(f, value, indent)
+    }
+
+    /// Write a `Debug` context, pretty-printed if it is over the threshold.
+    ///
+    /// The caller writes the first line's prefix; continuation lines start
+    /// with `indent`.
+    pub(crate) fn write_debug<T: fmt::Debug + ?Sized>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        value: &T,
+        indent: &str,
+    ) -> fmt::Result {
+        if self.pretty_debug_over == usize::MAX {
+            return write!(f, "{:?}", value);
+        }
+        let compact = format!("{:?}", value);
+        if compact.len() <= self.pretty_debug_over {
+            return f.write_str(&compact);
+        }
+        let pretty = format!("{:#?}", value);
+        for (i, line) in pretty.lines().enumerate() {
+            if i > 0 {
+                write!(f, "\n{}", indent)?;
+            }
+            f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// `file` without the cargo registry directories before the crate's own,
+/// e.g. `.../registry/src/index.crates.io-6f17d22bba15001f/`.
+pub(crate) fn strip_registry(file: &str) -> &str {
+    for marker in ["/registry/src/", "\\registry\\src\\"] {
+        if let Some(pos) = file.find(marker) {
+            let index_dir = &file[pos + marker.len()..];
+            if let Some(sep) = index_dir.find(['/', '\\']) {
+                return &index_dir[sep + 1..];
+            }
+        }
+    }
+    file
+}
+
+/// Whether `path` is absolute on Unix (`/...`) or Windows (`C:\...`, `\\...`).
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    matches!(bytes.first(), Some(b'/' | b'\\'))
+        || (bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Write `path` with forward slashes and without a leading slash, since the
+/// URI schemes supply it.
+fn write_uri_path(f: &mut fmt::Formatter<'_>, path: &str) -> fmt::Result {
+    let path = path.trim_start_matches(['/', '\\']);
+    for (i, part) in path.split(['/', '\\']).enumerate() {
+        if i > 0 {
+            f.write_str("/")?;
+        }
+        f.write_str(part)?;
+    }
+    Ok(())
+}
+
+impl Default for AtTraceFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests for attaching a full `std::backtrace::Backtrace` (the `std-backtrace` feature).
+
+#![cfg(feature = "std-backtrace")]
+
+use whereat::{At, AtContextKind, ResultAtExt, at};
+
+#[derive(Debug)]
+struct MyError;
+
+impl core::fmt::Display for MyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("my error")
+    }
+}
+
+fn fails() -> Result<(), At<MyError>> {
+    Err(at(MyError)).at_str("inner")
+}
+
+#[test]
+fn backtrace_is_printed_after_the_frames() {
+    let err = fails().at().at_backtrace().unwrap_err();
+    assert!(err.backtrace().is_some());
+    assert_eq!(
+        err.contexts()
+            .filter(|c| c.kind() == AtContextKind::Backtrace)
+            .count(),
+        1
+    );
+
+    let debug = format!("{:?}", err);
+    let section = debug.find("\n    backtrace:\n").expect("backtrace section");
+    assert!(debug.find("inner").unwrap() < section);
+    assert!(!debug[..section].contains("backtrace:"));
+    assert!(debug[section..].contains("\n      "));
+
+    let full = err.full_trace().to_string();
+    assert_eq!(full.matches("\n    backtrace:\n").count(), 1);
+}
+
+#[test]
+fn only_the_first_backtrace_is_kept() {
+    let err = fails().at_backtrace().at().at_backtrace().unwrap_err();
+    assert_eq!(
+        format!("{:?}", err).matches("\n    backtrace:\n").count(),
+        1
+    );
+    assert!(err.frame_count() >= 2);
+}
+
+#[test]
+fn no_backtrace_without_at_backtrace() {
+    let err = fails().unwrap_err();
+    assert!(err.backtrace().is_none());
+    assert!(!format!("{:?}", err).contains("\n    backtrace:\n"));
+}
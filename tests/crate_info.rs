@@ -147,6 +147,59 @@ fn display_with_meta_uses_crate_info() {
     );
 }
 
+#[test]
+fn display_by_crate_sections_frames() {
+    static DEP: AtCrateInfo = AtCrateInfo::builder().name("payments-client").build();
+    static MID: AtCrateInfo = AtCrateInfo::builder().name("billing").build();
+    static APP: AtCrateInfo = AtCrateInfo::builder().name("myapp").build();
+
+    // A boundary marks the frame it is attached to; a callback returns to billing
+    let err = At::wrap(TestError)
+        .set_crate_info(&DEP)
+        .at()
+        .at_str("charging card")
+        .at()
+        .at_crate(&MID)
+        .at()
+        .at_crate(&APP)
+        .at()
+        .at()
+        .at_crate(&MID);
+    let output = err.display_by_crate().to_string();
+
+    let lines: Vec<_> = output
+        .lines()
+        .filter(|l| !l.starts_with("  error id:"))
+        .map(|l| if l.starts_with("    at ") { "at" } else { l })
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "Error: TestError",
+            "",
+            "── payments-client ──",
+            "at",
+            "       ╰─ charging card",
+            "",
+            "── billing ──",
+            "at",
+            "",
+            "── myapp ──",
+            "at",
+            "at",
+            "",
+            "── billing ──",
+            "at",
+        ],
+        "{}",
+        output
+    );
+    // The plain meta display keeps its single crate line and no headers
+    let meta = err.display_with_meta().to_string();
+    assert!(meta.contains("  crate: payments-client\n"));
+    assert!(!meta.contains("──"));
+}
+
 // ============================================================================
 // Cross-Crate Boundary Simulation
 // ============================================================================
@@ -315,6 +368,26 @@ fn sizeof_common_error_types() {
     );
 }
 
+#[test]
+#[cfg(all(feature = "off", not(feature = "trace-full")))]
+fn sizeof_capture_off_allocates_nothing() {
+    #[derive(Debug)]
+    struct Small(u8);
+
+    let err = at(Small(1))
+        .at()
+        .at_str("loading")
+        .at_string(|| unreachable!())
+        .at_named("phase");
+    assert!(err.trace().is_none());
+    assert_eq!(err.trace_stats().heap_bytes(), 0);
+    assert_eq!(err.frame_count(), 0);
+    assert!(!format!("{:?}", err).contains("    at "));
+
+    // The layout is unchanged: still error + pointer
+    assert_eq!(size_of::<At<Small>>(), 16);
+}
+
 // ============================================================================
 // Repository URL Formatting
 // ============================================================================
@@ -971,3 +1044,214 @@ mod with_path {
         );
     }
 }
+
+#[test]
+fn github_links_include_column_anchor() {
+    static INFO: AtCrateInfo = AtCrateInfo::builder()
+        .name("test")
+        .repo(Some("https://github.com/org/repo"))
+        .commit(Some("abc"))
+        .module("test")
+        .build();
+
+    let err = whereat::At::wrap(TestError).at().at_crate(&INFO);
+    let loc = err.frames().next().unwrap().location().unwrap();
+    let output = format!("{}", err.display_with_meta());
+
+    let expected = format!(
+        "https://github.com/org/repo/blob/abc/tests/crate_info.rs#L{}C{}",
+        loc.line(),
+        loc.column()
+    );
+    assert!(
+        output.contains(&expected),
+        "missing {} in:\n{}",
+        expected,
+        output
+    );
+}
+
+#[test]
+fn gitlab_links_stop_at_line() {
+    static INFO: AtCrateInfo = AtCrateInfo::builder()
+        .name("test")
+        .repo(Some("https://gitlab.com/org/repo"))
+        .commit(Some("abc"))
+        .module("test")
+        .link_format(whereat::GITLAB_LINK_FORMAT)
+        .build();
+
+    let err = whereat::At::wrap(TestError).at().at_crate(&INFO);
+    let loc = err.frames().next().unwrap().location().unwrap();
+    let output = format!("{}", err.display_with_meta());
+    assert!(output.contains(&format!("crate_info.rs#L{}\n", loc.line())));
+}
+
+#[test]
+fn sourcehut_links_are_auto_detected() {
+    let info = AtCrateInfo::builder()
+        .name("test")
+        .repo(Some("https://git.sr.ht/~org/repo"))
+        .commit(Some("abc"))
+        .link_format_auto()
+        .build();
+    assert_eq!(info.link_format(), whereat::SOURCEHUT_LINK_FORMAT);
+
+    let info: &'static AtCrateInfo = Box::leak(Box::new(info));
+    let err = whereat::At::wrap(TestError).at().at_crate(info);
+    let loc = err.frames().next().unwrap().location().unwrap();
+    let output = format!("{}", err.display_with_meta());
+    assert!(output.contains(&format!(
+        "https://git.sr.ht/~org/repo/tree/abc/item/tests/crate_info.rs#L{}\n",
+        loc.line()
+    )));
+}
+
+#[derive(Debug)]
+enum Wrapped<T> {
+    Timeout(T),
+}
+
+#[test]
+fn docs_url_links_the_failing_variant() {
+    static DOCS: AtCrateInfo = AtCrateInfo::builder()
+        .name("mylib")
+        .meta(&[("docs_url", "https://docs.rs/mylib/latest/mylib")])
+        .build();
+
+    // Types in this test crate resolve to `crate_info::...`
+    let err = At::wrap(Wrapped::Timeout(5u32)).set_crate_info(&DOCS).at();
+    assert_eq!(
+        err.docs_url().unwrap(),
+        "https://docs.rs/mylib/latest/mylib/enum.Wrapped.html#variant.Timeout"
+    );
+    assert!(!err.display_with_meta().to_string().contains("see:"));
+    let format = whereat::AtTraceFormat::new().docs_link(true);
+    let output = err.display_with_meta_with(format).to_string();
+    assert!(output.contains("\n  crate: mylib\n  see: https://docs.rs/mylib/latest/mylib/enum.Wrapped.html#variant.Timeout\n"), "{}", output);
+
+    let err = At::wrap(TestError).set_crate_info(&DOCS).at();
+    assert_eq!(
+        err.docs_url().unwrap(),
+        "https://docs.rs/mylib/latest/mylib/struct.TestError.html"
+    );
+
+    // No link without the metadata
+    let err = at!(TestError);
+    assert_eq!(err.docs_url(), None);
+    assert!(!err.display_with_meta().to_string().contains("see:"));
+}
+
+// ============================================================================
+// Build configuration (at_cfg!)
+// ============================================================================
+
+#[test]
+fn at_cfg_attaches_build_info() {
+    use whereat::{AtBuildInfo, at_cfg};
+
+    fn load() -> Result<(), At<TestError>> {
+        at_cfg!(Err(at(TestError)), features = ["serde", "std"])
+    }
+
+    let err = load().unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    let info = err
+        .contexts()
+        .find_map(|c| c.downcast_ref::<AtBuildInfo>())
+        .unwrap();
+    assert_eq!(info.crate_name(), "whereat");
+    assert_eq!(info.version(), env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.is_debug(), cfg!(debug_assertions));
+
+    let expected: Vec<_> = [
+        ("serde", cfg!(feature = "serde")),
+        ("std", cfg!(feature = "std")),
+    ]
+    .into_iter()
+    .filter(|(_, on)| *on)
+    .map(|(name, _)| name)
+    .collect();
+    assert_eq!(info.features().collect::<Vec<_>>(), expected);
+
+    let features = if expected.is_empty() {
+        "none".to_string()
+    } else {
+        expected.join(", ")
+    };
+    let line = format!(
+        "whereat {} ({}, {}, features: {})",
+        env!("CARGO_PKG_VERSION"),
+        info.target(),
+        info.profile(),
+        features
+    );
+    assert_eq!(info.to_string(), line);
+    assert!(format!("{:?}", err).contains(&format!("╰─ {}", line)));
+
+    // Without a feature list, features are left out entirely
+    let bare: Result<(), At<TestError>> = at_cfg!(Err(at(TestError)));
+    let bare = bare.unwrap_err();
+    let info = bare
+        .contexts()
+        .find_map(|c| c.downcast_ref::<AtBuildInfo>())
+        .unwrap();
+    assert!(
+        info.to_string()
+            .ends_with(&format!(", {})", info.profile()))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn main_reports_exit_codes() {
+    use std::process::{ExitCode, Termination};
+    use whereat::AtMain;
+
+    assert_eq!(
+        AtMain::<TestError>::from(Ok(())).report(),
+        ExitCode::SUCCESS
+    );
+    assert_eq!(
+        AtMain::from(Err(at!(TestError))).report(),
+        ExitCode::FAILURE
+    );
+    let main = AtMain::new(Err(at!(TestError)))
+        .exit_code(78)
+        .env_report(whereat::env_report());
+    assert_eq!(main.report(), ExitCode::from(78));
+    assert_eq!(whereat::report(Err(at(TestError))), ExitCode::FAILURE);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn env_report_sections() {
+    let report = whereat::env_report()
+        .build(whereat::at_build_info!())
+        .var("PATH")
+        .var("WHEREAT_TEST_UNSET_VAR");
+    assert!(!report.binary().is_empty());
+    assert_eq!(report.os(), std::env::consts::OS);
+    let vars: Vec<_> = report.vars().collect();
+    assert_eq!(vars[0].1, std::env::var("PATH").ok().as_deref());
+    assert_eq!(vars[1], ("WHEREAT_TEST_UNSET_VAR", None));
+
+    let line = report.to_string();
+    assert!(line.starts_with(&format!("whereat {} (", env!("CARGO_PKG_VERSION"))));
+    assert!(line.ends_with("WHEREAT_TEST_UNSET_VAR unset"));
+    assert!(!line.contains('\n'));
+
+    let section = format!("{:#}", report);
+    let labels: Vec<_> = section.lines().map(|l| &l[..9]).collect();
+    assert_eq!(
+        labels,
+        [
+            "binary:  ",
+            "build:   ",
+            "rustc:   ",
+            "os:      ",
+            "env:     ",
+            "         "
+        ]
+    );
+}
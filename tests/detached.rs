@@ -0,0 +1,201 @@
+//! Tests for owned (detached) traces and conversion from std backtraces.
+
+use whereat::{AtFrameDetached, AtTraceDetached};
+
+// ============================================================================
+// AtTraceDetached formatting
+// ============================================================================
+
+#[test]
+fn detached_display_matches_trace_layout() {
+    let trace = AtTraceDetached::from_frames([
+        AtFrameDetached::new("src/db.rs", 142, 9).with_context("user_id = 42"),
+        AtFrameDetached::skipped(),
+        AtFrameDetached::new("src/api.rs", 89, 5).with_function("handle_request"),
+    ]);
+
+    assert_eq!(
+        trace.to_string(),
+        "    at src/db.rs:142:9\n       ╰─ user_id = 42\n    [...]\n    at src/api.rs:89:5\n       ╰─ in handle_request\n"
+    );
+    assert_eq!(trace.frame_count(), 3);
+    assert!(trace.frames().nth(1).unwrap().is_skipped());
+}
+
+// ============================================================================
+// std::backtrace conversion
+// ============================================================================
+
+#[cfg(feature = "std")]
+#[inline(never)]
+fn capture_here() -> std::backtrace::Backtrace {
+    std::backtrace::Backtrace::force_capture()
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_backtrace_resolves_files_and_functions() {
+    let trace = whereat::AtTrace::from_backtrace(&capture_here());
+    assert!(!trace.is_empty());
+
+    // Oldest first: the capturing function comes after its caller
+    let frames: Vec<_> = trace.frames().collect();
+    let capture_idx = frames
+        .iter()
+        .position(|f| f.function().is_some_and(|n| n.ends_with("capture_here")))
+        .expect("capture_here frame");
+    let test_idx = frames
+        .iter()
+        .position(|f| {
+            f.function()
+                .is_some_and(|n| n.ends_with("from_backtrace_resolves_files_and_functions"))
+        })
+        .expect("test frame");
+    assert!(test_idx < capture_idx);
+
+    let capture = frames[capture_idx];
+    assert!(capture.file().unwrap().ends_with("detached.rs"));
+    assert!(capture.line() > 0);
+}
+
+// Holds whatever the (unspecified) backtrace text layout looks like
+#[cfg(feature = "std")]
+#[test]
+fn from_backtrace_only_reports_what_the_text_contains() {
+    let backtrace = capture_here();
+    let text = backtrace.to_string();
+    let trace = whereat::AtTrace::from_backtrace(&backtrace);
+
+    assert!(trace.frame_count() <= text.lines().count());
+    for frame in trace.frames() {
+        assert!(!frame.is_skipped());
+        if let Some(function) = frame.function() {
+            assert!(text.contains(function));
+        }
+        if let Some(file) = frame.file() {
+            assert!(text.contains(file));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_backtrace_disabled_is_empty() {
+    let trace = whereat::AtTrace::from_backtrace(&std::backtrace::Backtrace::disabled());
+    assert!(trace.is_empty());
+}
+
+// ============================================================================
+// Conversion from live traces
+// ============================================================================
+
+#[derive(Debug)]
+struct Deep;
+
+#[test]
+fn to_detached_keeps_text_and_function_names() {
+    let err = whereat::at(Deep)
+        .at_str("reading header")
+        .at_secret(|| "hunter2")
+        .at_named("load")
+        .dedup_trace();
+    let detached = err.trace().unwrap().to_detached();
+    let frames: Vec<_> = detached.frames().collect();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(
+        frames[0].contexts().collect::<Vec<_>>(),
+        ["reading header", "[redacted]"]
+    );
+    assert_eq!(frames[1].function(), Some("load"));
+    assert_eq!(frames[1].file(), Some(file!()));
+}
+
+// ============================================================================
+// Binary encoding (persist feature)
+// ============================================================================
+
+#[cfg(feature = "persist")]
+fn recurse(depth: u32) -> Result<(), whereat::At<Deep>> {
+    if depth == 0 {
+        return Err(whereat::at(Deep));
+    }
+    recurse(depth - 1).map_err(|e| e.at())
+}
+
+#[cfg(feature = "persist")]
+#[test]
+fn encoded_traces_round_trip() {
+    use whereat::{AtDecodeError, AtTrace};
+
+    let original = AtTraceDetached::from_frames([
+        AtFrameDetached::new("src/db.rs", 142, 9).with_context("user_id = 42"),
+        AtFrameDetached::skipped(),
+        AtFrameDetached::new("src/api.rs", 89, 5)
+            .with_function("handle_request")
+            .with_context("ünïcode"),
+    ]);
+    let bytes = original.encode();
+    assert_eq!(AtTrace::decode(&bytes), Ok(original.clone()));
+
+    // A live trace decodes to its detached form
+    let err = recurse(3).unwrap_err().at_str("top");
+    let trace = err.trace().unwrap();
+    assert_eq!(
+        AtTrace::decode(&trace.encode()).unwrap(),
+        trace.to_detached()
+    );
+
+    assert_eq!(AtTrace::decode(b"nope"), Err(AtDecodeError::NotATrace));
+    assert_eq!(
+        AtTrace::decode(b"WAT\x07"),
+        Err(AtDecodeError::UnsupportedVersion(7))
+    );
+    for len in 4..bytes.len() {
+        assert_eq!(
+            AtTrace::decode(&bytes[..len]),
+            Err(AtDecodeError::Truncated),
+            "prefix of {} bytes",
+            len
+        );
+    }
+    let mut padded = bytes.clone();
+    padded.push(0);
+    assert_eq!(AtTrace::decode(&padded), Err(AtDecodeError::TrailingBytes));
+    // A huge frame count is not trusted for allocation
+    assert_eq!(
+        AtTrace::decode(b"WAT\x01\xff\xff\xff\xff"),
+        Err(AtDecodeError::Truncated)
+    );
+}
+
+// ============================================================================
+// Re-attaching remote traces
+// ============================================================================
+
+#[test]
+fn remote_traces_render_under_the_receiving_frame() {
+    let remote = AtTraceDetached::from_frames([
+        AtFrameDetached::new("src/worker.rs", 42, 9).with_context("decoding frame 7"),
+        AtFrameDetached::skipped(),
+    ]);
+    let err = whereat::at("worker failed").at_remote_trace(remote.clone());
+
+    let ctx = err.contexts().next().unwrap();
+    assert_eq!(ctx.kind(), whereat::AtContextKind::Remote);
+    assert_eq!(ctx.as_remote_trace(), Some(&remote));
+
+    let debug = format!("{:?}", err);
+    assert!(debug.contains(
+        "\n       ╰─ remote trace:\n          at src/worker.rs:42:9\n             ╰─ decoding frame 7\n          [...]\n"
+    ));
+    let full = err.full_trace().to_string();
+    assert!(full.contains(
+        "\n        remote trace:\n        at src/worker.rs:42:9\n           ╰─ decoding frame 7\n        [...]"
+    ));
+
+    // Detaching again keeps the block aligned under the context marker
+    let detached = err.trace().unwrap().to_detached();
+    assert!(detached.to_string().contains(
+        "\n       ╰─ remote trace:\n          at src/worker.rs:42:9\n             ╰─ decoding frame 7\n"
+    ));
+}
@@ -0,0 +1,72 @@
+//! Tests for AtErrorDyn: traced errors of different types behind one trait object.
+
+use std::fmt;
+use whereat::{AtErrorDyn, AtLevel, AtTrace, AtTraceFormat, at};
+
+#[derive(Debug)]
+struct NotFound;
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not found")
+    }
+}
+
+struct PluginError {
+    trace: AtTrace,
+}
+
+impl whereat::AtTraceable for PluginError {
+    fn trace_mut(&mut self) -> &mut AtTrace {
+        &mut self.trace
+    }
+    fn trace(&self) -> Option<&AtTrace> {
+        Some(&self.trace)
+    }
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "plugin crashed")
+    }
+    fn help(&self) -> Option<&str> {
+        Some("restart the plugin")
+    }
+    fn error_code(&self) -> Option<&'static str> {
+        Some("P0042")
+    }
+}
+
+fn plugin_error() -> PluginError {
+    use whereat::AtTraceable;
+    PluginError {
+        trace: AtTrace::new(),
+    }
+    .at_note("reloading")
+}
+
+#[test]
+fn heterogeneous_errors_render_uniformly() {
+    let errors: Vec<Box<dyn AtErrorDyn + Send + Sync>> = vec![
+        Box::new(at(NotFound).at_str("loading config")),
+        Box::new(plugin_error()),
+    ];
+
+    assert_eq!(errors[0].message().to_string(), "not found");
+    assert_eq!(errors[1].message().to_string(), "plugin crashed");
+    assert_eq!(errors[0].frames().count(), 1);
+    assert!(errors[0].crate_info().is_none());
+
+    let first = errors[0].full_trace().to_string();
+    assert!(first.starts_with("not found\n"));
+    assert!(first.contains("\n        loading config"));
+
+    let second = errors[1].full_trace().to_string();
+    assert!(second.starts_with("plugin crashed\n    code: P0042\n"));
+    assert_eq!(errors[1].error_code(), Some("P0042"));
+    assert_eq!(errors[0].error_code(), None);
+    assert!(second.contains("note: reloading"));
+    assert!(second.ends_with("\n    help: restart the plugin"));
+
+    let quiet = errors[1]
+        .full_trace_with(AtTraceFormat::new().min_level(AtLevel::Warn))
+        .to_string();
+    assert!(!quiet.contains("reloading"));
+}
@@ -822,6 +822,51 @@ fn frames_with_error_context() {
     }
 }
 
+#[test]
+fn raw_frame_context_association() {
+    use whereat::AtContextKind;
+
+    let err = at(PlainError::NotFound)
+        .at_str("step 1")
+        .at_debug(|| 3u8)
+        .at_skipped_frames()
+        .at()
+        .at_help("retry");
+    let trace = err.trace().unwrap();
+
+    // Flat view: attachment order, tagged with frame index
+    let entries: Vec<_> = trace
+        .context_entries()
+        .map(|(frame, ctx)| (frame, ctx.kind()))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (0, AtContextKind::Text),
+            (0, AtContextKind::Debug),
+            (2, AtContextKind::Suggestion),
+        ]
+    );
+
+    // Per-frame view agrees with the flat view
+    for (index, frame) in trace.frames().enumerate() {
+        assert_eq!(frame.index(), index);
+        let per_frame: Vec<_> = frame.contexts().map(|c| c.kind()).collect();
+        let flat: Vec<_> = entries
+            .iter()
+            .filter(|(i, _)| *i == index)
+            .map(|(_, k)| *k)
+            .collect();
+        assert_eq!(per_frame, flat);
+    }
+    assert!(trace.frame(1).unwrap().is_skipped());
+    assert!(trace.frame(3).is_none());
+
+    let mut err = err;
+    err.take_trace();
+    assert!(err.trace().is_none());
+}
+
 // ============================================================================
 // Test: frame_count() convenience method
 // ============================================================================
@@ -834,3 +879,471 @@ fn frame_count_api() {
     let err = err.at().at().at();
     assert_eq!(err.frame_count(), 4);
 }
+
+// ============================================================================
+// Test: prelude covers both wrapper and embedded styles
+// ============================================================================
+
+#[test]
+fn prelude_single_import() {
+    mod uses_prelude {
+        use whereat::prelude::*;
+
+        #[derive(Debug)]
+        pub struct Wrapped;
+
+        pub fn wrapped() -> AtResult<(), Wrapped> {
+            Err(at(Wrapped)).at_str("from prelude")
+        }
+
+        pub struct Embedded(pub AtTrace);
+
+        impl AtTraceable for Embedded {
+            fn trace_mut(&mut self) -> &mut AtTrace {
+                &mut self.0
+            }
+            fn trace(&self) -> Option<&AtTrace> {
+                Some(&self.0)
+            }
+            fn fmt_message(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "embedded")
+            }
+        }
+
+        pub fn embedded() -> Result<(), Embedded> {
+            Err(Embedded(AtTrace::capture())).at()
+        }
+
+        pub fn started() -> At<std::io::Error> {
+            std::io::Error::other("io").start_at()
+        }
+    }
+
+    assert_eq!(uses_prelude::wrapped().unwrap_err().frame_count(), 1);
+    assert_eq!(
+        uses_prelude::embedded()
+            .unwrap_err()
+            .trace()
+            .unwrap()
+            .frame_count(),
+        2
+    );
+    assert_eq!(uses_prelude::started().frame_count(), 1);
+}
+
+// ============================================================================
+// Test: per-kind context iterators
+// ============================================================================
+
+#[test]
+fn contexts_partitioned_by_kind() {
+    static OURS: whereat::AtCrateInfo = whereat::AtCrateInfo::builder().name("ours").build();
+    static OTHER: whereat::AtCrateInfo = whereat::AtCrateInfo::builder().name("other").build();
+
+    // The first crate is stored as the trace's crate_info, not as a boundary
+    let err = at(PlainError::NotFound)
+        .set_crate_info(&OURS)
+        .at_str("first")
+        .at_error(io::Error::other("disk"))
+        .at_link("runbook", "https://example.com/rb")
+        .at_crate(&OTHER)
+        .at_debug(|| 7u8)
+        .at_string(|| "second".to_string());
+
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["second", "first"]);
+    assert_eq!(
+        err.errors().map(|e| e.to_string()).collect::<Vec<_>>(),
+        ["disk"]
+    );
+    assert_eq!(
+        err.links().collect::<Vec<_>>(),
+        [("runbook", "https://example.com/rb")]
+    );
+    assert_eq!(
+        err.crates().map(|c| c.name()).collect::<Vec<_>>(),
+        ["other"]
+    );
+
+    // Same iterators on embedded traces
+    struct Embedded(AtTrace);
+    impl AtTraceable for Embedded {
+        fn trace_mut(&mut self) -> &mut AtTrace {
+            &mut self.0
+        }
+        fn trace(&self) -> Option<&AtTrace> {
+            Some(&self.0)
+        }
+        fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "embedded")
+        }
+    }
+    let embedded = Embedded(AtTrace::capture())
+        .at_str("ctx")
+        .at_error(io::Error::other("io"));
+    assert_eq!(embedded.texts().collect::<Vec<_>>(), ["ctx"]);
+    assert_eq!(embedded.errors().count(), 1);
+    assert_eq!(embedded.links().count(), 0);
+}
+
+// ============================================================================
+// Test: downcasting At<Box<dyn Error>> keeps the trace
+// ============================================================================
+
+#[test]
+fn downcast_dyn_error_preserves_trace() {
+    type DynError = Box<dyn Error + Send + Sync>;
+
+    fn fails() -> Result<(), At<DynError>> {
+        Err(at(Box::new(io::Error::other("disk full")) as DynError))
+            .at_str("writing index")
+            .at()
+    }
+
+    let mut err = fails().unwrap_err();
+    assert!(err.is::<io::Error>());
+    assert!(!err.is::<fmt::Error>());
+    assert_eq!(
+        err.downcast_ref::<io::Error>().unwrap().to_string(),
+        "disk full"
+    );
+    assert!(err.downcast_mut::<io::Error>().is_some());
+
+    // Wrong type: the original error and trace come back untouched
+    let err = err.downcast::<fmt::Error>().unwrap_err();
+    assert_eq!(err.frame_count(), 2);
+
+    let io_err: At<io::Error> = err.downcast().unwrap();
+    assert_eq!(io_err.frame_count(), 2);
+    assert_eq!(io_err.texts().collect::<Vec<_>>(), ["writing index"]);
+
+    // Non-Send variant
+    let local: At<Box<dyn Error>> = at(Box::new(fmt::Error) as Box<dyn Error>);
+    assert_eq!(local.downcast::<fmt::Error>().unwrap().frame_count(), 1);
+}
+
+// ============================================================================
+// Test: Display-only errors through Box<dyn Error>
+// ============================================================================
+
+#[test]
+fn display_only_error_as_std_error() {
+    #[derive(Debug)]
+    struct Rejected;
+
+    impl fmt::Display for Rejected {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "rejected")
+        }
+    }
+
+    fn check() -> Result<(), Box<dyn Error + Send + Sync>> {
+        Err(at(Rejected).at_str("validating").into_std_error())?
+    }
+
+    let err = check().unwrap_err();
+    assert_eq!(err.to_string(), "rejected");
+    // Debug keeps the trace
+    assert!(format!("{:?}", err).contains("╰─ validating"));
+
+    let adapter = at(Rejected).into_std_error();
+    assert_eq!(adapter.as_at().frame_count(), 1);
+    let back: At<Rejected> = adapter.into();
+    assert_eq!(back.frame_count(), 1);
+}
+
+// ============================================================================
+// Test: At<E> in code generic over AtTraceable
+// ============================================================================
+
+#[test]
+fn at_reaches_generic_traceable_code() {
+    fn middleware<T: AtTraceable>(err: T) -> String {
+        err.at_str("in middleware").full_trace().to_string()
+    }
+
+    let err: At<PlainError> = at(PlainError::NotFound).at_str("loading");
+    let output = middleware(err.into_std_error());
+
+    assert!(output.starts_with("not found\n"));
+    assert!(output.contains("loading"));
+    assert!(output.contains("in middleware"));
+}
+
+// ============================================================================
+// Test: err_at! builds the error, message and key-value contexts at once
+// ============================================================================
+
+#[test]
+fn err_at_macro_attaches_message_and_fields() {
+    let user_id = 42u64;
+    let line = line!() + 1;
+    let err = whereat::err_at!(
+        PlainError::NotFound,
+        user_id = user_id,
+        path = "/etc",
+        "looking up {}",
+        "profile"
+    );
+
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(
+        err.frames().next().unwrap().location().unwrap().line(),
+        line
+    );
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["looking up profile"]);
+    assert_eq!(
+        err.contexts().filter_map(|c| c.as_kv()).collect::<Vec<_>>(),
+        [("path", "/etc"), ("user_id", "42")] // newest first
+    );
+
+    // Fields only, message only, bare error
+    let err = whereat::err_at!(PlainError::NotFound, user_id = user_id,);
+    assert_eq!(err.texts().count(), 0);
+    assert_eq!(err.contexts().count(), 1);
+    let err = whereat::err_at!(PlainError::NotFound, "static message");
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["static message"]);
+    assert_eq!(whereat::err_at!(PlainError::NotFound).contexts().count(), 0);
+}
+
+// ============================================================================
+// Test: at_bail! and at_ensure! return early with the macro's location
+// ============================================================================
+
+#[test]
+fn at_bail_and_at_ensure_return_traced_errors() {
+    let bail_line = line!() + 2;
+    fn bail(user_id: u64) -> Result<(), At<PlainError>> {
+        whereat::at_bail!(
+            PlainError::NotFound,
+            user_id = user_id,
+            "loading {}",
+            "profile"
+        );
+    }
+
+    let ensure_line = line!() + 2;
+    fn ensure(len: usize) -> Result<usize, At<PlainError>> {
+        whereat::at_ensure!(len < 10, PlainError::NotFound);
+        Ok(len)
+    }
+
+    let err = bail(7).unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(
+        err.frames().next().unwrap().location().unwrap().line(),
+        bail_line
+    );
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["loading profile"]);
+    assert_eq!(err.kvs().collect::<Vec<_>>(), [("user_id", "7")]);
+
+    assert_eq!(ensure(3).unwrap(), 3);
+    let err = ensure(30).unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(
+        err.frames().next().unwrap().location().unwrap().line(),
+        ensure_line
+    );
+    assert_eq!(err.contexts().count(), 0);
+}
+
+// ============================================================================
+// Test: at_fmt! formats lazily onto the last frame
+// ============================================================================
+
+#[test]
+fn at_fmt_macro_formats_only_on_err() {
+    use whereat::at_fmt;
+
+    let id = 7;
+    let ok: Result<u32, At<PlainError>> = Ok(1);
+    let ok = at_fmt!(ok, "never {}", {
+        panic!("formatted on the Ok path");
+        #[allow(unreachable_code)]
+        0
+    });
+    assert_eq!(ok.unwrap(), 1);
+
+    let err: Result<u32, At<PlainError>> = Err(at(PlainError::NotFound));
+    let err = at_fmt!(err, "loading user {id} from {}", "users").unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(
+        err.texts().collect::<Vec<_>>(),
+        ["loading user 7 from users"]
+    );
+
+    // Traceable errors and bare At<E> too
+    let err: Result<(), TraceableError> = Err(TraceableError::parse());
+    let err = at_fmt!(err, "line {}", 3).unwrap_err();
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["line 3"]);
+    let err = at_fmt!(at(PlainError::NotFound), "bare {id}");
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["bare 7"]);
+}
+
+// ============================================================================
+// Test: find_context() searches typed contexts across all frames
+// ============================================================================
+
+#[test]
+fn find_context_searches_every_frame() {
+    #[derive(Debug, PartialEq)]
+    struct RequestId(u64);
+
+    let err = at(PlainError::NotFound)
+        .at_debug(|| RequestId(1))
+        .at()
+        .at_str("retrying")
+        .at_data(|| 42u32)
+        .at()
+        .at_debug(|| RequestId(2));
+
+    assert_eq!(err.find_context::<RequestId>(), Some(&RequestId(2)));
+    assert_eq!(
+        err.find_all_contexts::<RequestId>().collect::<Vec<_>>(),
+        [&RequestId(2), &RequestId(1)]
+    );
+    assert_eq!(err.find_context::<u32>(), Some(&42));
+    assert_eq!(err.find_context::<String>(), None);
+
+    let err = TraceableError::parse().at_debug(|| RequestId(3)).at();
+    assert_eq!(err.find_context::<RequestId>(), Some(&RequestId(3)));
+    assert_eq!(err.find_all_contexts::<u32>().count(), 0);
+}
+
+// ============================================================================
+// Test: Result extension traits record the caller, and offer at_kv()
+// ============================================================================
+
+#[test]
+fn result_traits_record_caller_location_and_kv() {
+    fn failing() -> Result<(), TraceableError> {
+        Err(TraceableError::parse())
+    }
+
+    let err = failing();
+    let line = line!() + 1;
+    let err = err.at().at_kv("attempt", 3).unwrap_err();
+    let last = err.trace().unwrap().frames().last().unwrap();
+    assert_eq!(last.location().unwrap().file(), file!());
+    assert_eq!(last.location().unwrap().line(), line);
+    assert_eq!(
+        last.contexts().find_map(|c| c.as_kv()),
+        Some(("attempt", "3"))
+    );
+    assert_eq!(err.get_context_value("attempt"), Some("3"));
+    assert_eq!(err.kvs().count(), 1);
+
+    let err = Err::<(), _>(at(PlainError::NotFound))
+        .at_kv("path", "/etc")
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("╰─ path=/etc"));
+
+    // The newest pair wins
+    let err = err.at_kv("path", "/var");
+    assert_eq!(err.get_context_value("path"), Some("/var"));
+    assert_eq!(err.get_context_value("missing"), None);
+}
+
+// ============================================================================
+// Test: contexts paired with their frame index and location
+// ============================================================================
+
+#[test]
+fn contexts_with_frames_pairs_locations_in_both_orders() {
+    use whereat::{AtContextOwned, AtOrder};
+
+    let mut err = at(PlainError::NotFound)
+        .at_str("origin")
+        .at_skipped_frames()
+        .at();
+    assert!(!err.annotate(1, AtContextOwned::text("markers take no contexts")));
+    let err = err.at_kv("k", "v");
+
+    let oldest: Vec<_> = err
+        .contexts_with_frames(AtOrder::OldestFirst)
+        .map(|(i, loc, ctx)| (i, loc.map(|l| l.file()), ctx.to_string()))
+        .collect();
+    assert_eq!(
+        oldest,
+        [
+            (0, Some(file!()), "origin".to_string()),
+            (2, Some(file!()), "k=v".to_string()),
+        ]
+    );
+
+    let newest: Vec<_> = err
+        .contexts_with_frames(AtOrder::NewestFirst)
+        .map(|(i, _, _)| i)
+        .collect();
+    assert_eq!(newest, [2, 0]);
+    // Same order as contexts()
+    assert!(
+        err.contexts_with_frames(AtOrder::NewestFirst)
+            .map(|(_, _, c)| c.to_string())
+            .eq(err.contexts().map(|c| c.to_string()))
+    );
+
+    // Embedded traces get the same listing
+    let traced = TraceableError::parse().at_str("parsing");
+    let (frame, loc, ctx) = traced
+        .contexts_with_frames(AtOrder::OldestFirst)
+        .next()
+        .unwrap();
+    assert_eq!((frame, ctx.as_text()), (0, Some("parsing")));
+    assert_eq!(loc.unwrap().file(), file!());
+}
+
+#[test]
+fn iterator_adapters_trace_every_error_item() {
+    use whereat::IteratorAtExt;
+
+    let items = [Ok(1), Err(at(PlainError::NotFound)), Ok(3)];
+    let (iter, line) = (items.into_iter().at_errors(), line!());
+    let traced: Vec<_> = iter.at_str_errors("reading rows").rev().collect();
+    assert_eq!(traced.len(), 3);
+    assert!(matches!(traced[0], Ok(3)));
+
+    let err = traced[1].as_ref().unwrap_err();
+    assert_eq!(err.frame_count(), 2);
+    assert_eq!(
+        err.frames().last().unwrap().location().unwrap().line(),
+        line
+    );
+    assert_eq!(
+        err.contexts().next().unwrap().as_text(),
+        Some("reading rows")
+    );
+}
+
+#[test]
+fn option_ok_or_at_starts_trace_at_caller() {
+    use whereat::OptionAtExt;
+
+    let (err, line) = (None::<u32>.ok_or_at(PlainError::NotFound), line!());
+    let err = err.unwrap_err();
+    assert!(matches!(err.error(), PlainError::NotFound));
+    assert_eq!(err.frame_count(), 1);
+    let loc = err.frames().next().unwrap().location().unwrap();
+    assert_eq!((loc.file(), loc.line()), (file!(), line));
+
+    let mut built = false;
+    let value = Some(5).ok_or_at_with(|| {
+        built = true;
+        PlainError::NotFound
+    });
+    assert_eq!(value.unwrap(), 5);
+    assert!(!built);
+
+    let err = None::<u32>
+        .ok_or_at_with(|| PlainError::InvalidInput("empty".into()))
+        .unwrap_err();
+    assert!(matches!(err.error(), PlainError::InvalidInput(s) if s == "empty"));
+
+    let err = None::<u32>
+        .ok_or_at_str(PlainError::NotFound, "looking up user")
+        .unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(
+        err.contexts().next().unwrap().as_text(),
+        Some("looking up user")
+    );
+}
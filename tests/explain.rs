@@ -0,0 +1,20 @@
+//! Tests for the diagnostic code explanation registry.
+
+#![cfg(feature = "std")]
+
+static LIBRARY: whereat::AtExplanations =
+    &[("E2001", "library text"), ("E2002", "only in the library")];
+
+static APP: whereat::AtExplanations = &[("E2001", "application override")];
+
+#[test]
+fn later_tables_override_earlier_ones() {
+    whereat::register_explanations(LIBRARY);
+    whereat::register_explanations(APP);
+    // Re-registering does not move a table to the front
+    whereat::register_explanations(LIBRARY);
+
+    assert_eq!(whereat::explain("E2001"), Some("application override"));
+    assert_eq!(whereat::explain("E2002"), Some("only in the library"));
+    assert_eq!(whereat::explain("E2999"), None);
+}
@@ -0,0 +1,103 @@
+//! Tests for the `eyre` report handler (the `eyre` feature).
+//!
+//! The hook is global and can only be installed once, so everything runs in
+//! one test function.
+
+#![cfg(feature = "eyre")]
+
+use eyre::WrapErr;
+use whereat::{At, AtEyreHook, AtTrace, AtTraceable, ResultAtExt, at};
+
+#[derive(Debug)]
+struct DbError;
+
+impl core::fmt::Display for DbError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("user not found")
+    }
+}
+
+impl core::error::Error for DbError {}
+
+#[derive(Debug)]
+struct PluginError {
+    trace: AtTrace,
+}
+
+impl core::fmt::Display for PluginError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("plugin crashed")
+    }
+}
+
+impl core::error::Error for PluginError {}
+
+impl AtTraceable for PluginError {
+    fn trace_mut(&mut self) -> &mut AtTrace {
+        &mut self.trace
+    }
+    fn trace(&self) -> Option<&AtTrace> {
+        Some(&self.trace)
+    }
+    fn fmt_message(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "plugin crashed")
+    }
+}
+
+#[derive(Debug)]
+struct Untraced;
+
+impl core::fmt::Display for Untraced {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("untraced")
+    }
+}
+
+impl core::error::Error for Untraced {}
+
+fn load() -> Result<(), At<DbError>> {
+    Err(at(DbError)).at_str("SELECT * FROM users WHERE id = 7")
+}
+
+#[test]
+fn reports_render_registered_traces() {
+    AtEyreHook::new()
+        .with::<At<DbError>>()
+        .with::<PluginError>()
+        .install()
+        .unwrap();
+
+    // Traced error wrapped with context: found in the chain
+    let report = load().wrap_err("loading profile").unwrap_err();
+    let output = format!("{:?}", report);
+    assert!(
+        output.starts_with("loading profile\n\nCaused by:\n    user not found\n\nTrace:\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("\n    at tests/eyre.rs:"));
+    assert!(output.contains("\n        SELECT * FROM users WHERE id = 7"));
+    assert!(!output.contains("Location:"));
+
+    // AtTraceable types work too
+    let plugin = PluginError {
+        trace: AtTrace::new(),
+    }
+    .at()
+    .at_str("running hook");
+    let output = format!("{:?}", eyre::Report::new(plugin));
+    assert!(output.starts_with("plugin crashed\n\nTrace:\n"));
+    assert!(output.contains("\n    at tests/eyre.rs:"));
+    assert!(output.contains("\n        running hook"));
+
+    // Unregistered errors show the report's creation site instead
+    let (report, line) = (eyre::Report::new(Untraced), line!());
+    let output = format!("{:?}", report);
+    assert!(output.starts_with(&format!(
+        "untraced\n\nLocation:\n    tests/eyre.rs:{}:",
+        line
+    )));
+
+    // {:#?} is the inner error's Debug, as with eyre's default handler
+    assert_eq!(format!("{:#?}", eyre::Report::new(Untraced)), "Untraced");
+}
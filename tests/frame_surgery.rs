@@ -3,7 +3,9 @@
 //! These APIs allow manipulation of trace frames for advanced use cases like
 //! transferring frames between traces or reordering error context.
 
-use whereat::{At, AtFrameOwned, AtTraceable, at};
+use whereat::{At, AtContextOwned, AtFrameOwned, AtTraceable, at};
+
+whereat::define_at_crate_info!();
 
 #[derive(Debug)]
 struct TestError;
@@ -240,6 +242,153 @@ fn frame_owned_builder_chain() {
     assert_eq!(frame.context_count(), 4);
 }
 
+// ============================================================================
+// Building traces from external capture sources
+// ============================================================================
+
+#[track_caller]
+fn external_location() -> &'static core::panic::Location<'static> {
+    core::panic::Location::caller()
+}
+
+#[test]
+fn from_locations_feeds_formatters() {
+    let locs = [external_location(), external_location()];
+    let trace = whereat::AtTrace::from_locations(locs);
+    assert_eq!(trace.frame_count(), 2);
+
+    let err = At::from_parts(TestError, trace);
+    let output = format!("{}", err.full_trace());
+    for loc in locs {
+        assert!(output.contains(&format!("frame_surgery.rs:{}:", loc.line())));
+    }
+}
+
+#[test]
+fn from_locations_respects_frame_limit() {
+    let loc = external_location();
+    let trace = whereat::AtTrace::from_locations(core::iter::repeat_n(loc, 500));
+    assert_eq!(trace.frame_count(), whereat::AT_MAX_FRAMES);
+}
+
+#[test]
+fn from_frames_keeps_contexts_and_markers() {
+    let trace = whereat::AtTrace::from_frames([
+        AtFrameOwned::capture().with_str("generated.tpl:12"),
+        AtFrameOwned::new(None),
+        AtFrameOwned::capture().with_context(AtContextOwned::debug(("node", 3))),
+    ]);
+
+    let frames: Vec<_> = trace.frames().collect();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(
+        frames[0].contexts().next().unwrap().as_text(),
+        Some("generated.tpl:12")
+    );
+    assert!(frames[1].is_skipped());
+    assert_eq!(
+        frames[2]
+            .contexts()
+            .next()
+            .unwrap()
+            .downcast_ref::<(&str, i32)>(),
+        Some(&("node", 3))
+    );
+}
+
+// ============================================================================
+// Annotating existing frames
+// ============================================================================
+
+#[test]
+fn annotate_targets_origin_frame() {
+    let mut err = at(TestError)
+        .at_str("origin ctx")
+        .at()
+        .at()
+        .at_str("last ctx");
+
+    assert!(err.annotate(0, AtContextOwned::text("tenant: acme")));
+    assert!(err.annotate(1, AtContextOwned::debug(42u32)));
+
+    let per_frame: Vec<Vec<String>> = err
+        .frames()
+        .map(|f| f.contexts().map(|c| c.to_string()).collect())
+        .collect();
+    assert_eq!(
+        per_frame,
+        vec![
+            vec!["origin ctx".to_string(), "tenant: acme".to_string()],
+            vec!["42".to_string()],
+            vec!["last ctx".to_string()],
+        ]
+    );
+
+    // Pop still returns the last frame's contexts only
+    let last = err.at_pop().unwrap();
+    assert_eq!(last.contexts().next().unwrap().as_text(), Some("last ctx"));
+    let middle = err.at_pop().unwrap();
+    assert_eq!(
+        middle.contexts().next().unwrap().downcast_ref::<u32>(),
+        Some(&42)
+    );
+}
+
+#[test]
+fn annotate_rejects_invalid_frames() {
+    let mut err = at(TestError).at_skipped_frames();
+    assert!(!err.annotate(1, AtContextOwned::text("skipped marker")));
+    assert!(!err.annotate(2, AtContextOwned::text("out of range")));
+
+    let mut empty = At::wrap(TestError);
+    assert!(!empty.annotate(0, AtContextOwned::text("no frames")));
+}
+
+// ============================================================================
+// Draining contexts
+// ============================================================================
+
+#[test]
+fn take_contexts_keeps_locations() {
+    let mut err = at(TestError)
+        .at_str("origin")
+        .at()
+        .at_string(|| "request body".to_string())
+        .at_debug(|| 7u8);
+
+    let contexts = err.take_contexts();
+
+    let summary: Vec<_> = contexts
+        .iter()
+        .map(|(idx, ctx)| (*idx, ctx.to_string()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (0, "origin".to_string()),
+            (1, "request body".to_string()),
+            (1, "7".to_string()),
+        ]
+    );
+
+    assert_eq!(err.frame_count(), 2);
+    assert_eq!(err.contexts().count(), 0);
+    assert!(err.take_contexts().is_empty());
+}
+
+#[test]
+fn take_contexts_keeps_crate_boundaries() {
+    let mut err = fake_dep::fetch_data("key")
+        .unwrap_err()
+        .at_crate(crate::at_crate_info())
+        .at_str("context");
+
+    let contexts = err.take_contexts();
+    assert_eq!(contexts.len(), 1);
+    assert!(err.contexts().all(|c| c.is_crate_boundary()));
+    assert_eq!(err.contexts().count(), 1);
+}
+
 // ============================================================================
 // Edge cases
 // ============================================================================
@@ -278,9 +427,9 @@ fn mixed_pop_and_pop_first() {
     // Only B should remain
     assert_eq!(err.frame_count(), 1);
     let debug = format!("{:?}", err);
-    assert!(debug.contains("B"));
-    assert!(!debug.contains("A"));
-    assert!(!debug.contains("C"));
+    assert!(debug.contains("╰─ B"));
+    assert!(!debug.contains("╰─ A"));
+    assert!(!debug.contains("╰─ C"));
 }
 
 #[test]
@@ -348,6 +497,17 @@ fn attraceable_pop_push() {
     assert_eq!(err.trace().unwrap().frame_count(), 1);
 }
 
+#[test]
+fn attraceable_take_contexts() {
+    let mut err = TraceableError::new().at().at_str("moved out");
+
+    let contexts = err.take_contexts();
+    assert_eq!(contexts.len(), 1);
+    assert_eq!(contexts[0].1.as_ref().as_text(), Some("moved out"));
+    assert_eq!(err.trace().unwrap().frame_count(), 1);
+    assert!(!err.trace().unwrap().frames().next().unwrap().has_contexts());
+}
+
 #[test]
 fn attraceable_first_insert() {
     let mut err = TraceableError::new().at().at_str("original");
@@ -361,3 +521,94 @@ fn attraceable_first_insert() {
     assert!(output.contains("inserted"));
     assert!(output.contains("original"));
 }
+
+// ============================================================================
+// Deduplication
+// ============================================================================
+
+fn recurse(err: At<TestError>, depth: u32) -> At<TestError> {
+    if depth == 0 {
+        return err;
+    }
+    recurse(err.at().at_string(|| format!("depth {}", depth)), depth - 1)
+}
+
+#[test]
+fn dedup_trace_collapses_consecutive_frames() {
+    let err = recurse(at(TestError), 3).at_skipped_frames();
+    let err = recurse(err, 2);
+    assert_eq!(err.frame_count(), 7);
+
+    let mut err = err.dedup_trace();
+    // origin, run of 3, skip marker, run of 2
+    let counts: Vec<_> = err.frames().map(|f| f.repeat_count()).collect();
+    assert_eq!(counts, [1, 3, 1, 2]);
+    assert!(err.frames().nth(2).unwrap().is_skipped());
+
+    // Absorbed frames hand their contexts to the kept frame
+    let texts: Vec<_> = err
+        .frames()
+        .nth(1)
+        .unwrap()
+        .contexts()
+        .filter_map(|c| c.as_text().map(str::to_string))
+        .collect();
+    assert_eq!(texts, ["depth 3", "depth 2", "depth 1"]);
+
+    let full = err.full_trace().to_string();
+    assert_eq!(full.matches(" (x3)\n").count(), 1);
+    assert!(full.ends_with(" (x2)\n        depth 2\n        depth 1"));
+    assert!(!full.contains("repeated"));
+
+    // Draining contexts keeps the counts
+    let _ = err.take_contexts();
+    let counts: Vec<_> = err.frames().map(|f| f.repeat_count()).collect();
+    assert_eq!(counts, [1, 3, 1, 2]);
+}
+
+#[test]
+fn compress_adds_up_counts() {
+    let err = recurse(at(TestError), 2).dedup_trace();
+    let mut err = recurse(err, 1);
+    assert_eq!(err.frame_count(), 3);
+
+    let mut trace = err.take_trace().unwrap();
+    assert_eq!(trace.compress(), 1);
+    let counts: Vec<_> = trace.frames().map(|f| f.repeat_count()).collect();
+    assert_eq!(counts, [1, 3]);
+    assert_eq!(trace.compress(), 0);
+}
+
+// ============================================================================
+// Merging traces
+// ============================================================================
+
+#[test]
+fn merge_policies_group_frames_by_source() {
+    use whereat::{AtMergePolicy, AtTrace};
+
+    fn branch(tags: &[&'static str]) -> AtTrace {
+        AtTrace::from_frames(tags.iter().map(|t| AtFrameOwned::capture().with_str(t)))
+    }
+    fn texts(trace: &AtTrace) -> Vec<&str> {
+        trace
+            .frames()
+            .map(|f| f.contexts().next().unwrap().as_text().unwrap())
+            .collect()
+    }
+
+    let mut trace = branch(&["a1", "a2"]);
+    trace.merge(branch(&["b1"]), AtMergePolicy::Append);
+    assert_eq!(texts(&trace), ["a1", "a2", "b1"]);
+
+    let mut trace = branch(&["a1", "a2"]);
+    trace.merge(branch(&["b1"]), AtMergePolicy::Grouped);
+    assert_eq!(texts(&trace), ["trace 1", "a1", "a2", "trace 2", "b1"]);
+    assert!(trace.frame(0).unwrap().is_skipped());
+    assert!(trace.frame(3).unwrap().is_skipped());
+
+    // An empty side gets no separator
+    let mut trace = AtTrace::new();
+    trace.merge(branch(&["b1"]), AtMergePolicy::Grouped);
+    assert_eq!(texts(&trace), ["trace 2", "b1"]);
+}
@@ -0,0 +1,97 @@
+//! Tests for tracing combinators on futures and streams (the `futures` feature).
+
+#![cfg(feature = "futures")]
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+use whereat::{At, FutureAtExt, at};
+
+#[derive(Debug)]
+struct MyError;
+
+async fn fails() -> Result<u32, At<MyError>> {
+    Err(at(MyError))
+}
+
+async fn succeeds() -> Result<u32, At<MyError>> {
+    Ok(7)
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut fut = pin!(fut);
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+    }
+}
+
+#[test]
+fn location_is_captured_at_the_combinator_call() {
+    let (pending, line) = (fails().at(), line!());
+    let err = block_on(pending).unwrap_err();
+    assert_eq!(err.frame_count(), 2);
+    let last = err.frames().last().unwrap().location().unwrap();
+    assert_eq!(last.file(), file!());
+    assert_eq!(last.line(), line);
+}
+
+#[test]
+fn str_and_string_attach_to_the_last_frame() {
+    let err = block_on(
+        fails()
+            .at_str("fetching")
+            .at_string(|| format!("user {}", 42)),
+    )
+    .unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    let texts: Vec<_> = err.contexts().filter_map(|c| c.as_text()).collect();
+    assert_eq!(texts, ["user 42", "fetching"]);
+}
+
+#[test]
+fn ok_path_is_untouched() {
+    let value = block_on(succeeds().at().at_string(|| unreachable!()));
+    assert_eq!(value.unwrap(), 7);
+}
+
+#[test]
+fn stream_errors_are_traced_at_the_adapter_call() {
+    use futures_core::Stream;
+    use whereat::StreamAtExt;
+
+    struct Items(Vec<Result<u32, At<MyError>>>);
+
+    impl Stream for Items {
+        type Item = Result<u32, At<MyError>>;
+
+        fn poll_next(
+            mut self: core::pin::Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.0.pop())
+        }
+    }
+
+    let items = Items(vec![Err(at(MyError)), Ok(1)]);
+    let (stream, line) = (items.at_errors().at_str_errors("receiving"), line!());
+    let mut stream = pin!(stream);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let Poll::Ready(Some(Ok(1))) = stream.as_mut().poll_next(&mut cx) else {
+        panic!("expected Ok(1)");
+    };
+    let Poll::Ready(Some(Err(err))) = stream.as_mut().poll_next(&mut cx) else {
+        panic!("expected an error");
+    };
+    assert_eq!(err.frame_count(), 2);
+    assert_eq!(
+        err.frames().last().unwrap().location().unwrap().line(),
+        line
+    );
+    assert_eq!(err.contexts().next().unwrap().as_text(), Some("receiving"));
+    assert!(matches!(stream.poll_next(&mut cx), Poll::Ready(None)));
+}
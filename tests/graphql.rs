@@ -0,0 +1,48 @@
+//! Tests for the `async-graphql` feature.
+
+#![cfg(feature = "async-graphql")]
+
+use serde_json::json;
+use whereat::{At, ResultAtExt, at};
+
+#[derive(Debug)]
+enum ApiError {
+    NotFound(u64),
+}
+
+impl core::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ApiError::NotFound(id) => write!(f, "user {} not found", id),
+        }
+    }
+}
+
+fn resolve(id: u64) -> async_graphql::Result<String> {
+    let found: Result<String, At<ApiError>> = Err(at(ApiError::NotFound(id)));
+    found.at_str("token=secret").map_err(At::into_graphql_error)
+}
+
+#[test]
+fn graphql_error_carries_trace_extensions() {
+    let err = resolve(7).unwrap_err();
+    let value = serde_json::to_value(&err).unwrap();
+
+    assert_eq!(value["message"], "user 7 not found");
+    let ext = &value["extensions"];
+    assert_eq!(ext["kind"], "NotFound");
+    assert_eq!(ext["fingerprint"].as_str().unwrap().len(), 16);
+    let trace = ext["trace"].as_array().unwrap();
+    assert_eq!(trace.len(), 1);
+    assert!(trace[0].as_str().unwrap().starts_with(file!()));
+    assert!(!value.to_string().contains("secret"));
+
+    // The traced error stays available to the server
+    let source = err.source.as_ref().unwrap();
+    let traced = source.downcast_ref::<At<ApiError>>().unwrap();
+    assert_eq!(
+        ext["fingerprint"],
+        json!(format!("{:016x}", traced.fingerprint()))
+    );
+    assert_eq!(traced.texts().collect::<Vec<_>>(), ["token=secret"]);
+}
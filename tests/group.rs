@@ -0,0 +1,103 @@
+//! Tests for AtGroup and try_join_at!.
+
+use std::time::Duration;
+use whereat::{At, AtGroup, ResultAtExt, at, try_join_at};
+
+#[derive(Debug, PartialEq)]
+struct MyError(&'static str);
+
+impl std::fmt::Display for MyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed", self.0)
+    }
+}
+
+async fn branch(name: &'static str, delay_ms: u64, ok: bool) -> Result<&'static str, At<MyError>> {
+    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    if ok {
+        Ok(name)
+    } else {
+        Err(at(MyError(name))).at_str("in branch")
+    }
+}
+
+#[tokio::test]
+async fn all_branches_succeed() {
+    let values = try_join_at!(
+        branch("a", 5, true),
+        branch("b", 1, true),
+        branch("c", 0, true)
+    );
+    assert_eq!(values.unwrap(), ("a", "b", "c"));
+
+    let single = try_join_at!(branch("only", 0, true));
+    assert_eq!(single.unwrap(), ("only",));
+}
+
+#[tokio::test]
+async fn every_failure_is_kept() {
+    // The fast failure must not cancel the slow one
+    let group = try_join_at!(
+        branch("slow", 10, false),
+        branch("ok", 0, true),
+        branch("fast", 0, false),
+    )
+    .unwrap_err();
+
+    let errors: Vec<_> = group.errors().iter().map(|e| e.error()).collect();
+    assert_eq!(errors, [&MyError("slow"), &MyError("fast")]);
+
+    // Each branch keeps its own trace
+    for error in group.errors() {
+        assert_eq!(error.frame_count(), 1);
+        assert_eq!(error.texts().collect::<Vec<_>>(), ["in branch"]);
+    }
+
+    // The join site is the shared frame
+    let shared = group.trace().unwrap();
+    assert_eq!(shared.frame_count(), 1);
+    assert_eq!(
+        shared.frames().next().unwrap().location().unwrap().file(),
+        file!()
+    );
+
+    assert_eq!(group.to_string(), "2 errors: slow failed; fast failed");
+}
+
+#[test]
+fn manual_group() {
+    let mut group = AtGroup::new();
+    assert!(group.is_empty());
+    group.push(at(MyError("x")));
+    assert_eq!(group.len(), 1);
+    assert_eq!(group.to_string(), "1 error: x failed");
+    assert_eq!(group.into_errors().len(), 1);
+}
+
+#[test]
+fn collected_group_renders_each_error_indented() {
+    let group: AtGroup<MyError> = [MyError("a"), MyError("b")]
+        .into_iter()
+        .map(|e| at(e).at_str("parsing"))
+        .collect();
+    assert!(group.trace().is_none());
+
+    let (mut group, line) = (group.at(), line!());
+    group.extend([at(MyError("c"))]);
+    assert_eq!(group.len(), 3);
+
+    let out = group.full_trace().to_string();
+    // The error-id feature adds an id line to every trace
+    let mut lines = out
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("error id:"));
+    assert_eq!(lines.next(), Some("3 errors"));
+    assert!(lines.next().unwrap().ends_with(&format!(":{}:36", line)));
+    assert_eq!(lines.next(), Some(""));
+    assert_eq!(lines.next(), Some("    a failed"));
+    assert!(lines.next().unwrap().starts_with("        at "));
+    assert_eq!(lines.next(), Some("            parsing"));
+    assert_eq!(lines.next(), Some(""));
+    assert_eq!(lines.next(), Some("    b failed"));
+    assert!(lines.any(|l| l == "    c failed"));
+}
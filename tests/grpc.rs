@@ -0,0 +1,68 @@
+//! Tests for the `tonic` integration (the `tonic` feature).
+
+#![cfg(feature = "tonic")]
+
+use tonic::{Code, Status};
+use whereat::{AT_TRACE_METADATA_KEY, At, AtTraceDetached, ResultAtExt, at};
+
+#[derive(Debug)]
+struct DbError;
+
+impl core::fmt::Display for DbError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("user not found")
+    }
+}
+
+fn find_user() -> Result<(), At<DbError>> {
+    Err(at(DbError)).at_str("looking up user")
+}
+
+// tonic handlers return `Status` unboxed
+#[allow(clippy::result_large_err)]
+fn handler() -> Result<(), Status> {
+    find_user().at()?;
+    Ok(())
+}
+
+#[test]
+fn status_carries_the_trace_to_the_client() {
+    let err = find_user().at_kv("user_id", 7).unwrap_err();
+    let expected = err.trace().unwrap().to_detached();
+
+    let status = err.into_status(Code::NotFound);
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.message(), "user not found");
+    assert!(status.metadata().get_bin(AT_TRACE_METADATA_KEY).is_some());
+    assert_eq!(AtTraceDetached::from_status(&status), Some(expected));
+
+    // The client nests it under its own frame
+    let remote = AtTraceDetached::from_status(&status).unwrap();
+    let client = at("upstream failed").at_remote_trace(remote);
+    let output = format!("{:?}", client);
+    assert!(output.contains("╰─ remote trace:\n          at tests/grpc.rs:"));
+    assert!(output.contains("\n             ╰─ looking up user\n"));
+}
+
+#[test]
+fn question_mark_sends_the_message_only() {
+    let status = handler().unwrap_err();
+    assert_eq!(status.code(), Code::Internal);
+    assert_eq!(status.message(), "user not found");
+    assert!(status.metadata().is_empty());
+    assert_eq!(AtTraceDetached::from_status(&status), None);
+}
+
+#[test]
+fn statuses_without_a_trace_have_none() {
+    let status = At::wrap(DbError).into_status(Code::Unavailable);
+    assert!(status.metadata().is_empty());
+    assert_eq!(AtTraceDetached::from_status(&status), None);
+
+    let mut foreign = Status::internal("boom");
+    foreign.metadata_mut().insert_bin(
+        AT_TRACE_METADATA_KEY,
+        tonic::metadata::MetadataValue::from_bytes(b"nope"),
+    );
+    assert_eq!(AtTraceDetached::from_status(&foreign), None);
+}
@@ -279,6 +279,255 @@ fn context_indentation_deeper_than_location() {
     );
 }
 
+// ============================================================================
+// Context Levels
+// ============================================================================
+
+#[test]
+fn leveled_contexts_show_labels_and_filter_by_threshold() {
+    use whereat::{AtLevel, AtTraceFormat};
+
+    let err = TestError::NotFound
+        .start_at()
+        .at_note("cache miss")
+        .at_warn("using stale replica")
+        .at_str("loading user");
+
+    let levels: Vec<_> = err.contexts().map(|c| c.level()).collect();
+    assert_eq!(levels, [AtLevel::Error, AtLevel::Warn, AtLevel::Note]);
+
+    let debug = format!("{:?}", err);
+    assert!(debug.contains("       ╰─ note: cache miss\n"));
+    assert!(debug.contains("       ╰─ warn: using stale replica\n"));
+    assert_eq!(err.debug_with(AtTraceFormat::new()).to_string(), debug);
+
+    let warn_and_up = AtTraceFormat::new().min_level(AtLevel::Warn);
+    let filtered = err.debug_with(warn_and_up).to_string();
+    assert!(!filtered.contains("cache miss"));
+    assert!(filtered.contains("warn: using stale replica"));
+    assert!(filtered.contains("loading user"));
+
+    let errors_only = AtTraceFormat::new().min_level(AtLevel::Error);
+    let full = err.full_trace_with(errors_only).to_string();
+    assert!(!full.contains("cache miss") && !full.contains("stale replica"));
+    assert!(full.contains("        loading user"));
+
+    // Filtering never removes the contexts themselves
+    assert_eq!(err.contexts().count(), 3);
+}
+
+#[test]
+fn pretty_debug_threshold_indents_large_contexts() {
+    use whereat::AtTraceFormat;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Request {
+        method: &'static str,
+        path: &'static str,
+    }
+
+    let err = TestError::NotFound
+        .start_at()
+        .at_debug(|| Request {
+            method: "POST",
+            path: "/api/v1/users",
+        })
+        .at_debug(|| 42u32);
+
+    // Default stays on one line
+    let one_line = format!("{:?}", err);
+    assert!(one_line.contains("       ╰─ Request { method: \"POST\", path: \"/api/v1/users\" }\n"));
+
+    let pretty = AtTraceFormat::new().pretty_debug(30);
+    let debug = err.debug_with(pretty).to_string();
+    assert!(debug.contains(
+        "       ╰─ Request {\n              method: \"POST\",\n              path: \"/api/v1/users\",\n          }\n"
+    ));
+    // Short values stay compact
+    assert!(debug.contains("       ╰─ 42\n"));
+
+    let full = err.full_trace_with(pretty).to_string();
+    assert!(full.contains("\n        Request {\n            method: \"POST\",\n"));
+    assert!(full.contains("\n        }"));
+    assert!(full.contains("\n        42"));
+}
+
+#[test]
+fn editor_location_styles_resolve_against_source_root() {
+    use whereat::{AtCrateInfo, AtLocationStyle, AtTraceFormat};
+
+    static NESTED: AtCrateInfo = AtCrateInfo::builder()
+        .name("nested")
+        .path(Some("crates/nested/"))
+        .build();
+
+    let err = TestError::NotFound.start_at();
+    let loc = err.frames().next().unwrap().location().unwrap();
+    let (line, col) = (loc.line(), loc.column());
+
+    let vscode = AtTraceFormat::new()
+        .location_style(AtLocationStyle::Vscode)
+        .source_root("/home/me/project/");
+    let debug = err.debug_with(vscode).to_string();
+    assert!(debug.contains(&format!(
+        "    at vscode://file/home/me/project/tests/output_format.rs:{}:{}\n",
+        line, col
+    )));
+
+    let file_uri = vscode.location_style(AtLocationStyle::FileUri);
+    let full = err.full_trace_with(file_uri).to_string();
+    assert!(full.contains(&format!(
+        "\n    at file:///home/me/project/tests/output_format.rs:{}:{}",
+        line, col
+    )));
+
+    // The crate's path within the repository goes between the root and the file
+    let err = At::wrap(TestError::NotFound).set_crate_info(&NESTED).at();
+    let line = err.frames().next().unwrap().location().unwrap().line();
+    let debug = err.debug_with(file_uri).to_string();
+    assert!(debug.contains(&format!(
+        "at file:///home/me/project/crates/nested/tests/output_format.rs:{}:",
+        line
+    )));
+
+    // Relative (the default) is unchanged
+    assert_eq!(
+        err.debug_with(AtTraceFormat::new()).to_string(),
+        format!("{:?}", err)
+    );
+}
+
+// ============================================================================
+// Pinned Contexts
+// ============================================================================
+
+#[test]
+fn pinned_context_shown_under_header() {
+    fn inner() -> Result<(), At<TestError>> {
+        Err(TestError::NotFound.start_at())
+            .at_str("reading shard 12")
+            .at_pinned("shard 12 is read-only during migration")
+    }
+
+    let err = inner().unwrap_err().at().at_str("serving request");
+    assert_eq!(
+        err.pinned().collect::<Vec<_>>(),
+        ["shard 12 is read-only during migration"]
+    );
+
+    let debug = format!("{:?}", err);
+    // Below the header (and error id, if enabled), above the frames
+    assert!(debug.starts_with("Error: NotFound\n"));
+    assert!(debug.contains("\n    shard 12 is read-only during migration\n\n    at "));
+    assert_eq!(debug.matches("read-only").count(), 1);
+
+    let full = err.full_trace().to_string();
+    assert!(full.starts_with("not found\n"));
+    assert!(full.contains("\n    shard 12 is read-only during migration\n    at "));
+    assert_eq!(full.matches("read-only").count(), 1);
+
+    // Still attached to its frame for programmatic access
+    let first = err.frames().next().unwrap();
+    assert!(first.contexts().any(|c| c.is_pinned()));
+    assert_eq!(err.frames().nth(1).unwrap().contexts().count(), 1);
+}
+
+// ============================================================================
+// Link Contexts
+// ============================================================================
+
+#[test]
+fn link_context_renders_see_line() {
+    let url = "https://grafana.example.com/d/queues";
+    let err = TestError::NotFound
+        .start_at()
+        .at_link("dashboard", url)
+        .at_link("runbook", String::from("https://wiki.example.com/rb/42"));
+
+    let links: Vec<_> = err.contexts().filter_map(|c| c.as_link()).collect();
+    assert_eq!(
+        links,
+        [
+            ("runbook", "https://wiki.example.com/rb/42"),
+            ("dashboard", url)
+        ]
+    );
+
+    let debug = format!("{:?}", err);
+    assert!(debug.contains("       ╰─ see: dashboard (https://grafana.example.com/d/queues)\n"));
+    let full = format!("{}", err.full_trace());
+    assert!(full.contains("        see: runbook (https://wiki.example.com/rb/42)"));
+}
+
+// ============================================================================
+// Help Section
+// ============================================================================
+
+#[test]
+fn help_hints_render_after_the_trace() {
+    let err = TestError::NotFound
+        .start_at()
+        .at_help("check the id")
+        .at_str("looking up user")
+        .at()
+        .at_help("try running `init` first");
+
+    assert_eq!(
+        err.suggestions().collect::<Vec<_>>(),
+        ["check the id", "try running `init` first"]
+    );
+
+    let debug = format!("{:?}", err);
+    assert!(
+        !debug.contains("╰─ help"),
+        "hints are not frame context:\n{}",
+        debug
+    );
+    let tail: Vec<_> = debug.lines().rev().take(2).collect();
+    assert_eq!(
+        tail,
+        [
+            "    help: try running `init` first",
+            "    help: check the id"
+        ]
+    );
+
+    let full = format!("{}", err.full_trace());
+    assert!(
+        full.ends_with("\n    help: check the id\n    help: try running `init` first"),
+        "Got:\n{}",
+        full
+    );
+    assert!(full.contains("        looking up user"));
+}
+
+// ============================================================================
+// Grouped Display
+// ============================================================================
+
+#[test]
+fn grouped_trace_merges_consecutive_frames_in_one_file() {
+    let first = TestError::NotFound.start_at();
+    let first_line = first.frames().next().unwrap().location().unwrap().line();
+    let err = first.at().at_str("dropped").at_skipped_frames().at();
+    let output = format!("{}", err.grouped_trace());
+    // Ignore the error id line when the `error-id` feature is on
+    let output: Vec<_> = output
+        .lines()
+        .filter(|line| !line.contains("error id:"))
+        .collect();
+
+    let expected = format!(
+        "not found\n    at {file}: lines {a} → {b}\n    [...]\n    at {file}: line {c}",
+        file = file!(),
+        a = first_line,
+        b = first_line + 2,
+        c = first_line + 2,
+    );
+    assert_eq!(output.join("\n"), expected);
+}
+
 // ============================================================================
 // Edge Cases
 // ============================================================================
@@ -403,6 +652,49 @@ fn at_source_is_none_when_inner_has_no_source() {
     );
 }
 
+#[test]
+fn attached_errors_are_reachable_as_sources() {
+    let disk = AppError {
+        msg: "write failed",
+        source: Some(IoError { msg: "disk full" }),
+    };
+    let traced: At<AppError> = at(AppError {
+        msg: "save failed",
+        source: None,
+    })
+    .at_error(IoError {
+        msg: "retry exhausted",
+    })
+    .at_error(disk);
+
+    // Without an inner source, source() falls back to the newest attached error
+    assert_eq!(
+        traced.source().unwrap().to_string(),
+        "app error: write failed"
+    );
+
+    // sources() visits every attached error, each followed by its own chain
+    let chain: Vec<String> = traced.sources().map(|e| e.to_string()).collect();
+    assert_eq!(
+        chain,
+        [
+            "app error: write failed",
+            "io error: disk full",
+            "io error: retry exhausted"
+        ]
+    );
+    let io = traced.downcast_source::<IoError>().unwrap();
+    assert_eq!(io.msg, "disk full");
+
+    // An inner source still comes first
+    let traced: At<AppError> = at(AppError {
+        msg: "request failed",
+        source: Some(IoError { msg: "reset" }),
+    })
+    .at_error(IoError { msg: "attached" });
+    assert_eq!(traced.source().unwrap().to_string(), "io error: reset");
+}
+
 #[test]
 fn error_chain_traversable() {
     let inner = IoError {
@@ -531,3 +823,294 @@ fn source_preserved_through_trace_operations() {
         "source should still be accessible after trace operations"
     );
 }
+
+// ============================================================================
+// Localization Hook
+// ============================================================================
+
+#[test]
+fn localized_message_keeps_trace_in_english() {
+    use whereat::AtMessage;
+
+    let catalog = [
+        ("NotFound", "introuvable"),
+        ("InvalidInput", "entrée invalide"),
+    ];
+    let french = |msg: &AtMessage<'_>| {
+        catalog
+            .iter()
+            .find(|(key, _)| *key == msg.variant())
+            .map(|(_, text)| format!("{} ({})", text, msg.default_message()))
+    };
+
+    let err = TestError::InvalidInput("x".into())
+        .start_at()
+        .at_str("parsing header");
+    assert_eq!(
+        err.localized(french).to_string(),
+        "entrée invalide (invalid input: x)"
+    );
+
+    let full = err.full_trace_localized(french).to_string();
+    let english = err.full_trace().to_string();
+    assert_eq!(
+        full.split_once('\n').unwrap().1,
+        english.split_once('\n').unwrap().1
+    );
+    assert!(full.contains("        parsing header"));
+
+    // No translation: falls back to the default message
+    let untranslated = |_: &AtMessage<'_>| None;
+    assert_eq!(
+        at(TestError::NotFound).localized(untranslated).to_string(),
+        "not found"
+    );
+}
+
+#[test]
+fn expected_actual_renders_aligned_block() {
+    use whereat::AtExpectedActual;
+
+    let err = at(TestError::NotFound).at_expected_actual("user_id", "userid".to_string());
+
+    // Debug: values line up, caret under the first differing character
+    let debug = format!("{:?}", err);
+    assert!(
+        debug.contains(
+            "       ╰─ expected: \"user_id\"\n          actual:   \"userid\"\n                         ^\n"
+        ),
+        "{}",
+        debug
+    );
+
+    // Full trace uses the context indent for continuation lines
+    let full = err.full_trace().to_string();
+    assert!(
+        full.contains("\n        expected: \"user_id\"\n        actual:   \"userid\"\n"),
+        "{}",
+        full
+    );
+
+    // Non-string values get no caret; typed values stay retrievable
+    let err = at(TestError::NotFound).at_expected_actual(3usize, 5usize);
+    let debug = format!("{:?}", err);
+    assert!(debug.contains("expected: 3\n          actual:   5\n"));
+    assert!(!debug.contains('^'));
+    let pair: &AtExpectedActual = err.contexts().find_map(|c| c.as_expected_actual()).unwrap();
+    assert_eq!(pair.expected::<usize>(), Some(&3));
+    assert_eq!(pair.actual::<usize>(), Some(&5));
+    assert_eq!(pair.to_string(), "expected: 3, actual: 5");
+}
+
+#[test]
+fn bytes_render_as_hex_dump() {
+    use whereat::AT_MAX_CONTEXT_BYTES;
+
+    let err = at(TestError::NotFound).at_bytes("request", b"GET /index.html HTTP/1.1\r\n");
+
+    // 16 bytes per row, short rows padded so the ASCII column lines up
+    let debug = format!("{:?}", err);
+    let expected = "       ╰─ request (26 bytes):\n\
+        \x20         0000  47 45 54 20 2f 69 6e 64 65 78 2e 68 74 6d 6c 20  |GET /index.html |\n\
+        \x20         0010  48 54 54 50 2f 31 2e 31 0d 0a                    |HTTP/1.1..|\n";
+    assert!(debug.contains(expected), "{}", debug);
+
+    let full = err.full_trace().to_string();
+    assert!(
+        full.contains("\n        request (26 bytes):\n        0000  47 45 54 20"),
+        "{}",
+        full
+    );
+
+    // Long inputs keep a bounded prefix and their original length
+    let big = [0xffu8; 1000];
+    let err = at(TestError::NotFound).at_bytes("payload", &big);
+    let bytes = err.contexts().find_map(|c| c.as_bytes()).unwrap();
+    assert_eq!(bytes.bytes().len(), AT_MAX_CONTEXT_BYTES);
+    assert_eq!(bytes.len(), 1000);
+    assert!(bytes.is_truncated());
+    let debug = format!("{:?}", err);
+    assert!(
+        debug.contains("payload (first 64 of 1000 bytes):"),
+        "{}",
+        debug
+    );
+    assert_eq!(debug.matches("|....").count(), AT_MAX_CONTEXT_BYTES / 16);
+}
+
+#[test]
+fn secrets_are_redacted_unless_revealed() {
+    use whereat::{AtContextKind, AtTraceFormat};
+
+    let err = at(TestError::NotFound)
+        .at_str("running query")
+        .at_secret(|| "email = 'a@b.c'");
+    let ctx = err.contexts().next().unwrap();
+    assert_eq!(ctx.kind(), AtContextKind::Secret);
+    assert_eq!(ctx.to_string(), "[redacted]");
+    assert_eq!(format!("{:?}", ctx), "[redacted]");
+
+    let outputs = [
+        format!("{:?}", err),
+        err.full_trace().to_string(),
+        err.display_with_meta().to_string(),
+        err.to_json(),
+    ];
+    for output in &outputs {
+        assert!(output.contains("[redacted]"), "{}", output);
+        assert!(!output.contains("a@b.c"), "{}", output);
+    }
+
+    // Only an explicit format shows the value; code can always read it
+    let revealed = err.display_unredacted().to_string();
+    assert!(
+        revealed.contains("╰─ \"email = 'a@b.c'\"\n"),
+        "{}",
+        revealed
+    );
+    let full = err
+        .full_trace_with(AtTraceFormat::new().redact(false))
+        .to_string();
+    assert!(full.contains("\n        \"email = 'a@b.c'\""), "{}", full);
+    assert_eq!(err.find_context::<&str>(), Some(&"email = 'a@b.c'"));
+}
+
+#[test]
+fn bare_trace_displays_like_full_trace_body() {
+    use whereat::{AtLevel, AtTraceBoxed, AtTraceFormat};
+
+    let mut err = at(TestError::NotFound)
+        .at_str("loading")
+        .at()
+        .at_note("cached");
+    let full = err.full_trace().to_string();
+    let trace = err.trace().unwrap();
+
+    // Everything after the message, minus the line break that followed it
+    let body = full.strip_prefix("not found\n").unwrap();
+    assert_eq!(trace.to_string(), body);
+    assert!(!trace.to_string().starts_with('\n'));
+
+    let quiet = AtTraceFormat::new().min_level(AtLevel::Warn);
+    assert_eq!(
+        trace.display_with(quiet).to_string(),
+        err.full_trace_with(quiet)
+            .to_string()
+            .strip_prefix("not found\n")
+            .unwrap()
+    );
+
+    // Boxed traces delegate; an unallocated one writes nothing
+    let body = body.to_string();
+    let boxed = AtTraceBoxed::from(err.take_trace().unwrap());
+    assert_eq!(boxed.to_string(), body);
+    assert_eq!(AtTraceBoxed::new().to_string(), "");
+}
+
+#[test]
+fn to_json_follows_documented_schema() {
+    use serde_json::{Value, json};
+    use whereat::AtCrateInfo;
+
+    static REPO: AtCrateInfo = AtCrateInfo::builder()
+        .name("app")
+        .repo(Some("https://github.com/org/app"))
+        .commit(Some("abc123"))
+        .build();
+
+    let err = At::wrap(TestError::InvalidInput("a \"quoted\"\nline".into()))
+        .set_crate_info(&REPO)
+        .at()
+        .at_str("parsing")
+        .at_kv("user_id", 42)
+        .at_bytes("header", b"GET /")
+        .at_skipped_frames();
+    let loc = err.frames().next().unwrap().location().unwrap();
+
+    let json = err.to_json();
+    let value: Value = serde_json::from_str(&json).unwrap_or_else(|e| panic!("{}: {}", e, json));
+
+    assert_eq!(value["version"], 1);
+    assert_eq!(value["error"], "invalid input: a \"quoted\"\nline");
+    let frames = value["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1], Value::Null);
+    assert_eq!(frames[0]["file"], file!());
+    assert_eq!(frames[0]["line"], loc.line());
+    assert_eq!(frames[0]["crate"], "app");
+    assert_eq!(
+        frames[0]["link"],
+        format!(
+            "https://github.com/org/app/blob/abc123/{}#L{}C{}",
+            file!(),
+            loc.line(),
+            loc.column()
+        )
+    );
+    assert_eq!(
+        frames[0]["contexts"],
+        json!([
+            { "kind": "text", "value": "parsing" },
+            { "kind": "key_value", "key": "user_id", "value": "42" },
+            { "kind": "bytes", "label": "header", "len": 5, "value": "R0VUIC8=" },
+        ])
+    );
+
+    // Without crate info there is no crate or link; control characters are escaped
+    let err = at(TestError::InvalidInput("\u{1}".into()));
+    let value: Value = serde_json::from_str(&err.to_json()).unwrap();
+    assert!(value["frames"][0].get("crate").is_none());
+    assert!(err.to_json().contains(r#""error":"invalid input: \u0001""#));
+}
+
+#[test]
+fn formatters_share_one_location_grammar() {
+    use whereat::AtTraceFormat;
+
+    #[derive(Debug)]
+    struct Failed;
+
+    impl core::fmt::Display for Failed {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("failed")
+        }
+    }
+
+    let (err, line) = (at(Failed), line!());
+    let with_col = format!("at {}:{}:{}", file!(), line, 24);
+    let without = format!("at {}:{}\n", file!(), line);
+
+    assert!(format!("{:?}", err).contains(&with_col));
+    assert!(err.full_trace().to_string().contains(&with_col));
+    assert!(err.display_with_meta().to_string().contains(&with_col));
+
+    let format = AtTraceFormat::new().columns(false);
+    assert!(err.debug_with(format).to_string().contains(&without));
+    assert!(format!("{}\n", err.full_trace_with(format)).contains(&without));
+    assert!(
+        err.display_with_meta_with(format)
+            .to_string()
+            .contains(&without)
+    );
+}
+
+#[test]
+fn path_options_shorten_relative_locations() {
+    use whereat::{AtCrateInfo, AtTraceFormat};
+
+    static TESTS: AtCrateInfo = AtCrateInfo::builder()
+        .name("tests")
+        .path(Some("tests/"))
+        .build();
+
+    let (err, line) = (at(TestError::NotFound).set_crate_info(&TESTS), line!());
+    let short = format!("    at output_format.rs:{}:", line);
+
+    let format = AtTraceFormat::new().crate_relative_paths(true);
+    assert!(err.debug_with(format).to_string().contains(&short));
+    let format = AtTraceFormat::new().strip_prefixes(&["src/", "tests/"]);
+    assert!(err.full_trace_with(format).to_string().contains(&short));
+
+    // Off by default
+    assert!(format!("{:?}", err).contains(&format!("    at {}:{}:", file!(), line)));
+}
@@ -0,0 +1,17 @@
+//! Tests for the trace-style panic hook.
+
+#![cfg(feature = "std")]
+
+whereat::define_at_crate_info!();
+
+#[test]
+fn hook_reports_and_unwinds() {
+    whereat::install_panic_hook(Some(crate::at_crate_info()));
+    let id = std::hint::black_box(7);
+    let result = std::panic::catch_unwind(|| panic!("boom {}", id));
+    let _ = std::panic::take_hook();
+
+    // The hook only prints; the payload still reaches the catcher
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<String>().unwrap(), "boom 7");
+}
@@ -0,0 +1,80 @@
+//! Tests for retry_at (the `retry` feature).
+
+#![cfg(feature = "retry")]
+
+use whereat::{At, ResultAtExt, at, retry_at};
+
+#[derive(Debug, PartialEq)]
+struct Busy(u32);
+
+#[test]
+fn succeeds_after_failures() {
+    let mut calls = 0;
+    let value = retry_at(5, || {
+        calls += 1;
+        if calls < 3 {
+            Err(at(Busy(calls)))
+        } else {
+            Ok(calls)
+        }
+    });
+    assert_eq!(value.unwrap(), 3);
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn every_attempt_keeps_its_frames() {
+    let mut calls = 0;
+    let mut origin_line = 0;
+    let mut attempt = || {
+        calls += 1;
+        origin_line = line!() + 1;
+        Err::<(), At<Busy>>(at(Busy(calls))).at_str("connecting")
+    };
+    let (result, line) = (retry_at(3, &mut attempt), line!());
+    let err = result.unwrap_err();
+    assert_eq!(err.error(), &Busy(3));
+
+    // Per attempt: origin, retry site; a skip marker between attempts
+    assert_eq!(err.frame_count(), 3 * 2 + 2);
+    let lines: Vec<_> = err
+        .frames()
+        .map(|f| f.location().map(|l| l.line()))
+        .collect();
+    let attempt = [Some(origin_line), Some(line)];
+    assert_eq!(
+        lines,
+        [&attempt[..], &[None], &attempt, &[None], &attempt].concat()
+    );
+
+    let labels: Vec<_> = err
+        .frames()
+        .filter_map(|f| f.contexts().find_map(|c| c.as_text()))
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "connecting",
+            "attempt 1/3",
+            "connecting",
+            "attempt 2/3",
+            "connecting",
+            "attempt 3/3"
+        ]
+    );
+}
+
+#[test]
+fn zero_attempts_runs_once() {
+    let mut calls = 0;
+    let err = retry_at(0, || {
+        calls += 1;
+        Err::<(), _>(at(Busy(calls)))
+    })
+    .unwrap_err();
+    assert_eq!(calls, 1);
+    assert_eq!(
+        err.contexts().next().unwrap().as_text(),
+        Some("attempt 1/1")
+    );
+}
@@ -0,0 +1,207 @@
+//! Tests for the process-wide capture switches.
+//!
+//! The switches are global, so everything runs in one test function to keep
+//! the other tests in this binary (there are none) from observing them.
+
+use whereat::{At, AtFrameOwned, AtTrace, AtTracePolicy, ResultAtExt, at};
+
+#[cfg(feature = "std")]
+#[path = "support/db_exec.rs"]
+mod db_exec;
+
+#[derive(Debug)]
+struct MyError;
+
+fn fail() -> Result<(), At<MyError>> {
+    Err(at(MyError)).at_str("failing")
+}
+
+#[test]
+fn enable_disable_and_sampling() {
+    assert!(whereat::is_enabled());
+    assert_eq!(whereat::sample_rate(), 1);
+
+    // Disabled: no frames, no contexts, existing traces stop growing
+    let started = fail().unwrap_err();
+    let mut moved_later = fail().unwrap_err();
+    whereat::set_enabled(false);
+    let err = fail().unwrap_err().at().at_str("more");
+    assert_eq!(err.frame_count(), 0);
+    assert_eq!(err.contexts().count(), 0);
+    assert!(err.trace().is_none());
+    let grown = started.at().at_str("after disable");
+    assert_eq!(grown.frame_count(), 1);
+    assert_eq!(grown.contexts().count(), 1);
+    let grown = grown.at_origin_str("origin after disable");
+    assert_eq!(grown.contexts().count(), 1);
+    assert_eq!(at(MyError).at_origin_str("origin").contexts().count(), 0);
+    // Moving frames that were already captured is not capture
+    let moved = AtTrace::from_frames([AtFrameOwned::new(None), AtFrameOwned::new(None)]);
+    assert_eq!(moved.frame_count(), 2);
+    let mut target = AtTrace::new();
+    target.append(moved_later.take_trace().unwrap());
+    assert_eq!(target.frame_count(), 1);
+    whereat::set_enabled(true);
+    assert_eq!(fail().unwrap_err().frame_count(), 1);
+
+    // Sampling: one in four traces start; started traces keep every frame
+    let mut sampled_later = fail().unwrap_err();
+    whereat::set_sample_rate(4);
+    let errors: Vec<_> = (0..40).map(|_| fail().unwrap_err().at()).collect();
+    let traced: Vec<_> = errors.iter().filter(|e| e.frame_count() > 0).collect();
+    assert_eq!(traced.len(), 10);
+    assert!(traced.iter().all(|e| e.frame_count() == 2));
+    let mut target = AtTrace::new();
+    target.append(sampled_later.take_trace().unwrap());
+    assert_eq!(target.frame_count(), 1);
+    for _ in 0..4 {
+        assert_eq!(
+            AtTrace::from_frames([AtFrameOwned::new(None)]).frame_count(),
+            1
+        );
+    }
+    assert!(
+        traced
+            .iter()
+            .all(|e| e.contexts().any(|c| c.as_text() == Some("failing")))
+    );
+
+    whereat::set_sample_rate(0);
+    assert_eq!(whereat::sample_rate(), 1);
+    assert_eq!(fail().unwrap_err().frame_count(), 1);
+
+    policy();
+    #[cfg(feature = "std")]
+    rate_limit();
+    #[cfg(feature = "std")]
+    skip_files();
+}
+
+fn policy() {
+    assert_eq!(whereat::trace_policy(), AtTracePolicy::DEFAULT);
+
+    // No contexts: frames are still recorded, closures never run
+    whereat::configure(AtTracePolicy::new().capture_contexts(false));
+    let err = fail()
+        .unwrap_err()
+        .at_string(|| unreachable!("context closure called"))
+        .at();
+    assert_eq!(err.frame_count(), 2);
+    assert_eq!(err.contexts().count(), 0);
+    assert!(!whereat::trace_policy().captures_contexts());
+
+    // Depth limit: oldest frames kept, the middle folded into a marker
+    whereat::configure(AtTracePolicy::new().max_depth(5));
+    let mut err = fail().unwrap_err();
+    for _ in 0..10 {
+        err = err.at();
+    }
+    let frames: Vec<_> = err.frames().collect();
+    assert_eq!(frames.len(), 5);
+    assert!(frames[0].location().is_some());
+    assert!(frames[1].location().is_some());
+    assert!(frames[2].is_skipped());
+    assert_eq!(frames[2].repeat_count(), 7); // 11 frames, 4 kept
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["failing"]);
+
+    // Too shallow for a marker: new frames are dropped
+    whereat::configure(AtTracePolicy::new().max_depth(0));
+    assert_eq!(whereat::trace_policy().depth_limit(), 1);
+    assert_eq!(fail().unwrap_err().at().at().frame_count(), 1);
+
+    // Capture off is the same switch as set_enabled()
+    whereat::configure(AtTracePolicy::new().capture(false));
+    assert!(!whereat::is_enabled());
+    assert_eq!(fail().unwrap_err().frame_count(), 0);
+
+    whereat::configure(AtTracePolicy::new());
+    assert_eq!(fail().unwrap_err().at().frame_count(), 2);
+    assert_eq!(whereat::trace_policy(), AtTracePolicy::default());
+}
+
+#[cfg(feature = "std")]
+fn rate_limit() {
+    use std::time::Duration;
+    use whereat::AtCrateInfo;
+
+    fn other() -> Result<(), At<MyError>> {
+        Err(at(MyError))
+    }
+
+    whereat::set_rate_limit(3, Duration::from_millis(200));
+    let errors: Vec<_> = (0..5).map(|_| fail().unwrap_err().at()).collect();
+    for err in &errors[..3] {
+        assert!(!err.trace().unwrap().is_rate_limited());
+        assert_eq!(err.frame_count(), 2);
+        assert_eq!(err.texts().count(), 1);
+    }
+    for (err, suppressed) in errors[3..]
+        .iter()
+        .zip(["1 similar error", "2 similar errors"])
+    {
+        let trace = err.trace().unwrap();
+        assert!(trace.is_rate_limited());
+        assert_eq!(err.frame_count(), 1);
+        assert_eq!(err.texts().count(), 0);
+        let origin = trace.frame(0).unwrap().location().unwrap();
+        assert_eq!(
+            origin,
+            errors[0].frames().next().unwrap().location().unwrap()
+        );
+        let expected = format!("warn: {} suppressed", suppressed);
+        assert!(format!("{:?}", err).contains(&expected));
+    }
+
+    // Nothing more is recorded, origin contexts included
+    let err = fail().unwrap_err().at_origin_str("origin");
+    assert!(err.trace().unwrap().is_rate_limited());
+    assert_eq!(err.texts().count(), 0);
+
+    // Rate-limited traces keep their crate info for repository links
+    static APP: AtCrateInfo = AtCrateInfo::builder().name("app").build();
+    let err = fail().unwrap_err().set_crate_info(&APP);
+    assert!(err.trace().unwrap().is_rate_limited());
+    assert_eq!(err.trace().unwrap().crate_info().unwrap().name(), "app");
+
+    // Counted per origin
+    assert!(!other().unwrap_err().trace().unwrap().is_rate_limited());
+
+    // A new window starts over
+    std::thread::sleep(Duration::from_millis(250));
+    assert!(!fail().unwrap_err().trace().unwrap().is_rate_limited());
+
+    whereat::clear_rate_limit();
+    for _ in 0..5 {
+        assert_eq!(fail().unwrap_err().at().frame_count(), 2);
+    }
+}
+
+#[cfg(feature = "std")]
+fn skip_files() {
+    fn caller() -> Result<(), At<db_exec::DbError>> {
+        db_exec::exec().at()
+    }
+
+    fn wrapped_here() -> Result<(), At<db_exec::DbError>> {
+        db_exec::exec_via(Err(at(db_exec::DbError)))
+    }
+
+    whereat::set_skip_files(&["no/such/file.rs", "*/support/db_*.rs"]);
+
+    // The origin inside the wrapper and its context are dropped; the caller's frame stays
+    let err = caller().unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(
+        err.frames().next().unwrap().location().unwrap().file(),
+        file!()
+    );
+    assert_eq!(err.texts().count(), 0);
+
+    // A wrapper adding context to an existing trace attaches it to the previous frame
+    let err = wrapped_here().unwrap_err();
+    assert_eq!(err.frame_count(), 1);
+    assert_eq!(err.texts().collect::<Vec<_>>(), ["from exec_via"]);
+
+    whereat::clear_skip_files();
+    assert_eq!(caller().unwrap_err().frame_count(), 2);
+}
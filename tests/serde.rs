@@ -0,0 +1,242 @@
+//! Tests for the `serde` feature.
+
+#![cfg(feature = "serde")]
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use whereat::{At, ResultAtExt, at};
+
+#[derive(Debug, Serialize)]
+enum DbError {
+    NotFound { id: u64 },
+}
+
+impl core::fmt::Display for DbError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DbError::NotFound { id } => write!(f, "user {} not found", id),
+        }
+    }
+}
+
+fn find(id: u64) -> Result<(), At<DbError>> {
+    Err(at(DbError::NotFound { id }))
+}
+
+#[test]
+fn inner_error_is_serialized_structurally() {
+    let err = find(42).at().at_str("looking up user").unwrap_err();
+    let value = serde_json::to_value(&err).unwrap();
+
+    assert_eq!(value["error"], json!({ "NotFound": { "id": 42 } }));
+
+    let frames = value["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0]["file"], file!());
+    assert!(frames[0]["line"].as_u64().unwrap() > 0);
+    assert_eq!(frames[0]["contexts"], json!([]));
+    assert_eq!(frames[1]["contexts"], json!(["looking up user"]));
+}
+
+#[test]
+fn skipped_frames_serialize_as_null() {
+    let err = at(DbError::NotFound { id: 1 }).at_skipped_frames().at();
+    let value = serde_json::to_value(&err).unwrap();
+    let frames = value["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[1], Value::Null);
+}
+
+#[test]
+fn untraced_error_has_no_frames() {
+    let err = At::wrap(DbError::NotFound { id: 7 });
+    let value = serde_json::to_value(&err).unwrap();
+    assert_eq!(value["frames"], json!([]));
+    assert_eq!(value["error"]["NotFound"]["id"], 7);
+}
+
+#[test]
+fn frames_carry_resolved_crate_info() {
+    use whereat::AtCrateInfo;
+
+    static DEP: AtCrateInfo = AtCrateInfo::builder()
+        .name("dep")
+        .repo(Some("https://gitlab.com/org/dep"))
+        .commit(Some("abc123"))
+        .path(Some("crates/dep/"))
+        .link_format(whereat::GITLAB_LINK_FORMAT)
+        .build();
+    static APP: AtCrateInfo = AtCrateInfo::builder().name("app").build();
+
+    let err = At::wrap(DbError::NotFound { id: 1 })
+        .set_crate_info(&DEP)
+        .at()
+        .at()
+        .at_crate(&APP)
+        .at_str("in app");
+    let value = serde_json::to_value(&err).unwrap();
+    let frames = value["frames"].as_array().unwrap();
+
+    assert_eq!(
+        frames[0]["crate"],
+        json!({
+            "name": "dep",
+            "repo": "https://gitlab.com/org/dep",
+            "commit": "abc123",
+            "path": "crates/dep/",
+            "link_format": whereat::GITLAB_LINK_FORMAT,
+        })
+    );
+    assert_eq!(frames[1]["crate"]["name"], "app");
+    assert_eq!(frames[1]["crate"]["repo"], Value::Null);
+    // The boundary itself isn't repeated as a context
+    assert_eq!(frames[1]["contexts"], json!(["in app"]));
+
+    // No crate info, no field
+    let plain = serde_json::to_value(find(1).unwrap_err()).unwrap();
+    assert!(plain["frames"][0].get("crate").is_none());
+}
+
+#[test]
+fn problem_details_follow_rfc_9457() {
+    use whereat::AtProblem;
+
+    let err = find(7).at_str("password=hunter2").unwrap_err();
+    let problem = err
+        .to_problem()
+        .with_type("https://example.com/probs/not-found")
+        .with_status(404)
+        .with_detail("no such user");
+    let value = serde_json::to_value(&problem).unwrap();
+
+    assert_eq!(value["type"], "https://example.com/probs/not-found");
+    assert_eq!(value["title"], "user 7 not found");
+    assert_eq!(value["status"], 404);
+    assert_eq!(value["detail"], "no such user");
+    assert!(value.get("instance").is_none());
+    assert_eq!(
+        value["fingerprint"],
+        format!("{:016x}", err.fingerprint()).as_str()
+    );
+    let trace = value["trace"].as_array().unwrap();
+    assert_eq!(trace.len(), 1);
+    assert!(trace[0].as_str().unwrap().starts_with(file!()));
+    assert!(!value.to_string().contains("hunter2"));
+
+    // Defaults: type is about:blank, optional members omitted
+    let value = serde_json::to_value(err.to_problem()).unwrap();
+    assert_eq!(value["type"], "about:blank");
+    assert!(value.get("status").is_none());
+    assert_eq!(AtProblem::CONTENT_TYPE, "application/problem+json");
+}
+
+#[test]
+fn key_values_serialize_as_objects() {
+    let err = find(1).at_str("loading").at_kv("user_id", 42).unwrap_err();
+    let value = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        value["frames"][0]["contexts"],
+        json!(["loading", { "key": "user_id", "value": "42" }])
+    );
+    assert_eq!(err.get_context_value("user_id"), Some("42"));
+}
+
+#[test]
+fn bytes_serialize_as_base64() {
+    let err = find(1).at_bytes("header", b"GET /").unwrap_err();
+    let value = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        value["frames"][0]["contexts"],
+        json!([{ "label": "header", "len": 5, "base64": "R0VUIC8=" }])
+    );
+
+    // Every padding length round-trips the standard alphabet
+    for (input, encoded) in [(&b"\xfb\xff"[..], "+/8="), (b"abc", "YWJj"), (b"a", "YQ==")] {
+        let value =
+            serde_json::to_value(at(DbError::NotFound { id: 1 }).at_bytes("x", input)).unwrap();
+        assert_eq!(value["frames"][0]["contexts"][0]["base64"], encoded);
+    }
+}
+
+#[test]
+fn trace_frame_and_context_serialize_standalone() {
+    use whereat::{AtTraceBoxed, AtTraceable};
+
+    // A hand-rolled error whose fields don't implement Serialize
+    #[derive(Debug)]
+    struct Opaque {
+        trace: AtTraceBoxed,
+    }
+
+    impl AtTraceable for Opaque {
+        fn trace_mut(&mut self) -> &mut whereat::AtTrace {
+            self.trace.get_or_insert_mut()
+        }
+        fn trace(&self) -> Option<&whereat::AtTrace> {
+            self.trace.as_ref()
+        }
+        fn fmt_message(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("opaque")
+        }
+    }
+
+    let err = Opaque {
+        trace: AtTraceBoxed::new(),
+    }
+    .at()
+    .at_str("reading header");
+
+    // The whole trace serializes like At<E> without its "error"
+    let value = serde_json::to_value(&err.trace).unwrap();
+    let frames = value["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0]["file"], file!());
+    assert_eq!(frames[0]["contexts"], json!(["reading header"]));
+    // Frames and contexts on their own
+    let trace = err.trace.as_ref().unwrap();
+    let frame = trace.frames().next().unwrap();
+    assert_eq!(serde_json::to_value(frame).unwrap(), frames[0]);
+    let ctx = frame.contexts().next().unwrap();
+    assert_eq!(serde_json::to_value(ctx).unwrap(), json!("reading header"));
+
+    // An unallocated trace has no frames
+    let empty = serde_json::to_value(AtTraceBoxed::new()).unwrap();
+    assert_eq!(empty["frames"], json!([]));
+}
+
+#[test]
+fn detached_traces_deserialize_from_serialized_errors() {
+    use whereat::AtTraceDetached;
+
+    let err = find(1)
+        .at_str("loading")
+        .at_kv("user_id", 42)
+        .unwrap_err()
+        .at_skipped_frames()
+        .at_named("handler");
+    let json = serde_json::to_string(&err).unwrap();
+
+    // A serialized `At<E>` reads back as its trace; the error is ignored
+    let detached = err.trace().unwrap().to_detached();
+    let remote: AtTraceDetached = serde_json::from_str(&json).unwrap();
+    assert_eq!(remote.to_string(), detached.to_string());
+
+    // The owned form round-trips through its own serialization
+    let value = serde_json::to_value(&detached).unwrap();
+    assert_eq!(value["frames"][1], Value::Null);
+    assert_eq!(value["frames"][2]["function"], "handler");
+    let again: AtTraceDetached = serde_json::from_value(value).unwrap();
+    assert_eq!(again, detached);
+}
+
+#[test]
+fn correlation_id_is_a_top_level_member() {
+    let err = find(1).at_correlation("req-7f3a").unwrap_err();
+    let value = serde_json::to_value(&err).unwrap();
+    assert_eq!(value["correlation_id"], "req-7f3a");
+    assert_eq!(
+        serde_json::to_value(err.trace().unwrap()).unwrap()["correlation_id"],
+        "req-7f3a"
+    );
+    assert!(serde_json::to_value(find(1).unwrap_err()).unwrap()["correlation_id"].is_null());
+}
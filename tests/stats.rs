@@ -0,0 +1,110 @@
+//! Tests for AtStats, per-trace stats and fingerprints.
+
+use whereat::{At, AtStats, ResultAtExt, at};
+
+#[derive(Debug)]
+#[allow(dead_code)]
+enum JobError {
+    Timeout(u32),
+    BadInput { line: u32 },
+}
+
+fn timeout(job: u32) -> Result<(), At<JobError>> {
+    Err(at(JobError::Timeout(job)))
+}
+
+fn bad_input(line: u32) -> Result<(), At<JobError>> {
+    Err(at(JobError::BadInput { line }))
+}
+
+fn via_retry(job: u32) -> Result<(), At<JobError>> {
+    timeout(job).at_str("retrying")
+}
+
+#[test]
+fn fingerprint_ignores_payload_and_contexts() {
+    let a = timeout(1).unwrap_err();
+    let b = timeout(2).unwrap_err().at_str("extra context");
+    assert_eq!(a.fingerprint(), b.fingerprint());
+
+    // Same path, different kind
+    let c = bad_input(1).unwrap_err();
+    assert_ne!(a.fingerprint(), c.fingerprint());
+
+    // Same kind and origin, longer path
+    let d = via_retry(1).unwrap_err().at();
+    assert_ne!(a.fingerprint(), d.fingerprint());
+}
+
+#[test]
+fn stats_group_by_kind_origin_and_fingerprint() {
+    let mut errors = Vec::new();
+    for job in 0..4 {
+        errors.push(timeout(job).unwrap_err());
+    }
+    errors.push(via_retry(9).unwrap_err().at());
+    errors.push(bad_input(3).unwrap_err());
+
+    let mut stats = AtStats::new();
+    assert!(stats.is_empty());
+    stats.extend(&errors);
+
+    assert_eq!(stats.total(), 6);
+    assert_eq!(stats.by_kind(), [("Timeout", 5), ("BadInput", 1)]);
+
+    // via_retry's error originates inside timeout()
+    let origins = stats.by_origin();
+    assert_eq!(origins.len(), 2);
+    assert_eq!(origins[0].1, 5);
+    assert_eq!(origins[0].0.file(), file!());
+
+    let fingerprints = stats.by_fingerprint();
+    let counts: Vec<_> = fingerprints.iter().map(|(_, n)| *n).collect();
+    assert_eq!(counts, [4, 1, 1]);
+    assert_eq!(fingerprints[0].0, errors[0].fingerprint());
+
+    let table = stats.to_string();
+    assert!(table.starts_with("6 errors\n"));
+    assert!(table.contains("\nby kind:\n       5  Timeout\n       1  BadInput\n"));
+    assert!(table.contains(&format!("{:016x}  Timeout at ", errors[0].fingerprint())));
+}
+
+#[test]
+fn stats_from_owned_errors() {
+    let stats: AtStats = (0..3).map(|line| bad_input(line).unwrap_err()).collect();
+    assert_eq!(stats.by_fingerprint().len(), 1);
+    assert_eq!(stats.to_string().lines().next(), Some("3 errors"));
+}
+
+#[test]
+fn trace_stats_counts_frames_contexts_and_bytes() {
+    use whereat::AtContextKind;
+
+    let empty = At::wrap(JobError::Timeout(0));
+    assert_eq!(empty.trace_stats(), Default::default());
+
+    let err = via_retry(7)
+        .at_string(|| format!("job {}", 7))
+        .at_debug(|| vec![1u8; 16])
+        .map_err(|e| e.at_skipped_frames().at())
+        .unwrap_err();
+    let stats = err.trace_stats();
+    assert_eq!(stats.frames(), err.frame_count());
+    assert_eq!(stats.skipped_frames(), 1);
+    assert_eq!(stats.contexts(), 3);
+    assert_eq!(stats.contexts_of(AtContextKind::Text), 2);
+    assert_eq!(stats.contexts_of(AtContextKind::Debug), 1);
+    assert_eq!(
+        stats.contexts_by_kind().collect::<Vec<_>>(),
+        [(AtContextKind::Text, 2), (AtContextKind::Debug, 1)]
+    );
+
+    // Owned payloads count towards heap use; borrowed strings don't
+    let borrowed = via_retry(1).unwrap_err().trace_stats().heap_bytes();
+    let owned = timeout(1)
+        .at_string(|| "retrying".to_string())
+        .unwrap_err()
+        .trace_stats()
+        .heap_bytes();
+    assert!(owned >= borrowed + "retrying".len());
+}
@@ -0,0 +1,14 @@
+//! A helper wrapper whose frames the skip list should hide.
+
+use whereat::{At, ResultAtExt, at};
+
+#[derive(Debug)]
+pub struct DbError;
+
+pub fn exec() -> Result<(), At<DbError>> {
+    Err(at(DbError)).at_str("executing query")
+}
+
+pub fn exec_via<T>(result: Result<T, At<DbError>>) -> Result<T, At<DbError>> {
+    result.at_str("from exec_via")
+}
@@ -0,0 +1,74 @@
+//! Tests for overriding captured locations (the `test-util` feature).
+
+#![cfg(feature = "test-util")]
+
+use core::panic::Location;
+use whereat::test_util::{freeze_locations, map_locations};
+use whereat::{At, ResultAtExt, at};
+
+#[derive(Debug)]
+struct MyError;
+
+static FROZEN: &Location<'static> = Location::caller();
+static OTHER: &Location<'static> = Location::caller();
+
+fn fails() -> Result<(), At<MyError>> {
+    Err(at(MyError)).at_str("inner")
+}
+
+fn first_location(err: &At<MyError>) -> &'static Location<'static> {
+    err.frames().next().unwrap().location().unwrap()
+}
+
+#[test]
+fn frozen_output_is_stable() {
+    let err = {
+        let _frozen = freeze_locations(FROZEN);
+        fails().at().at_str("outer").unwrap_err()
+    };
+    assert_eq!(err.frame_count(), 2);
+    assert!(err.frames().all(|f| f.location() == Some(FROZEN)));
+
+    // Every frame line is the frozen location, whatever line the errors came from
+    let output = format!("{:?}", err);
+    let frame_lines: Vec<_> = output
+        .lines()
+        .filter(|l| l.starts_with("    at "))
+        .collect();
+    let frozen = format!(
+        "    at {}:{}:{}",
+        FROZEN.file(),
+        FROZEN.line(),
+        FROZEN.column()
+    );
+    assert_eq!(frame_lines, [frozen.as_str(), frozen.as_str()]);
+    assert!(output.contains("╰─ inner\n") && output.contains("╰─ outer\n"));
+
+    // Captures after the guard drops are real again
+    assert_ne!(first_location(&fails().unwrap_err()), FROZEN);
+}
+
+#[test]
+fn guards_nest_and_map() {
+    fn to_other(_: &'static Location<'static>) -> &'static Location<'static> {
+        OTHER
+    }
+
+    let outer = freeze_locations(FROZEN);
+    {
+        let _inner = map_locations(to_other);
+        assert_eq!(first_location(&fails().unwrap_err()), OTHER);
+    }
+    assert_eq!(first_location(&fails().unwrap_err()), FROZEN);
+    drop(outer);
+    assert_eq!(first_location(&fails().unwrap_err()).file(), file!());
+}
+
+#[test]
+fn override_is_per_thread() {
+    let _frozen = freeze_locations(FROZEN);
+    let other_thread = std::thread::spawn(|| first_location(&fails().unwrap_err()))
+        .join()
+        .unwrap();
+    assert_ne!(other_thread, FROZEN);
+}
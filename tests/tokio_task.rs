@@ -0,0 +1,112 @@
+//! Tests for recording tokio task ids (the `tokio` feature).
+
+#![cfg(feature = "tokio")]
+
+use whereat::{At, ResultAtExt, at};
+
+#[derive(Debug)]
+struct MyError;
+
+impl std::fmt::Display for MyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "my error")
+    }
+}
+
+async fn fails() -> Result<(), At<MyError>> {
+    Err(at(MyError))
+}
+
+#[test]
+fn no_task_outside_runtime() {
+    let err = at(MyError).at();
+    assert_eq!(err.task_id(), None);
+    assert!(!err.crossed_task_boundary());
+    assert!(!err.full_trace().to_string().contains("task:"));
+}
+
+#[tokio::test]
+async fn task_id_recorded_on_first_capture() {
+    let handle = tokio::spawn(async {
+        let id = tokio::task::id();
+        (id, fails().await.at().unwrap_err())
+    });
+    let (spawned_id, err) = handle.await.unwrap();
+
+    assert_eq!(err.task_id(), Some(spawned_id));
+    assert!(!err.crossed_task_boundary());
+    assert!(
+        err.full_trace()
+            .to_string()
+            .contains(&format!("\n    task: {}\n", spawned_id))
+    );
+}
+
+#[tokio::test]
+async fn crossing_a_task_boundary_is_flagged() {
+    let spawned = tokio::spawn(async { fails().await.unwrap_err() });
+    let spawned_id = spawned.id();
+
+    // Traced again by the awaiting task
+    let err = tokio::spawn(async move { spawned.await.unwrap().at() })
+        .await
+        .unwrap();
+
+    assert_eq!(err.task_id(), Some(spawned_id));
+    assert!(err.crossed_task_boundary());
+    assert!(
+        err.full_trace()
+            .to_string()
+            .contains(&format!("task: {} (crossed task boundary)", spawned_id))
+    );
+}
+
+#[tokio::test]
+async fn await_at_appends_join_site() {
+    use whereat::JoinHandleAtExt;
+
+    #[derive(Debug)]
+    enum JobError {
+        Failed,
+        Join { panicked: bool },
+    }
+
+    impl From<tokio::task::JoinError> for JobError {
+        fn from(e: tokio::task::JoinError) -> Self {
+            JobError::Join {
+                panicked: e.is_panic(),
+            }
+        }
+    }
+
+    async fn fails() -> Result<u32, At<JobError>> {
+        Err(at(JobError::Failed))
+    }
+
+    async fn panics() -> Result<u32, At<JobError>> {
+        panic!("job exploded")
+    }
+
+    let ok = tokio::spawn(async { Ok::<_, At<JobError>>(7) });
+    assert_eq!(ok.await_at().await.unwrap(), 7);
+
+    let join_line = line!() + 1;
+    let err = tokio::spawn(fails()).await_at().await.unwrap_err();
+    assert!(matches!(err.error(), JobError::Failed));
+    let lines: Vec<_> = err.frames().map(|f| f.location().unwrap().line()).collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1], join_line);
+    assert!(err.crossed_task_boundary());
+
+    let err = tokio::spawn(panics()).await_at().await.unwrap_err();
+    assert!(matches!(err.error(), JobError::Join { panicked: true }));
+    assert_eq!(err.frame_count(), 1);
+
+    let handle = tokio::spawn(async {
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        Ok::<u32, At<JobError>>(0)
+    });
+    handle.abort();
+    let err = handle.await_at().await.unwrap_err();
+    assert!(matches!(err.error(), JobError::Join { panicked: false }));
+}
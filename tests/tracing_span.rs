@@ -0,0 +1,165 @@
+//! Tests for recording `tracing` span ids on frames (the `tracing` feature).
+
+#![cfg(feature = "tracing")]
+
+use tracing_subscriber::Registry;
+use whereat::{At, at};
+
+#[derive(Debug)]
+struct MyError;
+
+fn fails() -> Result<(), At<MyError>> {
+    Err(at(MyError))
+}
+
+// Recording is process-wide, so everything runs in one test
+#[test]
+fn span_ids_are_recorded_per_frame() {
+    let _guard = tracing::subscriber::set_default(Registry::default());
+
+    let outer = tracing::info_span!("outer");
+    let inner = tracing::info_span!("inner");
+    let outer_id = outer.id().unwrap().into_u64();
+    let inner_id = inner.id().unwrap().into_u64();
+
+    // Disabled by default
+    let err = inner.in_scope(fails).unwrap_err();
+    assert_eq!(err.frames().next().unwrap().span_id(), None);
+
+    whereat::set_record_span_ids(true);
+    assert!(whereat::record_span_ids());
+
+    let err = inner.in_scope(fails).unwrap_err();
+    let err = outer.in_scope(|| err.at());
+    let err = err.at(); // outside any span
+
+    let ids: Vec<_> = err.frames().map(|f| f.span_id()).collect();
+    assert_eq!(ids, [Some(inner_id), Some(outer_id), None]);
+
+    let debug = format!("{:?}", err);
+    assert!(debug.contains(&format!("╰─ span: {}", inner_id)));
+
+    // Span ids aren't ordinary text contexts
+    assert_eq!(err.texts().count(), 0);
+
+    whereat::set_record_span_ids(false);
+    let err = inner.in_scope(fails).unwrap_err();
+    assert_eq!(err.frames().next().unwrap().span_id(), None);
+}
+
+// Field recording has its own switch; the span-id test above never installs
+// the fields layer, so the two don't observe each other.
+#[test]
+fn span_fields_become_kv_contexts_on_first_capture() {
+    use tracing_subscriber::layer::SubscriberExt;
+    use whereat::AtSpanFieldsLayer;
+
+    let subscriber = tracing_subscriber::registry().with(AtSpanFieldsLayer::new());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let outer = tracing::info_span!("request", request_id = "r-9", attempt = 1);
+    let inner = outer.in_scope(|| tracing::info_span!("db", table = "users", attempt = 2));
+
+    // Disabled by default
+    assert_eq!(inner.in_scope(fails).unwrap_err().kvs().count(), 0);
+
+    whereat::set_record_span_fields(true);
+    assert!(whereat::record_span_fields());
+
+    inner.record("table", "accounts");
+    let err = inner.in_scope(fails).unwrap_err();
+    // Later frames don't snapshot again
+    let err = outer.in_scope(|| err.at());
+
+    let mut kvs: Vec<_> = err.kvs().collect();
+    kvs.reverse();
+    assert_eq!(
+        kvs,
+        [
+            ("request_id", "r-9"),
+            ("table", "accounts"),
+            ("attempt", "2")
+        ]
+    );
+    let first = err.frames().next().unwrap();
+    assert_eq!(first.contexts().filter(|c| c.as_kv().is_some()).count(), 3);
+
+    let debug = format!("{:?}", err);
+    assert!(debug.contains("       ╰─ request_id=r-9\n"));
+
+    // Outside any span nothing is added
+    assert_eq!(fails().unwrap_err().kvs().count(), 0);
+
+    whereat::set_record_span_fields(false);
+    assert_eq!(inner.in_scope(fails).unwrap_err().kvs().count(), 0);
+}
+
+#[test]
+fn record_as_event_splits_trace_into_fields() {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+    type Fields = Arc<Mutex<Vec<(String, String)>>>;
+
+    struct Capture(Fields);
+
+    impl Visit for Capture {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let entry = (field.name().to_string(), format!("{:?}", value));
+            self.0.lock().unwrap().push(entry);
+        }
+    }
+
+    struct CaptureLayer(Fields);
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+            assert_eq!(event.metadata().target(), "whereat");
+            assert_eq!(*event.metadata().level(), tracing::Level::WARN);
+            event.record(&mut Capture(self.0.clone()));
+        }
+    }
+
+    #[derive(Debug)]
+    enum DbError {
+        NotFound,
+    }
+
+    impl core::fmt::Display for DbError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("user not found")
+        }
+    }
+
+    let fields = Fields::default();
+    let subscriber = tracing_subscriber::registry().with(CaptureLayer(fields.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let err = at(DbError::NotFound)
+        .at_str("loading profile")
+        .at_kv("user_id", 7)
+        .at_skipped_frames()
+        .at_str("handling request");
+    err.record_as_event(tracing::Level::WARN);
+
+    let origin = err.frames().next().unwrap().location().unwrap();
+    let origin = format!("{}:{}:{}", origin.file(), origin.line(), origin.column());
+    let fields = fields.lock().unwrap();
+    let get = |name: &str| {
+        let found = fields.iter().find(|(n, _)| n == name);
+        found
+            .map(|(_, v)| v.as_str())
+            .unwrap_or_else(|| panic!("{}", name))
+    };
+    assert_eq!(get("message"), "user not found");
+    assert_eq!(get("error.kind"), "NotFound");
+    assert_eq!(
+        get("error.fingerprint"),
+        format!("{:016x}", err.fingerprint())
+    );
+    assert_eq!(get("error.location"), origin);
+    assert_eq!(get("error.frames"), format!("{}, [...]", origin));
+    assert_eq!(get("error.contexts"), "loading profile; handling request");
+    assert_eq!(get("error.kvs"), "user_id=7");
+}